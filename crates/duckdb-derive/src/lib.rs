@@ -0,0 +1,145 @@
+//! Derive macros for [duckdb](https://docs.rs/duckdb), enabled with its
+//! `derive` feature.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{ext::IdentExt, parse_macro_input, Data, DeriveInput, Error, Field, Fields, Index, LitStr, Member, Result};
+
+/// Implements `duckdb::ToSqlRow` for a struct, binding its fields as
+/// parameters in declaration order.
+///
+/// Fields marked `#[column(skip)]` are left out.
+///
+/// ```ignore
+/// use duckdb::ToSqlRow;
+///
+/// #[derive(ToSqlRow)]
+/// struct Person {
+///     id: i32,
+///     name: String,
+///     #[column(skip)]
+///     cached_age: Option<u32>,
+/// }
+///
+/// conn.execute("INSERT INTO person VALUES (?, ?)", person.to_params())?;
+/// ```
+#[proc_macro_derive(ToSqlRow, attributes(column))]
+pub fn derive_to_sql_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    to_sql_row(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// Implements `duckdb::FromRow` for a struct with named fields, reading each
+/// field from the column of the same name.
+///
+/// `#[column(rename = "name")]` reads a field from another column, and fields
+/// marked `#[column(skip)]` are set to their `Default`.
+///
+/// ```ignore
+/// use duckdb::FromRow;
+///
+/// #[derive(FromRow)]
+/// struct Person {
+///     id: i32,
+///     #[column(rename = "full_name")]
+///     name: String,
+///     email: Option<String>,
+/// }
+///
+/// let people = stmt.query_map([], Person::from_row)?;
+/// ```
+#[proc_macro_derive(FromRow, attributes(column))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_row(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn to_sql_row(input: DeriveInput) -> Result<TokenStream2> {
+    let mut members = Vec::new();
+    for (i, field) in struct_fields(&input, "ToSqlRow")?.iter().enumerate() {
+        if ColumnAttrs::parse(field)?.skip {
+            continue;
+        }
+        members.push(match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::duckdb::ToSqlRow for #name #ty_generics #where_clause {
+            fn to_params(&self) -> ::duckdb::ParamsFromIter<::std::vec::Vec<&dyn ::duckdb::ToSql>> {
+                ::duckdb::params_from_iter(::std::vec![#(&self.#members as &dyn ::duckdb::ToSql),*])
+            }
+        }
+    })
+}
+
+fn from_row(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = struct_fields(&input, "FromRow")?;
+    let mut inits = Vec::new();
+    for field in fields.iter() {
+        let Some(ident) = &field.ident else {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "FromRow can only be derived for structs with named fields",
+            ));
+        };
+        let attrs = ColumnAttrs::parse(field)?;
+        inits.push(if attrs.skip {
+            quote!(#ident: ::std::default::Default::default())
+        } else {
+            let column = attrs.rename.unwrap_or_else(|| ident.unraw().to_string());
+            quote!(#ident: row.get(#column)?)
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::duckdb::FromRow for #name #ty_generics #where_clause {
+            fn from_row(row: &::duckdb::Row<'_>) -> ::duckdb::Result<Self> {
+                ::std::result::Result::Ok(Self { #(#inits),* })
+            }
+        }
+    })
+}
+
+fn struct_fields<'a>(input: &'a DeriveInput, derive: &str) -> Result<&'a Fields> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        _ => Err(Error::new_spanned(
+            &input.ident,
+            format!("{derive} can only be derived for structs"),
+        )),
+    }
+}
+
+/// The options of a field's `#[column(...)]` attributes.
+#[derive(Default)]
+struct ColumnAttrs {
+    skip: bool,
+    rename: Option<String>,
+}
+
+impl ColumnAttrs {
+    fn parse(field: &Field) -> Result<ColumnAttrs> {
+        let mut attrs = ColumnAttrs::default();
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("column")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    attrs.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    attrs.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported column attribute, expected `skip` or `rename`"))
+                }
+            })?;
+        }
+        Ok(attrs)
+    }
+}
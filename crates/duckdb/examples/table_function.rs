@@ -0,0 +1,102 @@
+// A table function serving rows from a Rust `Vec`
+
+extern crate duckdb;
+
+use duckdb::{
+    vtab::{BindInfo, DataChunk, Free, FunctionInfo, InitInfo, Inserter, LogicalType, LogicalTypeId, VTab},
+    Connection, Result,
+};
+use std::{error::Error, sync::OnceLock};
+
+struct Person {
+    name: String,
+    age: i32,
+}
+
+/// The rows served by the `people` table function.
+static PEOPLE: OnceLock<Vec<Person>> = OnceLock::new();
+
+/// Rows of at most this age are returned.
+#[repr(C)]
+struct PeopleBindData {
+    max_age: i32,
+}
+
+impl Free for PeopleBindData {}
+
+/// The next row of `PEOPLE` to look at.
+#[repr(C)]
+struct PeopleInitData {
+    offset: usize,
+}
+
+impl Free for PeopleInitData {}
+
+struct PeopleVTab;
+
+impl VTab for PeopleVTab {
+    type InitData = PeopleInitData;
+    type BindData = PeopleBindData;
+
+    unsafe fn bind(bind: &BindInfo, data: *mut PeopleBindData) -> Result<(), Box<dyn Error>> {
+        bind.add_result_column("name", LogicalType::new(LogicalTypeId::Varchar));
+        bind.add_result_column("age", LogicalType::new(LogicalTypeId::Integer));
+        (*data).max_age = bind.get_parameter(0).to_int64().try_into()?;
+        Ok(())
+    }
+
+    unsafe fn init(_: &InitInfo, data: *mut PeopleInitData) -> Result<(), Box<dyn Error>> {
+        (*data).offset = 0;
+        Ok(())
+    }
+
+    unsafe fn func(func: &FunctionInfo, output: &mut DataChunk) -> Result<(), Box<dyn Error>> {
+        let init_data = &mut *func.get_init_data::<PeopleInitData>();
+        let max_age = (*func.get_bind_data::<PeopleBindData>()).max_age;
+        let people = PEOPLE.get().map(Vec::as_slice).unwrap_or_default();
+
+        let names = output.flat_vector(0);
+        let mut ages = output.flat_vector(1);
+        let mut len = 0;
+        // A chunk holds at most `capacity` rows; the rest are returned by the
+        // next calls, until a chunk of length 0 ends the scan.
+        while init_data.offset < people.len() && len < names.capacity() {
+            let person = &people[init_data.offset];
+            init_data.offset += 1;
+            if person.age <= max_age {
+                names.insert(len, person.name.as_str());
+                ages.as_mut_slice::<i32>()[len] = person.age;
+                len += 1;
+            }
+        }
+        output.set_len(len);
+        Ok(())
+    }
+
+    fn parameters() -> Option<Vec<LogicalType>> {
+        Some(vec![LogicalType::new(LogicalTypeId::Integer)])
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let people = (0..5000).map(|i| Person {
+        name: format!("person {i}"),
+        age: i % 100,
+    });
+    PEOPLE.get_or_init(|| people.collect());
+
+    let conn = Connection::open_in_memory()?;
+    conn.register_table_function::<PeopleVTab>("people")?;
+
+    let count: i64 = conn.query_row("SELECT count(*) FROM people(17)", [], |row| row.get(0))?;
+    println!("{count} people are 17 or younger");
+
+    let mut stmt = conn.prepare("SELECT name, age FROM people(30) WHERE name LIKE '%4_07' ORDER BY name")?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let name: String = row.get(0)?;
+        let age: i32 = row.get(1)?;
+        println!("{name} is {age}");
+    }
+    Ok(())
+}
@@ -1,15 +1,17 @@
 use super::{ffi, Appender, Result};
 use crate::{
-    error::result_from_duckdb_appender,
     vtab::{record_batch_to_duckdb_data_chunk, to_duckdb_logical_type, DataChunk, LogicalType},
     Error,
 };
 use arrow::record_batch::RecordBatch;
-use ffi::duckdb_append_data_chunk;
 
 impl Appender<'_> {
     /// Append one record_batch
     ///
+    /// The columns of the batch must be in the order of the table's, with
+    /// matching types. Batches of any size are split in data chunks of
+    /// DuckDB's vector size.
+    ///
     /// ## Example
     ///
     /// ```rust,no_run
@@ -24,30 +26,66 @@ impl Appender<'_> {
     ///
     /// # Failure
     ///
-    /// Will return `Err` if append column count not the same with the table schema
-    #[inline]
+    /// Will return `Err` if the batch doesn't have as many columns as the
+    /// table, or if the type of a column doesn't match the table's column at
+    /// the same position, or if a column with nulls is appended to a
+    /// `NOT NULL` column. Columns of the same type in the wrong order can't be
+    /// told apart.
     pub fn append_record_batch(&mut self, record_batch: RecordBatch) -> Result<()> {
         let schema = record_batch.schema();
+        self.check_column_count("record batch", schema.fields().len())?;
+        if record_batch.columns().iter().any(|column| column.null_count() > 0) {
+            for i in self.not_null_columns()? {
+                if record_batch.column(i).null_count() > 0 {
+                    return Err(Error::InvalidAppend(format!(
+                        "column {i} `{}` has nulls, but the table's column {i} is NOT NULL",
+                        schema.field(i).name()
+                    )));
+                }
+            }
+        }
         let mut logical_type: Vec<LogicalType> = vec![];
-        for field in schema.fields() {
+        for (i, field) in schema.fields().iter().enumerate() {
             let logical_t = to_duckdb_logical_type(field.data_type())
                 .map_err(|_op| Error::ArrowTypeToDuckdbType(field.to_string(), field.data_type().clone()))?;
+            let column_t = self.column_type(i);
+            if logical_t.id() != column_t.id() {
+                return Err(Error::InvalidAppend(format!(
+                    "column {i} `{}` is a {logical_t:?}, but the table's column {i} is a {column_t:?}",
+                    field.name()
+                )));
+            }
             logical_type.push(logical_t);
         }
 
         let mut data_chunk = DataChunk::new(&logical_type);
-        record_batch_to_duckdb_data_chunk(&record_batch, &mut data_chunk).map_err(|_op| Error::AppendError)?;
+        let capacity = unsafe { ffi::duckdb_vector_size() } as usize;
+        for offset in (0..record_batch.num_rows()).step_by(capacity) {
+            let len = capacity.min(record_batch.num_rows() - offset);
+            data_chunk.reset();
+            record_batch_to_duckdb_data_chunk(&record_batch.slice(offset, len), &mut data_chunk)
+                .map_err(|err| Error::ToSqlConversionFailure(err.to_string().into()))?;
+            self.append_chunk(&data_chunk)?;
+        }
+        Ok(())
+    }
 
-        let rc = unsafe { duckdb_append_data_chunk(self.app, data_chunk.get_ptr()) };
-        result_from_duckdb_appender(rc, &mut self.app)
+    /// The indices of the `NOT NULL` columns of the table.
+    fn not_null_columns(&self) -> Result<Vec<usize>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT column_index - 1 FROM duckdb_columns() \
+             WHERE database_name = current_database() AND schema_name = ? \
+             AND lower(table_name) = lower(?) AND NOT is_nullable",
+        )?;
+        stmt.iter::<usize>([&self.schema, &self.table])?.collect()
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{Connection, Result};
+    use crate::{Connection, Error, Result};
     use arrow::{
-        array::{Int8Array, StringArray},
+        array::{Int32Array, Int8Array, StringArray},
         datatypes::{DataType, Field, Schema},
         record_batch::RecordBatch,
     };
@@ -79,4 +117,72 @@ mod test {
         assert_eq!(rbs.iter().map(|op| op.num_rows()).sum::<usize>(), 5);
         Ok(())
     }
+
+    #[test]
+    fn test_append_large_record_batch() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(id INTEGER, name VARCHAR)")?;
+
+        let ids = Int32Array::from_iter_values(0..10_000);
+        let names = StringArray::from_iter((0..10_000).map(|i| (i % 3 != 0).then(|| i.to_string())));
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        let record_batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(ids), Arc::new(names)]).unwrap();
+        let mut app = db.appender("foo")?;
+        app.append_record_batch(record_batch)?;
+        app.flush()?;
+
+        let (count, sum, names): (i64, i64, i64) =
+            db.query_row("SELECT count(*), sum(id), count(name) FROM foo", [], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+            })?;
+        assert_eq!((count, sum, names), (10_000, 49_995_000, 6_666));
+        let name: String = db.query_row("SELECT name FROM foo WHERE id = 9998", [], |r| r.get(0))?;
+        assert_eq!(name, "9998");
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_mismatched_record_batch() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(id INTEGER NOT NULL, name VARCHAR)")?;
+        let ids = || Arc::new(Int32Array::from(vec![Some(1), None]));
+        let names = || Arc::new(StringArray::from(vec!["a", "b"]));
+        let id_field = Field::new("id", DataType::Int32, true);
+        let name_field = Field::new("name", DataType::Utf8, true);
+
+        let schema = Schema::new(vec![id_field.clone()]);
+        let record_batch = RecordBatch::try_new(Arc::new(schema), vec![ids()]).unwrap();
+        let err = db.appender("foo")?.append_record_batch(record_batch).unwrap_err();
+        assert!(matches!(err, Error::InvalidAppend(_)), "{err:?}");
+        assert!(err.to_string().contains("1 columns, but the table has 2"), "{err}");
+
+        let schema = Schema::new(vec![name_field.clone(), id_field.clone()]);
+        let record_batch = RecordBatch::try_new(Arc::new(schema), vec![names(), ids()]).unwrap();
+        let err = db.appender("foo")?.append_record_batch(record_batch).unwrap_err();
+        assert!(err.to_string().contains("column 0 `name` is a Varchar"), "{err}");
+
+        let schema = Arc::new(Schema::new(vec![id_field, name_field]));
+        let record_batch = RecordBatch::try_new(schema.clone(), vec![ids(), names()]).unwrap();
+        let err = db.appender("foo")?.append_record_batch(record_batch).unwrap_err();
+        assert!(matches!(err, Error::InvalidAppend(_)), "{err:?}");
+        assert!(
+            err.to_string()
+                .contains("column 0 `id` has nulls, but the table's column 0 is NOT NULL"),
+            "{err}"
+        );
+
+        // Nulls in nullable columns are fine
+        let names = Arc::new(StringArray::from(vec![Some("a"), None]));
+        let ids = Arc::new(Int32Array::from(vec![1, 2]));
+        let record_batch = RecordBatch::try_new(schema, vec![ids, names]).unwrap();
+        let mut app = db.appender("foo")?;
+        app.append_record_batch(record_batch)?;
+        app.flush()?;
+        let count: i64 = db.query_row("SELECT count(*) FROM foo WHERE name IS NULL", [], |r| r.get(0))?;
+        assert_eq!(count, 1);
+        Ok(())
+    }
 }
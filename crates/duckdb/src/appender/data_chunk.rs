@@ -0,0 +1,128 @@
+use super::{ffi, Appender, Result};
+use crate::{
+    error::result_from_duckdb_appender,
+    vtab::{DataChunk, LogicalType},
+    Error,
+};
+
+impl Appender<'_> {
+    /// Append a data chunk filled through the vectors of the `vtab` module,
+    /// e.g. by the code of a table function.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// # use duckdb::vtab::{DataChunk, LogicalType, LogicalTypeId};
+    /// fn insert_squares(conn: &Connection) -> Result<()> {
+    ///     let chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Bigint)]);
+    ///     let mut vector = chunk.flat_vector(0);
+    ///     for (i, square) in vector.as_mut_slice::<i64>()[..100].iter_mut().enumerate() {
+    ///         *square = (i * i) as i64;
+    ///     }
+    ///     chunk.set_len(100);
+    ///
+    ///     let mut app = conn.appender("squares")?;
+    ///     app.append_data_chunk(&chunk)?;
+    ///     app.flush()
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the chunk is empty, which it is until its length
+    /// is set with [`set_len`](DataChunk::set_len), if it doesn't have as many
+    /// columns as the table, or if the type of a column doesn't match the
+    /// table's column at the same position.
+    pub fn append_data_chunk(&mut self, chunk: &DataChunk) -> Result<()> {
+        if chunk.is_empty() {
+            return Err(Error::InvalidAppend(
+                "the data chunk is empty, its length must be set with `DataChunk::set_len`".to_owned(),
+            ));
+        }
+        self.check_column_count("data chunk", chunk.num_columns())?;
+        for i in 0..chunk.num_columns() {
+            let chunk_t = chunk.flat_vector(i).logical_type();
+            let column_t = self.column_type(i);
+            if chunk_t.id() != column_t.id() {
+                return Err(Error::InvalidAppend(format!(
+                    "column {i} is a {chunk_t:?}, but the table's column {i} is a {column_t:?}"
+                )));
+            }
+        }
+        self.append_chunk(chunk)
+    }
+
+    pub(super) fn check_column_count(&self, source: &str, count: usize) -> Result<()> {
+        let column_count = unsafe { ffi::duckdb_appender_column_count(self.app) } as usize;
+        if count != column_count {
+            return Err(Error::InvalidAppend(format!(
+                "the {source} has {count} columns, but the table has {column_count}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// The type of the table's column `idx`.
+    pub(super) fn column_type(&self, idx: usize) -> LogicalType {
+        LogicalType::from(unsafe { ffi::duckdb_appender_column_type(self.app, idx as u64) })
+    }
+
+    pub(super) fn append_chunk(&mut self, chunk: &DataChunk) -> Result<()> {
+        let rc = unsafe { ffi::duckdb_append_data_chunk(self.app, chunk.get_ptr()) };
+        result_from_duckdb_appender(rc, &mut self.app)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        vtab::{DataChunk, Inserter, LogicalType, LogicalTypeId},
+        Connection, Error, Result,
+    };
+
+    #[test]
+    fn test_append_data_chunk() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(id BIGINT, name VARCHAR)")?;
+
+        let chunk = DataChunk::new(&[
+            LogicalType::new(LogicalTypeId::Bigint),
+            LogicalType::new(LogicalTypeId::Varchar),
+        ]);
+        let mut app = db.appender("foo")?;
+        let err = app.append_data_chunk(&chunk).unwrap_err();
+        assert!(matches!(err, Error::InvalidAppend(_)), "{err:?}");
+        assert!(err.to_string().contains("set_len"), "{err}");
+
+        let mut ids = chunk.flat_vector(0);
+        ids.as_mut_slice::<i64>()[..3].copy_from_slice(&[1, 2, 3]);
+        let names = chunk.flat_vector(1);
+        for (i, name) in ["a", "b", "c"].into_iter().enumerate() {
+            names.insert(i, name);
+        }
+        chunk.set_len(3);
+        app.append_data_chunk(&chunk)?;
+        app.flush()?;
+
+        let names = db
+            .prepare("SELECT name FROM foo ORDER BY id")?
+            .query_map([], |r| r.get::<_, String>(0))?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(names, ["a", "b", "c"]);
+
+        let chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Bigint)]);
+        chunk.set_len(1);
+        let err = app.append_data_chunk(&chunk).unwrap_err();
+        assert!(err.to_string().contains("1 columns, but the table has 2"), "{err}");
+
+        let chunk = DataChunk::new(&[
+            LogicalType::new(LogicalTypeId::Varchar),
+            LogicalType::new(LogicalTypeId::Bigint),
+        ]);
+        chunk.set_len(1);
+        let err = app.append_data_chunk(&chunk).unwrap_err();
+        assert!(err.to_string().contains("column 0 is a Varchar"), "{err}");
+        Ok(())
+    }
+}
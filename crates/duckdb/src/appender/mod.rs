@@ -1,9 +1,13 @@
 use super::{ffi, AppenderParams, Connection, Result, ValueRef};
-use std::{ffi::c_void, fmt, os::raw::c_char};
+use std::{
+    ffi::{c_void, CStr},
+    fmt,
+    os::raw::c_char,
+};
 
 use crate::{
-    error::result_from_duckdb_appender,
-    types::{ToSql, ToSqlOutput},
+    error::{error_from_duckdb_code, result_from_duckdb_appender},
+    types::{decimal_to_string, ToSql, ToSqlOutput, Value},
     Error,
 };
 
@@ -11,10 +15,16 @@ use crate::{
 pub struct Appender<'conn> {
     conn: &'conn Connection,
     app: ffi::duckdb_appender,
+    /// The schema and name of the table, as given to
+    /// [`Connection::appender_to_db`].
+    schema: String,
+    table: String,
 }
 
 #[cfg(feature = "appender-arrow")]
 mod arrow;
+#[cfg(feature = "vtab")]
+mod data_chunk;
 
 impl Appender<'_> {
     /// Append multiple rows from Iterator
@@ -70,6 +80,62 @@ impl Appender<'_> {
         result_from_duckdb_appender(rc, &mut self.app)
     }
 
+    /// Append one value to the row being built, in column order. Call
+    /// [`end_row`](Appender::end_row) once a value was appended for every
+    /// column.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn insert_row(conn: &Connection) -> Result<()> {
+    ///     let mut app = conn.appender("foo")?;
+    ///     app.append_value(1)?;
+    ///     app.append_null()?;
+    ///     app.append_value("bar")?;
+    ///     app.end_row()
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the value can't be converted or appended, e.g.
+    /// if the row already has a value for every column. A value is cast to
+    /// the type of its column right away, so a value which doesn't fit the
+    /// table, like `"abc"` for an `INTEGER` column, fails here with DuckDB's
+    /// conversion error rather than when the rows are flushed.
+    #[inline]
+    pub fn append_value<T: ToSql>(&mut self, value: T) -> Result<()> {
+        self.bind_parameter(&value)
+    }
+
+    /// Append a `NULL` to the row being built, see
+    /// [`append_value`](Appender::append_value).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the row already has a value for every column.
+    /// Like DuckDB, a `NULL` in a `NOT NULL` column isn't reported here, but
+    /// when the rows are flushed to the table, which fails with a
+    /// `NOT NULL constraint failed` error.
+    #[inline]
+    pub fn append_null(&mut self) -> Result<()> {
+        let rc = unsafe { ffi::duckdb_append_null(self.app) };
+        self.append_result(rc)
+    }
+
+    /// Finish the row built with [`append_value`](Appender::append_value) and
+    /// [`append_null`](Appender::append_null).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if a value wasn't appended for every column.
+    #[inline]
+    pub fn end_row(&mut self) -> Result<()> {
+        let rc = unsafe { ffi::duckdb_appender_end_row(self.app) };
+        result_from_duckdb_appender(rc, &mut self.app)
+    }
+
     #[inline]
     pub(crate) fn bind_parameters<P>(&mut self, params: P) -> Result<()>
     where
@@ -88,10 +154,18 @@ impl Appender<'_> {
         let ptr = self.app;
         let value = match value {
             ToSqlOutput::Borrowed(v) => v,
+            // nested values have no `ValueRef` representation
+            ToSqlOutput::Owned(
+                ref v @ (Value::Enum(..)
+                | Value::List(..)
+                | Value::Struct(..)
+                | Value::Map(..)
+                | Value::Array(..)
+                | Value::Union(..)),
+            ) => return Err(unsupported_value(&v.data_type().to_string())),
             ToSqlOutput::Owned(ref v) => ValueRef::from(v),
+            ToSqlOutput::List(..) => return Err(unsupported_value("LIST")),
         };
-        // NOTE: we ignore the return value here
-        //       because if anything failed, end_row will fail
         // TODO: append more
         let rc = match value {
             ValueRef::Null => unsafe { ffi::duckdb_append_null(ptr) },
@@ -121,6 +195,12 @@ impl Appender<'_> {
                 ffi::duckdb_append_timestamp(ptr, ffi::duckdb_timestamp { micros: u.to_micros(i) })
             },
             ValueRef::Blob(b) => unsafe { ffi::duckdb_append_blob(ptr, b.as_ptr() as *const c_void, b.len() as u64) },
+            // There's no way to append a decimal, DuckDB casts its text to
+            // the column's type instead.
+            ValueRef::Decimal { value, scale, .. } => unsafe {
+                let s = decimal_to_string(value, scale);
+                ffi::duckdb_append_varchar_length(ptr, s.as_ptr() as *const c_char, s.len() as u64)
+            },
             ValueRef::Date32(d) => unsafe { ffi::duckdb_append_date(ptr, ffi::duckdb_date { days: d }) },
             ValueRef::Time64(u, v) => unsafe {
                 ffi::duckdb_append_time(ptr, ffi::duckdb_time { micros: u.to_micros(v) })
@@ -135,17 +215,42 @@ impl Appender<'_> {
                     },
                 )
             },
-            _ => unreachable!("not supported"),
+            _ => return Err(unsupported_value(&value.try_data_type()?.to_string())),
         };
-        if rc != 0 {
+        self.append_result(rc)
+    }
+
+    /// Turns the result of appending a value into an error carrying DuckDB's
+    /// message, e.g. why a value couldn't be cast to its column's type.
+    /// Unlike [`result_from_duckdb_appender`], this keeps the appender alive.
+    fn append_result(&self, rc: ffi::duckdb_state) -> Result<()> {
+        if rc == ffi::DuckDBSuccess {
+            return Ok(());
+        }
+        if self.app.is_null() {
             return Err(Error::AppendError);
         }
-        Ok(())
+        let c_err = unsafe { ffi::duckdb_appender_error(self.app) };
+        if c_err.is_null() {
+            return Err(Error::AppendError);
+        }
+        let message = unsafe { CStr::from_ptr(c_err) }.to_string_lossy().into_owned();
+        error_from_duckdb_code(rc, Some(message))
     }
 
     #[inline]
-    pub(super) fn new(conn: &Connection, app: ffi::duckdb_appender) -> Appender<'_> {
-        Appender { conn, app }
+    pub(super) fn new<'conn>(
+        conn: &'conn Connection,
+        app: ffi::duckdb_appender,
+        schema: &str,
+        table: &str,
+    ) -> Appender<'conn> {
+        Appender {
+            conn,
+            app,
+            schema: schema.to_owned(),
+            table: table.to_owned(),
+        }
     }
 
     /// Flush data into DB
@@ -158,6 +263,10 @@ impl Appender<'_> {
     }
 }
 
+fn unsupported_value(ty: &str) -> Error {
+    Error::ToSqlConversionFailure(format!("{ty} values can't be appended").into())
+}
+
 impl Drop for Appender<'_> {
     fn drop(&mut self) {
         if !self.app.is_null() {
@@ -172,7 +281,11 @@ impl Drop for Appender<'_> {
 
 impl fmt::Debug for Appender<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Appender").field("conn", self.conn).finish()
+        f.debug_struct("Appender")
+            .field("conn", self.conn)
+            .field("schema", &self.schema)
+            .field("table", &self.table)
+            .finish()
     }
 }
 
@@ -286,6 +399,73 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_append_null() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER, y TEXT, z INTEGER NOT NULL)")?;
+
+        {
+            let mut app = db.appender("foo")?;
+            app.append_null()?;
+            app.append_value("bar")?;
+            app.append_value(1)?;
+            app.end_row()?;
+            app.append_value(2)?;
+            app.append_null()?;
+            app.append_value(3)?;
+            app.end_row()?;
+        }
+        let rows = db
+            .prepare("SELECT x, y FROM foo ORDER BY z")?
+            .query_map([], |row| <(Option<i32>, Option<String>)>::try_from(row))?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(rows, [(None, Some("bar".to_owned())), (Some(2), None)]);
+
+        let mut app = db.appender("foo")?;
+        app.append_value(4)?;
+        app.append_value("baz")?;
+        app.append_null()?;
+        app.end_row()?;
+        let err = app.flush().unwrap_err();
+        assert!(err.to_string().contains("NOT NULL constraint failed"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_type_mismatch() -> Result<()> {
+        use crate::{types::Value, Error};
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER, d DECIMAL(5, 2))")?;
+
+        let mut app = db.appender("foo")?;
+        let err = app.append_value("abc").unwrap_err();
+        assert!(matches!(err, Error::DuckDBFailure(..)), "{err:?}");
+        assert!(err.to_string().contains("abc"), "{err}");
+
+        let err = app.append_value(Value::List(vec![Value::Int(1)])).unwrap_err();
+        assert!(matches!(err, Error::ToSqlConversionFailure(_)), "{err:?}");
+        let err = app
+            .append_value(Value::Struct(vec![("a".to_owned(), Value::Int(1))].into()))
+            .unwrap_err();
+        assert!(matches!(err, Error::ToSqlConversionFailure(_)), "{err:?}");
+        let err = app.append_value(Value::Array(vec![Value::Int(1)])).unwrap_err();
+        assert!(matches!(err, Error::ToSqlConversionFailure(_)), "{err:?}");
+
+        app.append_value(1)?;
+        app.append_value(Value::Decimal {
+            value: 12345,
+            width: 5,
+            scale: 2,
+        })?;
+        app.end_row()?;
+        app.flush()?;
+
+        let d: String = db.query_row("SELECT d::TEXT FROM foo", [], |r| r.get(0))?;
+        assert_eq!(d, "123.45");
+        Ok(())
+    }
+
     #[test]
     fn test_appender_error() -> Result<(), crate::Error> {
         let conn = Connection::open_in_memory()?;
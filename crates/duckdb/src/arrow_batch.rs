@@ -1,6 +1,7 @@
 use super::{
     arrow::{datatypes::SchemaRef, record_batch::RecordBatch},
-    Statement,
+    raw_statement::RawStatement,
+    Result, Statement,
 };
 
 /// An handle for the resulting RecordBatch of a query.
@@ -29,3 +30,52 @@ impl<'stmt> Iterator for Arrow<'stmt> {
         Some(RecordBatch::from(&self.stmt?.step()?))
     }
 }
+
+/// An iterator over the RecordBatches of a streaming result, see
+/// [`Statement::query_arrow_streaming`].
+///
+/// Each batch is fetched from DuckDB when the iterator is advanced. The
+/// result is released when the iterator is dropped.
+#[must_use = "ArrowStream is lazy and will do nothing unless consumed"]
+pub struct ArrowStream<'stmt> {
+    stmt: &'stmt mut RawStatement,
+    done: bool,
+}
+
+impl<'stmt> ArrowStream<'stmt> {
+    #[inline]
+    pub(crate) fn new(stmt: &'stmt mut RawStatement) -> ArrowStream<'stmt> {
+        ArrowStream { stmt, done: false }
+    }
+
+    /// return arrow schema
+    #[inline]
+    pub fn get_schema(&self) -> SchemaRef {
+        self.stmt.schema()
+    }
+}
+
+impl Iterator for ArrowStream<'_> {
+    type Item = Result<RecordBatch>;
+
+    /// Returns the next batch, or the error that ended the result early.
+    /// Nothing is returned after an error.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.stmt.step() {
+            Some(array) => Some(Ok(RecordBatch::from(&array))),
+            None => {
+                self.done = true;
+                self.stmt.result_error().err().map(Err)
+            }
+        }
+    }
+}
+
+impl Drop for ArrowStream<'_> {
+    fn drop(&mut self) {
+        self.stmt.reset_result();
+    }
+}
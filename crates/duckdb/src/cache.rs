@@ -149,13 +149,16 @@ impl StatementCache {
         })
     }
 
-    // Return a statement to the cache.
-    fn cache_stmt(&self, stmt: RawStatement) {
+    // Return a statement to the cache, without the bindings, the result and
+    // the streaming setting of its last use.
+    fn cache_stmt(&self, mut stmt: RawStatement) {
         if stmt.is_null() {
             return;
         }
         let mut cache = self.0.borrow_mut();
         stmt.clear_bindings();
+        stmt.reset_result();
+        stmt.set_streaming(false);
         if let Some(sql) = stmt.statement_cache_key() {
             cache.insert(sql, stmt);
         } else {
@@ -306,6 +309,28 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_cached_statement_is_reset() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let sql = "SELECT ?::INTEGER + 1";
+        {
+            let mut stmt = db.prepare_cached(sql)?;
+            stmt.set_fetch_streaming(true);
+            assert_eq!(stmt.query_row([1], |r| r.get::<_, i32>(0))?, 2);
+            assert!((*stmt).stmt.is_streaming());
+        }
+        assert_eq!(1, db.cache.len());
+
+        let mut stmt = db.prepare_cached(sql)?;
+        // The result is released and the parameter unbound
+        assert!(!(*stmt).stmt.is_streaming());
+        assert!((*stmt).stmt.execute().is_err());
+        // The statement isn't streaming anymore
+        assert_eq!(stmt.query_row([2], |r| r.get::<_, i32>(0))?, 3);
+        assert!(!(*stmt).stmt.is_streaming());
+        Ok(())
+    }
+
     #[test]
     fn test_connection_close() -> Result<()> {
         let conn = Connection::open_in_memory()?;
@@ -1,8 +1,12 @@
 use std::str;
 
-use arrow::datatypes::DataType;
+use arrow::datatypes::SchemaRef;
 
-use crate::{Error, Result, Statement};
+use crate::{
+    ffi, params_from_iter, subquery,
+    types::{Type, Value},
+    Error, Result, Statement, StatementType,
+};
 
 /// Information about a column of a DuckDB query.
 #[derive(Debug)]
@@ -25,22 +29,117 @@ impl Column<'_> {
     }
 }
 
+/// A column of the result of a query, see [`Statement::describe`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnDescription {
+    name: String,
+    data_type: Type,
+}
+
+impl ColumnDescription {
+    /// Returns the name of the column.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the column, [`Type::Any`] if it depends on the
+    /// types of the values bound to the parameters.
+    #[inline]
+    pub fn data_type(&self) -> &Type {
+        &self.data_type
+    }
+}
+
 impl Statement<'_> {
+    /// Describes the columns of the result of the query without executing it.
+    /// Once the statement has been executed, the columns of its result are
+    /// returned instead.
+    ///
+    /// DuckDB doesn't describe the result of a prepared statement, so the
+    /// query is run wrapped in a `LIMIT 0`. A parameter whose type is inferred
+    /// from the query, e.g. in `LIMIT ?`, is bound to a value of that type.
+    /// Other parameters take the type of the value bound to them, e.g. in
+    /// `SELECT ? AS x`: the query is run twice with values of different types,
+    /// and the columns whose type changes are reported as [`Type::Any`].
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn print_columns(conn: &Connection) -> Result<()> {
+    ///     let stmt = conn.prepare("SELECT id, name FROM people WHERE age > ?")?;
+    ///     for column in stmt.describe()? {
+    ///         println!("{}: {}", column.name(), column.data_type());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return [`Error::InvalidQuery`] if the statement isn't a query and
+    /// hasn't been executed, e.g. an `INSERT ... RETURNING`, and `Err` if the
+    /// query fails with the values bound to its parameters, e.g. for
+    /// `read_parquet(?)` whose columns are those of the file.
+    pub fn describe(&self) -> Result<Vec<ColumnDescription>> {
+        match self.stmt.executed_schema() {
+            Some(schema) => describe_schema(schema),
+            None => self.prepared_columns().map(<[_]>::to_vec),
+        }
+    }
+
+    // The columns of the query before it's executed, described once and
+    // cached for the accessors below.
+    fn prepared_columns(&self) -> Result<&[ColumnDescription]> {
+        if let Some(columns) = self.described.get() {
+            return Ok(columns);
+        }
+        if self.statement_type() != StatementType::Select {
+            return Err(Error::InvalidQuery);
+        }
+        let sql = self.stmt.sql().ok_or(Error::InvalidQuery)?.to_str()?;
+        let mut stmt = self.conn.prepare(&format!("SELECT * FROM {} LIMIT 0", subquery(sql)))?;
+        let types: Vec<_> = (1..=stmt.parameter_count())
+            .map(|i| stmt.stmt.parameter_type(i))
+            .collect();
+        let params = |untyped: Value| {
+            let values = types.iter().map(move |&ty| match ty {
+                ffi::DUCKDB_TYPE_DUCKDB_TYPE_INVALID => untyped.clone(),
+                ty => placeholder(ty),
+            });
+            params_from_iter(values)
+        };
+        stmt.execute(params(Value::Int(0)))?;
+        let mut columns = describe_schema(&stmt.stmt.schema())?;
+        if types.contains(&ffi::DUCKDB_TYPE_DUCKDB_TYPE_INVALID) {
+            let probe = stmt
+                .execute(params(Value::BigInt(0)))
+                .and_then(|_| describe_schema(&stmt.stmt.schema()))
+                .unwrap_or_default();
+            for (i, column) in columns.iter_mut().enumerate() {
+                if probe.get(i).map(|c| &c.data_type) != Some(&column.data_type) {
+                    column.data_type = Type::Any;
+                }
+            }
+        }
+        Ok(self.described.get_or_init(|| columns))
+    }
+
     /// Get all the column names in the result set of the prepared statement.
     ///
     /// If associated DB schema can be altered concurrently, you should make
     /// sure that current statement has already been stepped once before
     /// calling this method.
     ///
-    /// # Caveats
-    /// Panics if the query has not been [`execute`](Statement::execute)d yet.
+    /// Until the statement has been executed, the names are those given by
+    /// [`describe`](Statement::describe), and the list is empty if it fails.
     pub fn column_names(&self) -> Vec<String> {
-        self.stmt
-            .schema()
-            .fields()
-            .iter()
-            .map(|f| f.name().to_owned())
-            .collect()
+        match self.stmt.executed_schema() {
+            Some(schema) => schema.fields().iter().map(|f| f.name().to_owned()).collect(),
+            None => self
+                .prepared_columns()
+                .map(|columns| columns.iter().map(|c| c.name.clone()).collect())
+                .unwrap_or_default(),
+        }
     }
 
     /// Return the number of columns in the result set returned by the prepared
@@ -49,9 +148,15 @@ impl Statement<'_> {
     /// If associated DB schema can be altered concurrently, you should make
     /// sure that current statement has already been stepped once before
     /// calling this method.
+    ///
+    /// Until the statement has been executed, it's the number of columns given
+    /// by [`describe`](Statement::describe), and 0 if it fails.
     #[inline]
     pub fn column_count(&self) -> usize {
-        self.stmt.column_count()
+        match self.stmt.executed_schema() {
+            Some(schema) => schema.fields().len(),
+            None => self.prepared_columns().map_or(0, <[_]>::len),
+        }
     }
 
     /// Check that column name reference lifetime is limited:
@@ -92,12 +197,15 @@ impl Statement<'_> {
     /// Returns an `Error::InvalidColumnIndex` if `idx` is outside the valid
     /// column range for this row.
     ///
-    /// # Caveats
-    /// Panics if the query has not been [`execute`](Statement::execute)d yet
-    /// or when column name is not valid UTF-8.
+    /// Until the statement has been executed, returns the error of
+    /// [`describe`](Statement::describe) if it fails.
     #[inline]
     pub fn column_name(&self, col: usize) -> Result<&String> {
-        self.stmt.column_name(col).ok_or(Error::InvalidColumnIndex(col))
+        match self.stmt.executed_schema() {
+            Some(schema) => schema.fields().get(col).map(|field| field.name()),
+            None => self.prepared_columns()?.get(col).map(|column| &column.name),
+        }
+        .ok_or(Error::InvalidColumnIndex(col))
     }
 
     /// Returns the column index in the result set for a given column name.
@@ -113,29 +221,43 @@ impl Statement<'_> {
     ///
     /// Will return an `Error::InvalidColumnName` when there is no column with
     /// the specified `name`.
-    ///
-    /// # Caveats
-    /// Panics if the query has not been [`execute`](Statement::execute)d yet.
     #[inline]
     pub fn column_index(&self, name: &str) -> Result<usize> {
         let n = self.column_count();
         for i in 0..n {
             // Note: `column_name` is only fallible if `i` is out of bounds,
             // which we've already checked.
-            if name.eq_ignore_ascii_case(self.stmt.column_name(i).unwrap()) {
+            if name.eq_ignore_ascii_case(self.column_name(i).unwrap()) {
                 return Ok(i);
             }
         }
         Err(Error::InvalidColumnName(String::from(name)))
     }
 
-    /// Returns the declared data type of the column.
+    /// Returns the data type of the column.
     ///
-    /// # Caveats
-    /// Panics if the query has not been [`execute`](Statement::execute)d yet.
+    /// Until the statement has been executed, it's the type given by
+    /// [`describe`](Statement::describe), [`Type::Any`] if it depends on the
+    /// types of the values bound to the parameters.
+    ///
+    /// # Failure
+    ///
+    /// Returns an `Error::InvalidColumnIndex` if `idx` is out of bounds, and
+    /// until the statement has been executed, the error of
+    /// [`describe`](Statement::describe) if it fails.
     #[inline]
-    pub fn column_type(&self, idx: usize) -> DataType {
-        self.stmt.column_type(idx)
+    pub fn column_type(&self, idx: usize) -> Result<Type> {
+        match self.stmt.executed_schema() {
+            Some(schema) => match schema.fields().get(idx) {
+                Some(field) => Type::try_from_arrow(field.data_type()),
+                None => Err(Error::InvalidColumnIndex(idx)),
+            },
+            None => self
+                .prepared_columns()?
+                .get(idx)
+                .map(|column| column.data_type.clone())
+                .ok_or(Error::InvalidColumnIndex(idx)),
+        }
     }
 
     /// Returns a slice describing the columns of the result of the query.
@@ -158,9 +280,44 @@ impl Statement<'_> {
     }
 }
 
+fn describe_schema(schema: &SchemaRef) -> Result<Vec<ColumnDescription>> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            Ok(ColumnDescription {
+                name: field.name().to_owned(),
+                data_type: Type::try_from_arrow(field.data_type())?,
+            })
+        })
+        .collect()
+}
+
+// A value of the DuckDB type `ty`, bound to a parameter of that type while
+// describing a query. `NULL` would make DuckDB bind the query again with an
+// untyped parameter, which fails e.g. for `LIMIT ?` or `range(?)`.
+fn placeholder(ty: ffi::duckdb_type) -> Value {
+    match ty {
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN => Value::Boolean(false),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_TINYINT => Value::TinyInt(0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT => Value::SmallInt(0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_INTEGER => Value::Int(0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_BIGINT => Value::BigInt(0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT => Value::HugeInt(0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => Value::UTinyInt(0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => Value::USmallInt(0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER => Value::UInt(0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_UBIGINT => Value::UBigInt(0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_FLOAT => Value::Float(0.0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE => Value::Double(0.0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR => Value::Text(String::new()),
+        _ => Value::Null,
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::{Connection, Result};
+    use crate::{params, Connection, Result};
 
     #[test]
     #[cfg(feature = "column_decltype")]
@@ -183,6 +340,49 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_describe() -> Result<()> {
+        use crate::{types::Type, Error};
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER, y TEXT); INSERT INTO foo VALUES (1, 'a')")?;
+        let stmt = db.prepare("SELECT x + $1::INTEGER AS sum, y, [y] AS l FROM foo WHERE y = $2 -- comment;")?;
+        let columns = stmt.describe()?;
+        let names: Vec<&str> = columns.iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["sum", "y", "l"]);
+        assert_eq!(columns[0].data_type(), &Type::Int);
+        assert_eq!(columns[1].data_type(), &Type::Text);
+        assert_eq!(columns[2].data_type(), &Type::List(Box::new(Type::Text)));
+        assert_eq!(stmt.column_count(), 3);
+        assert_eq!(stmt.column_names(), ["sum", "y", "l"]);
+        assert_eq!(stmt.column_index("l")?, 2);
+        assert_eq!(stmt.column_type(0)?, Type::Int);
+        assert_eq!(stmt.column_name(3), Err(Error::InvalidColumnIndex(3)));
+
+        // The type of `?` is the one of the value bound to it
+        let mut stmt = db.prepare("SELECT ? AS x, x + ? AS sum, y FROM foo")?;
+        assert_eq!(stmt.column_type(0)?, Type::Any);
+        assert_eq!(stmt.column_type(1)?, Type::Any);
+        assert_eq!(stmt.column_type(2)?, Type::Text);
+        stmt.execute(params!["a", 1i64])?;
+        assert_eq!(stmt.column_type(0)?, Type::Text);
+        assert_eq!(stmt.column_type(1)?, Type::BigInt);
+
+        // Parameters which can't be NULL
+        let stmt = db.prepare("SELECT * FROM range(?) LIMIT ?")?;
+        assert_eq!(stmt.column_name(0)?, "range");
+        assert_eq!(stmt.column_type(0)?, Type::BigInt);
+
+        let mut stmt = db.prepare("INSERT INTO foo VALUES (2, 'b') RETURNING y")?;
+        assert_eq!(stmt.describe(), Err(Error::InvalidQuery));
+        assert_eq!(stmt.column_count(), 0);
+        assert_eq!(stmt.column_type(0), Err(Error::InvalidQuery));
+        stmt.execute([])?;
+        assert_eq!(stmt.describe()?[0].name(), "y");
+        assert_eq!(db.query_row("SELECT count(*) FROM foo", [], |r| r.get::<_, i64>(0))?, 2);
+        Ok(())
+    }
+
     #[test]
     fn test_column_name_in_error() -> Result<()> {
         use crate::{types::Type, Error};
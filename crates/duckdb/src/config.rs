@@ -101,12 +101,46 @@ impl Config {
         Ok(self)
     }
 
+    /// The maximum memory of the system (e.g. 2GB), the same setting as
+    /// [`max_memory`](Config::max_memory) under DuckDB's primary name.
+    pub fn memory_limit(mut self, limit: &str) -> Result<Config> {
+        self.set("memory_limit", limit)?;
+        Ok(self)
+    }
+
     /// The number of total threads used by the system
     pub fn threads(mut self, thread_num: i64) -> Result<Config> {
         self.set("threads", &thread_num.to_string())?;
         Ok(self)
     }
 
+    /// Whether query results keep the order rows were inserted or read in when
+    /// there is no `ORDER BY` (default true).
+    ///
+    /// Disabling this lets bulk loads, `COPY` and parquet exports run in
+    /// parallel with less memory, but then queries without an `ORDER BY`,
+    /// including `SELECT * FROM tbl` and `COPY tbl TO ...`, may return rows in
+    /// any order.
+    pub fn preserve_insertion_order(mut self, enabled: bool) -> Result<Config> {
+        self.set("preserve_insertion_order", &enabled.to_string())?;
+        Ok(self)
+    }
+
+    /// The size of the write-ahead log at which it is checkpointed into the
+    /// database file (e.g. 16MB). Raising it speeds up bulk loads made of many
+    /// small transactions, at the cost of a larger WAL to replay on restart.
+    pub fn checkpoint_threshold(mut self, size: &str) -> Result<Config> {
+        self.set("checkpoint_threshold", size)?;
+        Ok(self)
+    }
+
+    /// The directory to spill to when data doesn't fit in memory. Defaults to
+    /// `<database>.tmp`, or `.tmp` for in-memory databases.
+    pub fn temp_directory(mut self, directory: &str) -> Result<Config> {
+        self.set("temp_directory", directory)?;
+        Ok(self)
+    }
+
     /// Add any setting to the config. DuckDB will return an error if the setting is unknown or
     /// otherwise invalid.
     pub fn with(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Result<Config> {
@@ -121,8 +155,8 @@ impl Config {
             assert_eq!(state, ffi::DuckDBSuccess);
             self.config = Some(config);
         }
-        let c_key = CString::new(key).unwrap();
-        let c_value = CString::new(value).unwrap();
+        let c_key = CString::new(key)?;
+        let c_value = CString::new(value)?;
         let state = unsafe {
             ffi::duckdb_set_config(
                 self.config.unwrap(),
@@ -218,6 +252,49 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_bulk_load_config() -> Result<()> {
+        let config = Config::default()
+            .preserve_insertion_order(false)?
+            .checkpoint_threshold("64MiB")?
+            .temp_directory("duckdb_spill")?;
+        let db = Connection::open_in_memory_with_flags(config)?;
+        let settings: (bool, String, String) = db.query_row(
+            "SELECT current_setting('preserve_insertion_order'), current_setting('checkpoint_threshold'), \
+             current_setting('temp_directory')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )?;
+        assert_eq!(settings, (false, "64.0 MiB".to_owned(), "duckdb_spill".to_owned()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_with_config() -> Result<()> {
+        let dir = tempdir::TempDir::new("duckdb_config")?;
+        let path = dir.path().join("config.db");
+        Connection::open(&path)?.execute_batch("CREATE TABLE foo (x INTEGER)")?;
+
+        let config = Config::default()
+            .threads(3)?
+            .memory_limit("1GB")?
+            .access_mode(crate::AccessMode::ReadOnly)?;
+        let db = Connection::open_with_flags(&path, config)?;
+        let settings: (i64, String, String) = db.query_row(
+            "SELECT current_setting('threads'), current_setting('memory_limit'), current_setting('access_mode')",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )?;
+        assert_eq!(settings, (3, "953.6 MiB".to_owned(), "read_only".to_owned()));
+        assert!(db.execute_batch("INSERT INTO foo VALUES (1)").is_err());
+
+        assert!(matches!(
+            Config::default().with("threads", "1\0"),
+            Err(crate::Error::NulError(_))
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_invalid_setting() -> Result<()> {
         let config = Config::default().with("some-invalid-setting", "true")?;
@@ -1,11 +1,12 @@
-use arrow::datatypes::DataType;
+use arrow::{datatypes::DataType, error::ArrowError};
 
 use super::Result;
 use crate::{
     ffi,
     types::{FromSqlError, Type},
+    StatementType,
 };
-use std::{error, ffi::CStr, fmt, path::PathBuf, str};
+use std::{error, ffi::CStr, fmt, io, path::PathBuf, str};
 
 /// Enum listing possible errors from duckdb.
 #[derive(Debug)]
@@ -63,6 +64,10 @@ pub enum Error {
     /// Error when datatype to duckdb type
     ArrowTypeToDuckdbType(String, DataType),
 
+    /// Error when an Arrow data type has no corresponding DuckDB
+    /// [`Type`](crate::types::Type).
+    UnsupportedArrowType(DataType),
+
     /// Error when a query that was expected to insert one row did not insert
     /// any or insert many.
     StatementChangedRows(usize),
@@ -83,6 +88,80 @@ pub enum Error {
 
     /// Append Error
     AppendError,
+
+    /// Error when an Arrow record batch or a data chunk can't be appended to a
+    /// table, e.g. because its columns don't match the table's, see
+    /// `Appender::append_record_batch` and `Appender::append_data_chunk` of
+    /// the `appender-arrow` and `vtab` features. The message tells what's wrong.
+    InvalidAppend(String),
+
+    /// Error when a streaming result is iterated again after it has already
+    /// been consumed. See [`Statement::set_fetch_streaming`](crate::Statement::set_fetch_streaming).
+    StreamingResultConsumed,
+
+    /// Error when a statement passed to
+    /// [`execute_read_only`](crate::Connection::execute_read_only) is not a
+    /// pure read. The associated value is the kind of the rejected statement.
+    ReadOnlyViolation(StatementType),
+
+    /// Error when writing query results out fails, e.g. in
+    /// [`write_json`](crate::Connection::write_json).
+    Io(io::Error),
+
+    /// Error when [`install_extension`](crate::Connection::install_extension),
+    /// [`load_extension`](crate::Connection::load_extension) or
+    /// [`load_extension_from_path`](crate::Connection::load_extension_from_path)
+    /// fails. The `message` is DuckDB's error.
+    ExtensionError {
+        /// The name or path of the extension.
+        name: String,
+        /// Why the extension couldn't be installed or loaded.
+        reason: ExtensionErrorReason,
+        /// The error reported by DuckDB.
+        message: String,
+    },
+
+    /// Error when a value can't be converted to another type, e.g. by
+    /// [`Value::coerce_to`](crate::types::Value::coerce_to) or
+    /// `EnumVector::insert_label` of the `vtab` feature.
+    /// The associated values are the type of the value, the requested type and
+    /// why the conversion failed.
+    InvalidCoercion(Type, Type, String),
+
+    /// Error when a query is interrupted by
+    /// [`InterruptHandle::interrupt`](crate::InterruptHandle::interrupt).
+    Interrupted,
+
+    /// Error when values don't fit in a vector of a table function, e.g.
+    /// because there are too many of them or their size isn't the width of
+    /// its elements, see `FlatVector::copy` of the `vtab` feature. The
+    /// message tells what's wrong.
+    InvalidVectorWrite(String),
+
+    /// Error from Arrow, e.g. when a chunk of a result can't be imported.
+    ArrowError(ArrowError),
+
+    /// Error from polars, e.g. when a result can't be converted to a
+    /// `DataFrame`.
+    #[cfg(feature = "polars")]
+    PolarsError(polars::prelude::PolarsError),
+}
+
+/// Why an extension couldn't be installed or loaded, see
+/// [`Error::ExtensionError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExtensionErrorReason {
+    /// The extension isn't installed, doesn't exist in the repository, or the
+    /// file doesn't exist.
+    NotFound,
+    /// The extension's signature is missing or invalid, and unsigned
+    /// extensions aren't allowed.
+    InvalidSignature,
+    /// The repository couldn't be reached, e.g. when offline.
+    Network,
+    /// Any other failure, such as a file built for another DuckDB version.
+    Other,
 }
 
 impl PartialEq for Error {
@@ -101,8 +180,27 @@ impl PartialEq for Error {
             (Error::InvalidColumnType(i1, n1, t1), Error::InvalidColumnType(i2, n2, t2)) => {
                 i1 == i2 && t1 == t2 && n1 == n2
             }
+            (Error::UnsupportedArrowType(t1), Error::UnsupportedArrowType(t2)) => t1 == t2,
             (Error::StatementChangedRows(n1), Error::StatementChangedRows(n2)) => n1 == n2,
             (Error::InvalidParameterCount(i1, n1), Error::InvalidParameterCount(i2, n2)) => i1 == i2 && n1 == n2,
+            (Error::StreamingResultConsumed, Error::StreamingResultConsumed) => true,
+            (Error::ReadOnlyViolation(t1), Error::ReadOnlyViolation(t2)) => t1 == t2,
+            (Error::Interrupted, Error::Interrupted) => true,
+            (Error::InvalidCoercion(f1, t1, r1), Error::InvalidCoercion(f2, t2, r2)) => {
+                f1 == f2 && t1 == t2 && r1 == r2
+            }
+            (
+                Error::ExtensionError {
+                    name: n1,
+                    reason: r1,
+                    message: m1,
+                },
+                Error::ExtensionError {
+                    name: n2,
+                    reason: r2,
+                    message: m2,
+                },
+            ) => n1 == n2 && r1 == r2 && m1 == m2,
             (..) => false,
         }
     }
@@ -115,6 +213,28 @@ impl From<str::Utf8Error> for Error {
     }
 }
 
+impl From<io::Error> for Error {
+    #[cold]
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<ArrowError> for Error {
+    #[cold]
+    fn from(err: ArrowError) -> Error {
+        Error::ArrowError(err)
+    }
+}
+
+#[cfg(feature = "polars")]
+impl From<polars::prelude::PolarsError> for Error {
+    #[cold]
+    fn from(err: polars::prelude::PolarsError) -> Error {
+        Error::PolarsError(err)
+    }
+}
+
 impl From<::std::ffi::NulError> for Error {
     #[cold]
     fn from(err: ::std::ffi::NulError) -> Error {
@@ -178,6 +298,7 @@ impl fmt::Display for Error {
             Error::ArrowTypeToDuckdbType(ref name, ref t) => {
                 write!(f, "Invalid column type {t} , name: {name}")
             }
+            Error::UnsupportedArrowType(ref t) => write!(f, "Unsupported arrow type: {t}"),
             Error::InvalidParameterCount(i1, n1) => {
                 write!(f, "Wrong number of parameters passed to query. Got {i1}, needed {n1}")
             }
@@ -186,6 +307,21 @@ impl fmt::Display for Error {
             Error::InvalidQuery => write!(f, "Query is not read-only"),
             Error::MultipleStatement => write!(f, "Multiple statements provided"),
             Error::AppendError => write!(f, "Append error"),
+            Error::InvalidAppend(ref msg) => write!(f, "Invalid append: {msg}"),
+            Error::StreamingResultConsumed => write!(f, "Streaming result has already been consumed"),
+            Error::ReadOnlyViolation(t) => write!(f, "Statement is not read-only: {t:?}"),
+            Error::Io(ref err) => err.fmt(f),
+            Error::ExtensionError {
+                ref name, ref message, ..
+            } => write!(f, "Extension {name} failed: {message}"),
+            Error::InvalidCoercion(ref from, ref to, ref reason) => {
+                write!(f, "Cannot coerce {from} value to {to}: {reason}")
+            }
+            Error::Interrupted => write!(f, "Query interrupted"),
+            Error::InvalidVectorWrite(ref msg) => write!(f, "Invalid vector write: {msg}"),
+            Error::ArrowError(ref err) => err.fmt(f),
+            #[cfg(feature = "polars")]
+            Error::PolarsError(ref err) => err.fmt(f),
         }
     }
 }
@@ -196,6 +332,10 @@ impl error::Error for Error {
             Error::DuckDBFailure(ref err, _) => Some(err),
             Error::Utf8Error(ref err) => Some(err),
             Error::NulError(ref err) => Some(err),
+            Error::Io(ref err) => Some(err),
+            Error::ArrowError(ref err) => Some(err),
+            #[cfg(feature = "polars")]
+            Error::PolarsError(ref err) => Some(err),
 
             Error::IntegralValueOutOfRange(..)
             | Error::InvalidParameterName(_)
@@ -209,7 +349,15 @@ impl error::Error for Error {
             | Error::StatementChangedRows(_)
             | Error::InvalidQuery
             | Error::AppendError
+            | Error::InvalidAppend(_)
+            | Error::StreamingResultConsumed
+            | Error::ReadOnlyViolation(_)
+            | Error::ExtensionError { .. }
+            | Error::InvalidCoercion(..)
+            | Error::Interrupted
+            | Error::InvalidVectorWrite(_)
             | Error::ArrowTypeToDuckdbType(..)
+            | Error::UnsupportedArrowType(_)
             | Error::MultipleStatement => None,
             Error::FromSqlConversionFailure(_, _, ref err) | Error::ToSqlConversionFailure(ref err) => Some(&**err),
         }
@@ -219,7 +367,10 @@ impl error::Error for Error {
 // These are public but not re-exported by lib.rs, so only visible within crate.
 
 #[inline]
-fn error_from_duckdb_code(code: ffi::duckdb_state, message: Option<String>) -> Result<()> {
+pub fn error_from_duckdb_code(code: ffi::duckdb_state, message: Option<String>) -> Result<()> {
+    if message.as_deref().is_some_and(|m| m.starts_with("INTERRUPT Error")) {
+        return Err(Error::Interrupted);
+    }
     Err(Error::DuckDBFailure(ffi::Error::new(code), message))
 }
 
@@ -279,3 +430,40 @@ pub fn result_from_duckdb_arrow(code: ffi::duckdb_state, mut out: ffi::duckdb_ar
         error_from_duckdb_code(code, message)
     }
 }
+
+#[cold]
+#[inline]
+pub fn result_from_duckdb_pending(code: ffi::duckdb_state, mut pending: ffi::duckdb_pending_result) -> Result<()> {
+    if code == ffi::DuckDBSuccess {
+        return Ok(());
+    }
+    unsafe {
+        let message = if pending.is_null() {
+            Some("pending is null".to_string())
+        } else {
+            let c_err = ffi::duckdb_pending_error(pending);
+            let message = Some(CStr::from_ptr(c_err).to_string_lossy().to_string());
+            ffi::duckdb_destroy_pending(&mut pending);
+            message
+        };
+        error_from_duckdb_code(code, message)
+    }
+}
+
+#[cold]
+#[inline]
+pub fn result_from_duckdb_result(code: ffi::duckdb_state, out: *mut ffi::duckdb_result) -> Result<()> {
+    if code == ffi::DuckDBSuccess {
+        return Ok(());
+    }
+    unsafe {
+        let c_err = ffi::duckdb_result_error(out);
+        let message = if c_err.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(c_err).to_string_lossy().to_string())
+        };
+        ffi::duckdb_destroy_result(out);
+        error_from_duckdb_code(code, message)
+    }
+}
@@ -1,9 +1,207 @@
+use std::{fmt, path::Path};
+
+use crate::{Connection, Error, ExtensionErrorReason, OptionalExt, Result};
+
+impl Connection {
+    /// Installs the extension `name` from the extension repository, or from a
+    /// file if `name` is a path to one. Installing an installed extension does
+    /// nothing.
+    ///
+    /// ## Failure
+    ///
+    /// Will return [`Error::ExtensionError`] if the extension couldn't be
+    /// installed, e.g. with [`ExtensionErrorReason::Network`] when the
+    /// repository can't be reached.
+    pub fn install_extension(&self, name: &str) -> Result<()> {
+        self.execute_batch(&format!("INSTALL {}", quote(name)))
+            .map_err(|err| extension_error(name, err))
+    }
+
+    /// Loads the extension `name`, or the extension file at a path. Known
+    /// extensions that aren't installed yet may be installed first.
+    ///
+    /// ## Failure
+    ///
+    /// Will return [`Error::ExtensionError`] if the extension couldn't be
+    /// loaded, e.g. with [`ExtensionErrorReason::NotFound`] when it isn't
+    /// installed.
+    pub fn load_extension(&self, name: &str) -> Result<()> {
+        self.execute_batch(&format!("LOAD {}", quote(name)))
+            .map_err(|err| extension_error(name, err))
+    }
+
+    /// Loads the extension file at `path`, e.g. a `.duckdb_extension` file
+    /// built for this DuckDB version.
+    ///
+    /// Extensions that aren't signed by DuckDB, such as ones you build
+    /// yourself, only load if the database was opened with
+    /// [`Config::allow_unsigned_extensions`](crate::Config::allow_unsigned_extensions).
+    ///
+    /// ## Failure
+    ///
+    /// Will return [`Error::InvalidPath`] if `path` isn't valid UTF-8, and
+    /// [`Error::ExtensionError`] if the extension couldn't be loaded, e.g.
+    /// with [`ExtensionErrorReason::InvalidSignature`] when it isn't signed.
+    pub fn load_extension_from_path<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let name = path.to_str().ok_or_else(|| Error::InvalidPath(path.to_owned()))?;
+        self.load_extension(name)
+    }
+
+    /// Installs and loads the `httpfs` extension, which reads and writes files
+    /// over HTTP(S) and S3, e.g. `read_parquet('s3://bucket/file.parquet')`.
+    /// Does nothing if it's already loaded, e.g. when built in with the
+    /// `httpfs` feature.
+    ///
+    /// See [`configure_s3`](Connection::configure_s3) to set S3 credentials.
+    ///
+    /// ## Failure
+    ///
+    /// Will return [`Error::ExtensionError`] if the extension couldn't be
+    /// installed or loaded, e.g. with [`ExtensionErrorReason::Network`] when
+    /// it isn't installed yet and the repository can't be reached.
+    pub fn enable_httpfs(&self) -> Result<()> {
+        let (installed, loaded) = self
+            .query_row(
+                "SELECT installed, loaded FROM duckdb_extensions() WHERE extension_name = 'httpfs'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .optional()?
+            .unwrap_or((false, false));
+        if loaded {
+            return Ok(());
+        }
+        if !installed {
+            self.install_extension("httpfs")?;
+        }
+        self.load_extension("httpfs")
+    }
+
+    /// Sets the S3 options of `config` for this connection's database. Options
+    /// that aren't set in `config` are left as they are.
+    ///
+    /// The options are defined by the `httpfs` extension, see
+    /// [`enable_httpfs`](Connection::enable_httpfs).
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result, S3Config};
+    /// fn count_rows(conn: &Connection) -> Result<i64> {
+    ///     conn.enable_httpfs()?;
+    ///     conn.configure_s3(&S3Config::default().region("eu-west-1").access_key("AKIA...").secret("..."))?;
+    ///     conn.query_row("SELECT count(*) FROM 's3://bucket/data.parquet'", [], |r| r.get(0))
+    /// }
+    /// ```
+    ///
+    /// ## Failure
+    ///
+    /// Will return `Err` if the `httpfs` extension isn't loaded.
+    pub fn configure_s3(&self, config: &S3Config) -> Result<()> {
+        self.execute_batch(&config.statements())
+    }
+}
+
+/// S3 options for [`Connection::configure_s3`]. The secret isn't shown by
+/// its `Debug` implementation.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct S3Config {
+    region: Option<String>,
+    access_key: Option<String>,
+    secret: Option<String>,
+    endpoint: Option<String>,
+}
+
+impl fmt::Debug for S3Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3Config")
+            .field("region", &self.region)
+            .field("access_key", &self.access_key)
+            .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+impl S3Config {
+    /// The region of the bucket, e.g. `us-east-1` (`s3_region`).
+    pub fn region(mut self, region: &str) -> Self {
+        self.region = Some(region.to_owned());
+        self
+    }
+
+    /// The access key id (`s3_access_key_id`).
+    pub fn access_key(mut self, access_key: &str) -> Self {
+        self.access_key = Some(access_key.to_owned());
+        self
+    }
+
+    /// The secret access key (`s3_secret_access_key`).
+    pub fn secret(mut self, secret: &str) -> Self {
+        self.secret = Some(secret.to_owned());
+        self
+    }
+
+    /// The host of an S3 compatible service, e.g. `localhost:9000`
+    /// (`s3_endpoint`).
+    pub fn endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = Some(endpoint.to_owned());
+        self
+    }
+
+    /// The `SET` statements for the options that are set.
+    fn statements(&self) -> String {
+        [
+            ("s3_region", &self.region),
+            ("s3_access_key_id", &self.access_key),
+            ("s3_secret_access_key", &self.secret),
+            ("s3_endpoint", &self.endpoint),
+        ]
+        .into_iter()
+        .filter_map(|(option, value)| Some(format!("SET {option} = {};", quote(value.as_deref()?))))
+        .collect()
+    }
+}
+
+fn quote(name: &str) -> String {
+    format!("'{}'", name.replace('\'', "''"))
+}
+
+/// Classifies a failure to install or load an extension by DuckDB's error
+/// message.
+fn extension_error(name: &str, err: Error) -> Error {
+    let Error::DuckDBFailure(_, Some(message)) = err else {
+        return err;
+    };
+    let reason = if message.contains("Failed to download extension") {
+        // An HTTP error means the repository answered, but doesn't have the
+        // extension; anything else means it couldn't be reached.
+        if message.starts_with("HTTP Error") {
+            ExtensionErrorReason::NotFound
+        } else {
+            ExtensionErrorReason::Network
+        }
+    } else if message.contains("signature is either missing or invalid") {
+        ExtensionErrorReason::InvalidSignature
+    } else if message.contains("not found") || message.contains("no such file") {
+        ExtensionErrorReason::NotFound
+    } else {
+        ExtensionErrorReason::Other
+    };
+    Error::ExtensionError {
+        name: name.to_owned(),
+        reason,
+        message,
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::{Connection, Result};
+    use super::{extension_error, S3Config};
+    use crate::{ffi, Config, Connection, Error, ExtensionErrorReason, Result};
 
     // https://duckdb.org/docs/extensions/json
     #[test]
+    #[cfg(feature = "json")]
     fn test_extension_json() -> Result<()> {
         let db = Connection::open_in_memory()?;
         assert_eq!(
@@ -19,6 +217,7 @@ mod test {
 
     // https://duckdb.org/docs/data/parquet/overview.html
     #[test]
+    #[cfg(feature = "parquet")]
     fn test_extension_parquet() -> Result<()> {
         let db = Connection::open_in_memory()?;
         assert_eq!(
@@ -34,6 +233,7 @@ mod test {
 
     // https://duckdb.org/docs/extensions/httpfs
     #[test]
+    #[cfg(feature = "httpfs")]
     fn test_extension_httpfs() -> Result<()> {
         let db = Connection::open_in_memory()?;
         assert_eq!(
@@ -42,4 +242,90 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_s3_config() -> Result<()> {
+        let config = S3Config::default().region("eu-west-1").secret("it's secret");
+        assert_eq!(
+            config.statements(),
+            "SET s3_region = 'eu-west-1';SET s3_secret_access_key = 'it''s secret';"
+        );
+        assert_eq!(S3Config::default().statements(), "");
+        assert_eq!(
+            format!("{config:?}"),
+            r#"S3Config { region: Some("eu-west-1"), access_key: None, secret: Some("<redacted>"), endpoint: None }"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "httpfs")]
+    fn test_configure_s3() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.enable_httpfs()?;
+        db.configure_s3(&S3Config::default().endpoint("localhost:9000"))?;
+        let endpoint: String = db.query_row("SELECT current_setting('s3_endpoint')", [], |r| r.get(0))?;
+        assert_eq!(endpoint, "localhost:9000");
+        Ok(())
+    }
+
+    #[test]
+    fn test_extension_errors() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let reason = |result: Result<()>| match result.unwrap_err() {
+            Error::ExtensionError { reason, .. } => reason,
+            err => panic!("expected an extension error, got {err:?}"),
+        };
+        assert_eq!(
+            reason(db.load_extension("no_such_extension")),
+            ExtensionErrorReason::NotFound
+        );
+        assert_eq!(
+            reason(db.install_extension("/no/such/file.duckdb_extension")),
+            ExtensionErrorReason::NotFound
+        );
+        assert_eq!(
+            reason(db.load_extension_from_path("/no/such/file.duckdb_extension")),
+            ExtensionErrorReason::NotFound
+        );
+
+        // Not an extension at all, with and without signature checks
+        let dir = tempdir::TempDir::new("duckdb_extension")?;
+        let path = dir.path().join("bogus.duckdb_extension");
+        std::fs::write(&path, [0u8; 1024]).unwrap();
+        match db.load_extension_from_path(&path).unwrap_err() {
+            Error::ExtensionError { name, reason, .. } => {
+                assert_eq!(name, path.to_str().unwrap());
+                assert_eq!(reason, ExtensionErrorReason::InvalidSignature);
+            }
+            err => panic!("expected an extension error, got {err:?}"),
+        }
+        let unsigned = Connection::open_in_memory_with_flags(Config::default().allow_unsigned_extensions()?)?;
+        assert_eq!(
+            reason(unsigned.load_extension_from_path(&path)),
+            ExtensionErrorReason::Other
+        );
+
+        let failure = |message: &str| {
+            let err = Error::DuckDBFailure(ffi::Error::new(ffi::DuckDBError), Some(message.to_owned()));
+            match extension_error("x", err) {
+                Error::ExtensionError { reason, .. } => reason,
+                err => panic!("expected an extension error, got {err:?}"),
+            }
+        };
+        assert_eq!(
+            failure("IO Error: Failed to download extension \"x\" at URL \"http://extensions.duckdb.org/x\" (ERROR Connection)"),
+            ExtensionErrorReason::Network
+        );
+        assert_eq!(
+            failure("HTTP Error: Failed to download extension \"x\" at URL \"http://extensions.duckdb.org/x\""),
+            ExtensionErrorReason::NotFound
+        );
+        assert_eq!(
+            failure("IO Error: Extension \"x\" could not be loaded because its signature is either missing or invalid and unsigned extensions are disabled by configuration (allow_unsigned_extensions)"),
+            ExtensionErrorReason::InvalidSignature
+        );
+        assert_eq!(failure("Catalog Error: something else"), ExtensionErrorReason::Other);
+        Ok(())
+    }
 }
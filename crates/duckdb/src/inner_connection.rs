@@ -1,25 +1,44 @@
 use std::{
+    cell::Cell,
     ffi::{c_void, CStr, CString},
     mem,
     os::raw::c_char,
     ptr, str,
+    sync::{Arc, Mutex},
 };
 
-use super::{ffi, Appender, Config, Connection, Result};
+use super::{ffi, Appender, Config, Connection, InterruptHandle, Result};
 use crate::{
-    error::{result_from_duckdb_appender, result_from_duckdb_arrow, result_from_duckdb_prepare, Error},
+    error::{
+        result_from_duckdb_appender, result_from_duckdb_arrow, result_from_duckdb_pending, result_from_duckdb_prepare,
+        result_from_duckdb_result, Error,
+    },
     raw_statement::RawStatement,
-    statement::Statement,
+    statement::{Statement, StatementType},
 };
 
 pub struct InnerConnection {
     pub db: ffi::duckdb_database,
     pub con: ffi::duckdb_connection,
+    /// `con`, or null once closed, shared with the [`InterruptHandle`]s.
+    interrupt_lock: Arc<Mutex<ffi::duckdb_connection>>,
+    progress_handler: Option<ProgressHandler>,
+    /// See [`Connection::register_arrow`].
+    #[cfg(feature = "vtab-arrow")]
+    pub(crate) arrow_tables: Arc<crate::vtab::arrow::ArrowTables>,
+    /// Whether a [`Transaction`](crate::Transaction) is open, see
+    /// [`Connection::is_autocommit`].
+    in_transaction: Cell<bool>,
     owned: bool,
 }
 
+/// See [`Connection::set_progress_handler`].
+pub type ProgressHandler = Box<dyn FnMut(f64) -> bool + Send>;
+
 impl InnerConnection {
     #[inline]
+    // The lock is only shared with `InterruptHandle`, which is `Send` and `Sync`
+    #[allow(clippy::arc_with_non_send_sync)]
     pub unsafe fn new(db: ffi::duckdb_database, owned: bool) -> Result<InnerConnection> {
         let mut con: ffi::duckdb_connection = ptr::null_mut();
         let r = ffi::duckdb_connect(db, &mut con);
@@ -30,7 +49,16 @@ impl InnerConnection {
                 Some("connect error".to_owned()),
             ));
         }
-        Ok(InnerConnection { db, con, owned })
+        Ok(InnerConnection {
+            db,
+            con,
+            interrupt_lock: Arc::new(Mutex::new(con)),
+            progress_handler: None,
+            #[cfg(feature = "vtab-arrow")]
+            arrow_tables: Arc::default(),
+            in_transaction: Cell::new(false),
+            owned,
+        })
     }
 
     pub fn open_with_flags(c_path: &CStr, config: Config) -> Result<InnerConnection> {
@@ -54,6 +82,8 @@ impl InnerConnection {
         if self.con.is_null() {
             return Ok(());
         }
+        // Taking the lock waits for an interrupt in progress
+        *self.interrupt_lock.lock().unwrap() = ptr::null_mut();
         unsafe {
             ffi::duckdb_disconnect(&mut self.con);
             self.con = ptr::null_mut();
@@ -68,10 +98,19 @@ impl InnerConnection {
 
     /// Creates a new connection to the already-opened database.
     pub fn try_clone(&self) -> Result<Self> {
-        unsafe { InnerConnection::new(self.db, false) }
+        #[allow(unused_mut)]
+        let mut inner = unsafe { InnerConnection::new(self.db, false) }?;
+        #[cfg(feature = "vtab-arrow")]
+        {
+            inner.arrow_tables = self.arrow_tables.clone();
+        }
+        Ok(inner)
     }
 
     pub fn execute(&mut self, sql: &str) -> Result<()> {
+        if self.progress_handler.is_some() {
+            return self.execute_with_progress(sql);
+        }
         let c_str = CString::new(sql).unwrap();
         unsafe {
             let mut out = mem::zeroed();
@@ -82,12 +121,119 @@ impl InnerConnection {
         }
     }
 
+    /// Runs the statements of `sql` one by one, reporting their progress.
+    fn execute_with_progress(&mut self, sql: &str) -> Result<()> {
+        let c_str = CString::new(sql)?;
+        unsafe {
+            let mut extracted: ffi::duckdb_extracted_statements = ptr::null_mut();
+            let count = ffi::duckdb_extract_statements(self.con, c_str.as_ptr(), &mut extracted);
+            let c_err = ffi::duckdb_extract_statements_error(extracted);
+            let result = if count == 0 && !c_err.is_null() {
+                Err(Error::DuckDBFailure(
+                    ffi::Error::new(ffi::DuckDBError),
+                    Some(CStr::from_ptr(c_err).to_string_lossy().to_string()),
+                ))
+            } else {
+                (0..count).try_for_each(|i| {
+                    let mut c_stmt: ffi::duckdb_prepared_statement = ptr::null_mut();
+                    let r = ffi::duckdb_prepare_extracted_statement(self.con, extracted, i, &mut c_stmt);
+                    result_from_duckdb_prepare(r, c_stmt)?;
+                    let mut pending: ffi::duckdb_pending_result = ptr::null_mut();
+                    let r = ffi::duckdb_pending_prepared(c_stmt, &mut pending);
+                    ffi::duckdb_destroy_prepare(&mut c_stmt);
+                    result_from_duckdb_pending(r, pending)?;
+                    let mut out = self.execute_pending(pending)?;
+                    ffi::duckdb_destroy_result(&mut out);
+                    Ok(())
+                })
+            };
+            ffi::duckdb_destroy_extracted(&mut extracted);
+            result
+        }
+    }
+
+    #[inline]
+    pub fn has_progress_handler(&self) -> bool {
+        self.progress_handler.is_some()
+    }
+
+    pub fn set_progress_handler(&mut self, handler: Option<ProgressHandler>) -> Result<()> {
+        // DuckDB only tracks the progress of queries while its progress bar
+        // is enabled; it isn't printed, the handler reports it.
+        self.execute(if handler.is_some() {
+            "SET enable_progress_bar = true; SET enable_progress_bar_print = false;"
+        } else {
+            "SET enable_progress_bar = false;"
+        })?;
+        self.progress_handler = handler;
+        Ok(())
+    }
+
+    /// Runs the tasks of `pending` until its result is ready, calling the
+    /// progress handler, if any, after each one. `pending` is destroyed.
+    pub unsafe fn execute_pending(&mut self, mut pending: ffi::duckdb_pending_result) -> Result<ffi::duckdb_result> {
+        let mut cancelled = false;
+        loop {
+            let state = ffi::duckdb_pending_execute_task(pending);
+            if state == ffi::duckdb_pending_state_DUCKDB_PENDING_ERROR {
+                result_from_duckdb_pending(ffi::DuckDBError, pending)?;
+            }
+            if ffi::duckdb_pending_execution_is_finished(state) {
+                break;
+            }
+            if state == ffi::duckdb_pending_state_DUCKDB_PENDING_NO_TASKS_AVAILABLE {
+                // The remaining tasks run on other threads
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            if let (Some(handler), false) = (&mut self.progress_handler, cancelled) {
+                let progress = ffi::duckdb_query_progress(self.con).percentage;
+                if !handler(if progress < 0.0 { -1.0 } else { progress / 100.0 }) {
+                    ffi::duckdb_interrupt(self.con);
+                    cancelled = true;
+                }
+            }
+        }
+        let mut out: ffi::duckdb_result = mem::zeroed();
+        let rc = ffi::duckdb_execute_pending(pending, &mut out);
+        ffi::duckdb_destroy_pending(&mut pending);
+        result_from_duckdb_result(rc, &mut out)?;
+        Ok(out)
+    }
+
     pub fn prepare<'a>(&mut self, conn: &'a Connection, sql: &str) -> Result<Statement<'a>> {
         let mut c_stmt: ffi::duckdb_prepared_statement = ptr::null_mut();
         let c_str = CString::new(sql).unwrap();
         let r = unsafe { ffi::duckdb_prepare(self.con, c_str.as_ptr() as *const c_char, &mut c_stmt) };
         result_from_duckdb_prepare(r, c_stmt)?;
-        Ok(Statement::new(conn, unsafe { RawStatement::new(c_stmt) }))
+        let mut stmt = unsafe { RawStatement::new(c_stmt) };
+        stmt.set_sql(c_str);
+        Ok(Statement::new(conn, stmt))
+    }
+
+    pub fn extract_statement_types(&mut self, sql: &str) -> Result<Vec<StatementType>> {
+        let c_str = CString::new(sql)?;
+        unsafe {
+            let mut extracted: ffi::duckdb_extracted_statements = ptr::null_mut();
+            let count = ffi::duckdb_extract_statements(self.con, c_str.as_ptr(), &mut extracted);
+            let c_err = ffi::duckdb_extract_statements_error(extracted);
+            let result = if count == 0 && !c_err.is_null() {
+                Err(Error::DuckDBFailure(
+                    ffi::Error::new(ffi::DuckDBError),
+                    Some(CStr::from_ptr(c_err).to_string_lossy().to_string()),
+                ))
+            } else {
+                (0..count)
+                    .map(|i| {
+                        let mut c_stmt: ffi::duckdb_prepared_statement = ptr::null_mut();
+                        let r = ffi::duckdb_prepare_extracted_statement(self.con, extracted, i, &mut c_stmt);
+                        result_from_duckdb_prepare(r, c_stmt)?;
+                        Ok(RawStatement::new(c_stmt).statement_type())
+                    })
+                    .collect()
+            };
+            ffi::duckdb_destroy_extracted(&mut extracted);
+            result
+        }
     }
 
     pub fn appender<'a>(&mut self, conn: &'a Connection, table: &str, schema: &str) -> Result<Appender<'a>> {
@@ -103,12 +249,23 @@ impl InnerConnection {
             )
         };
         result_from_duckdb_appender(r, &mut c_app)?;
-        Ok(Appender::new(conn, c_app))
+        Ok(Appender::new(conn, c_app, schema, table))
+    }
+
+    pub fn get_interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle {
+            con: Arc::clone(&self.interrupt_lock),
+        }
     }
 
     #[inline]
     pub fn is_autocommit(&self) -> bool {
-        true
+        !self.in_transaction.get()
+    }
+
+    #[inline]
+    pub(crate) fn set_in_transaction(&self, in_transaction: bool) {
+        self.in_transaction.set(in_transaction);
     }
 }
 
@@ -57,6 +57,10 @@
 
 pub use libduckdb_sys as ffi;
 
+// lets the `::duckdb` paths generated by derive macros resolve in our own tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as duckdb;
+
 use std::{
     cell::RefCell,
     convert,
@@ -64,27 +68,38 @@ use std::{
     fmt,
     path::{Path, PathBuf},
     result, str,
+    sync::{Arc, Mutex},
 };
 
-use crate::{cache::StatementCache, inner_connection::InnerConnection, raw_statement::RawStatement, types::ValueRef};
+use crate::{
+    cache::StatementCache,
+    inner_connection::InnerConnection,
+    raw_statement::RawStatement,
+    types::{FromSql, ValueRef},
+};
 
 #[cfg(feature = "r2d2")]
 pub use crate::r2d2::DuckdbConnectionManager;
 pub use crate::{
     appender::Appender,
     appender_params::{appender_params_from_iter, AppenderParams, AppenderParamsFromIter},
-    arrow_batch::Arrow,
+    arrow_batch::{Arrow, ArrowStream},
     cache::CachedStatement,
-    column::Column,
+    column::{Column, ColumnDescription},
     config::{AccessMode, Config, DefaultNullOrder, DefaultOrder},
-    error::Error,
+    error::{Error, ExtensionErrorReason},
+    extension::S3Config,
     ffi::ErrorCode,
-    params::{params_from_iter, Params, ParamsFromIter},
-    row::{AndThenRows, Map, MappedRows, Row, RowIndex, Rows},
-    statement::Statement,
+    params::{params_from_iter, Params, ParamsFromIter, ToSqlRow},
+    parquet::{ParquetCompression, ParquetOptions},
+    pragma::MemoryUsage,
+    row::{AndThenRows, FromRow, Map, MappedRows, Row, RowIndex, Rows},
+    statement::{FirstColumn, Statement, StatementType},
     transaction::{DropBehavior, Savepoint, Transaction, TransactionBehavior},
     types::ToSql,
 };
+#[cfg(feature = "derive")]
+pub use duckdb_derive::{FromRow, ToSqlRow};
 #[cfg(feature = "polars")]
 pub use polars_dataframe::Polars;
 
@@ -103,6 +118,7 @@ mod column;
 mod config;
 mod inner_connection;
 mod params;
+mod parquet;
 #[cfg(feature = "polars")]
 mod polars_dataframe;
 mod pragma;
@@ -113,7 +129,6 @@ mod row;
 mod statement;
 mod transaction;
 
-#[cfg(feature = "extensions-full")]
 mod extension;
 
 pub mod types;
@@ -160,6 +175,37 @@ macro_rules! params {
     };
 }
 
+/// A macro making it more convenient to pass lists of named parameters
+/// as a `&[(&str, &dyn ToSql)]`, see [`Params`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use duckdb::{Result, Connection, named_params};
+///
+/// struct Person {
+///     name: String,
+///     age_in_years: u8,
+/// }
+///
+/// fn add_person(conn: &Connection, person: &Person) -> Result<()> {
+///     conn.execute(
+///         "INSERT INTO person (name, age_in_years) VALUES ($name, $age)",
+///         named_params! { "$name": person.name, "$age": person.age_in_years },
+///     )?;
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! named_params {
+    () => {
+        &[] as &[(&str, &dyn $crate::ToSql)]
+    };
+    ($($param_name:literal: $param_val:expr),+ $(,)?) => {
+        &[$(($param_name, &$param_val as &dyn $crate::ToSql)),+] as &[(&str, &dyn $crate::ToSql)]
+    };
+}
+
 /// A typedef of the result returned by many methods.
 pub type Result<T, E = Error> = result::Result<T, E>;
 
@@ -376,6 +422,58 @@ impl Connection {
         self.prepare(sql).and_then(|mut stmt| stmt.execute(params))
     }
 
+    /// Convenience method to run a statement with a `RETURNING` clause, e.g.
+    /// to get the keys generated by an insert, and return the first column
+    /// of every returned row.
+    ///
+    /// DuckDB has no equivalent of SQLite's `last_insert_rowid`, so this is
+    /// the way to get the value of a sequence-backed key after an insert. A
+    /// multi-row insert returns all the keys, in the order of the rows.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn add_people(conn: &Connection) -> Result<Vec<i64>> {
+    ///     conn.execute_returning("INSERT INTO people (name) VALUES (?), (?) RETURNING id", ["Ann", "Bob"])
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `sql` cannot be converted to a C-compatible string
+    /// or if the underlying DuckDB call fails, or if a value can't be
+    /// converted to `T`.
+    pub fn execute_returning<T: FromSql, P: Params>(&self, sql: &str, params: P) -> Result<Vec<T>> {
+        self.prepare(sql)?.iter(params)?.collect()
+    }
+
+    /// Like [`execute_returning`](Connection::execute_returning), for a
+    /// statement that changes a single row, e.g. to get the key generated by
+    /// an `INSERT ... RETURNING id`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn add_person(conn: &Connection, name: &str) -> Result<i64> {
+    ///     conn.execute_returning_one("INSERT INTO people (name) VALUES (?) RETURNING id", [name])
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// As [`execute_returning`](Connection::execute_returning), and returns
+    /// `Err(StatementChangedRows)` if the statement doesn't return exactly one
+    /// row.
+    pub fn execute_returning_one<T: FromSql, P: Params>(&self, sql: &str, params: P) -> Result<T> {
+        let mut values = self.execute_returning(sql, params)?;
+        match values.len() {
+            1 => Ok(values.remove(0)),
+            n => Err(Error::StatementChangedRows(n)),
+        }
+    }
+
     /// Returns the path to the database file, if one exists and is known.
     #[inline]
     pub fn path(&self) -> Option<&Path> {
@@ -398,6 +496,26 @@ impl Connection {
     /// }
     /// ```
     ///
+    /// The row is only valid inside `f`, but `f` can borrow from it with
+    /// [`Row::get_ref`], e.g. to hash a blob without copying it into a
+    /// `Vec<u8>`:
+    ///
+    /// ```rust
+    /// # use duckdb::{Result, Connection};
+    /// # use std::hash::{DefaultHasher, Hash, Hasher};
+    /// fn blob_hash(conn: &Connection) -> Result<u64> {
+    ///     conn.query_row("SELECT 'duck'::BLOB", [], |row| {
+    ///         let blob: &[u8] = row.get_ref(0)?.as_blob()?;
+    ///         let mut hasher = DefaultHasher::new();
+    ///         blob.hash(&mut hasher);
+    ///         Ok(hasher.finish())
+    ///     })
+    /// }
+    /// # let conn = Connection::open_in_memory()?;
+    /// # blob_hash(&conn)?;
+    /// # Ok::<(), duckdb::Error>(())
+    /// ```
+    ///
     /// If the query returns more than one row, all rows except the first are
     /// ignored.
     ///
@@ -418,6 +536,30 @@ impl Connection {
         self.prepare(sql)?.query_row(params, f)
     }
 
+    /// Convenience method to execute a query that is expected to return a
+    /// single value, such as `SELECT count(*) FROM t`, see
+    /// [`Statement::query_scalar`]. For queries run often, prefer calling that
+    /// on a statement from [`prepare_cached`](Connection::prepare_cached).
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Result, Connection};
+    /// fn has_users(conn: &Connection) -> Result<bool> {
+    ///     conn.query_scalar("SELECT EXISTS (SELECT 1 FROM users)", [])
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `sql` cannot be converted to a C-compatible
+    /// string, if the query returns no rows, or if the underlying DuckDB call
+    /// fails.
+    #[inline]
+    pub fn query_scalar<T: FromSql, P: Params>(&self, sql: &str, params: P) -> Result<T> {
+        self.prepare(sql)?.query_scalar(params)
+    }
+
     /// Convenience method to execute a query that is expected to return a
     /// single row, and execute a mapping via `f` on that returned row with
     /// the possibility of failure. The `Result` type of `f` must implement
@@ -457,6 +599,65 @@ impl Connection {
             .and_then(f)
     }
 
+    /// Convenience method to run a query and return all rows as a JSON array
+    /// of objects, keyed by column name.
+    ///
+    /// Values are converted as described on the `From<Value>` implementation
+    /// for `serde_json::Value`, except that columns and struct fields keep
+    /// their order. See [`write_json`](Connection::write_json) to avoid buffering a
+    /// large result in memory.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn people_json(conn: &Connection) -> Result<String> {
+    ///     // e.g. `[{"id":1,"name":"Joe Smith"}]`
+    ///     conn.query_json("SELECT id, name FROM people", [])
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `sql` cannot be converted to a C-compatible string
+    /// or if the underlying DuckDB call fails.
+    #[cfg(feature = "serde_json")]
+    pub fn query_json<P: Params>(&self, sql: &str, params: P) -> Result<String> {
+        let mut json = Vec::new();
+        self.write_json(&mut json, sql, params)?;
+        Ok(String::from_utf8(json).expect("serde_json writes UTF-8"))
+    }
+
+    /// Like [`query_json`](Connection::query_json), but write the JSON to
+    /// `writer` one row at a time instead of building a `String`.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `sql` cannot be converted to a C-compatible string,
+    /// if the underlying DuckDB call fails, or if writing fails.
+    #[cfg(feature = "serde_json")]
+    pub fn write_json<W: std::io::Write, P: Params>(&self, mut writer: W, sql: &str, params: P) -> Result<()> {
+        let mut stmt = self.prepare(sql)?;
+        let mut rows = stmt.query(params)?;
+        let mut names = None;
+        writer.write_all(b"[")?;
+        while let Some(row) = rows.next()? {
+            let names = match names {
+                Some(ref names) => {
+                    writer.write_all(b",")?;
+                    names
+                }
+                None => names.insert(row.stmt.column_names()),
+            };
+            let values = (0..names.len())
+                .map(|i| row.get::<_, types::Value>(i))
+                .collect::<Result<Vec<_>>>()?;
+            types::write_object(&mut writer, names.iter().cloned().zip(values))?;
+        }
+        writer.write_all(b"]")?;
+        Ok(())
+    }
+
     /// Prepare a SQL statement for execution.
     ///
     /// ## Example
@@ -480,6 +681,81 @@ impl Connection {
         self.db.borrow_mut().prepare(self, sql)
     }
 
+    /// Prepare a SQL statement, checking that it is a pure read: a `SELECT` or
+    /// a plain `EXPLAIN`.
+    ///
+    /// This is meant as defense-in-depth for untrusted query input, on top of
+    /// DuckDB's own `access_mode` setting. It errs on the side of rejecting:
+    /// `PRAGMA` statements are refused even when DuckDB rewrites them into a
+    /// `SELECT`, and so is `EXPLAIN ANALYZE`, which runs the explained
+    /// statement.
+    ///
+    /// # Failure
+    ///
+    /// Will return [`Error::ReadOnlyViolation`] if `sql` is not a pure read, or
+    /// `Err` if it cannot be prepared.
+    pub fn prepare_read_only(&self, sql: &str) -> Result<Statement<'_>> {
+        let stmt = self.prepare(sql)?;
+        let statement_type = stmt.statement_type();
+        let read = match statement_type {
+            StatementType::Select => !starts_with_keywords(sql, &["PRAGMA"]),
+            StatementType::Explain => !starts_with_keywords(sql, &["EXPLAIN", "ANALYZE"]),
+            _ => false,
+        };
+        if read {
+            Ok(stmt)
+        } else {
+            Err(Error::ReadOnlyViolation(statement_type))
+        }
+    }
+
+    /// Like [`execute`](Connection::execute), but only runs `sql` if it is a
+    /// pure read, see [`prepare_read_only`](Connection::prepare_read_only).
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Error, Result};
+    /// fn run_untrusted(conn: &Connection, sql: &str) -> Result<usize> {
+    ///     match conn.execute_read_only(sql, []) {
+    ///         Err(Error::ReadOnlyViolation(kind)) => panic!("refusing to run {kind:?}"),
+    ///         result => result,
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return [`Error::ReadOnlyViolation`] if `sql` is not a pure read, or
+    /// `Err` if the underlying DuckDB call fails.
+    #[inline]
+    pub fn execute_read_only<P: Params>(&self, sql: &str, params: P) -> Result<usize> {
+        self.prepare_read_only(sql).and_then(|mut stmt| stmt.execute(params))
+    }
+
+    /// Classify each statement in `sql` without executing it.
+    ///
+    /// Returns one [`StatementType`] per statement, in order.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result, StatementType};
+    /// fn is_query(conn: &Connection, sql: &str) -> Result<bool> {
+    ///     let types = conn.extract_statement_types(sql)?;
+    ///     Ok(types.iter().all(|t| *t == StatementType::Select))
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Every statement is prepared to find its type, so this fails if any of
+    /// them cannot be bound, including statements that depend on tables
+    /// created earlier in the same `sql`.
+    pub fn extract_statement_types(&self, sql: &str) -> Result<Vec<StatementType>> {
+        self.db.borrow_mut().extract_statement_types(sql)
+    }
+
     /// Create an Appender for fast import data
     /// default to use `DatabaseName::Main`
     ///
@@ -527,6 +803,10 @@ impl Connection {
     /// `Connection` except that on failure, it returns an error and the
     /// connection itself (presumably so closing can be attempted again).
     ///
+    /// A transaction still open on the connection is rolled back, while the
+    /// changes committed before are kept: DuckDB writes and syncs them to the
+    /// write-ahead log when they commit.
+    ///
     /// # Failure
     ///
     /// Will return `Err` if the underlying DuckDB call fails.
@@ -536,13 +816,100 @@ impl Connection {
         r.map_err(move |err| (self, err))
     }
 
+    /// Moves the changes in the write-ahead log into the database file, so
+    /// that the file is complete on its own and the log is emptied.
+    ///
+    /// DuckDB already writes and syncs the log when a transaction commits,
+    /// so a committed change survives a crash without calling this; it is for
+    /// copying or backing up the database file while it is open.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if a transaction is open on this connection and has
+    /// made changes, or if a transaction is open on another connection to the
+    /// same database.
+    pub fn checkpoint(&self) -> Result<()> {
+        self.execute_batch("CHECKPOINT")
+    }
+
+    /// Returns a handle that interrupts the query running on this connection,
+    /// from another thread.
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Error, Result};
+    /// # use std::{thread, time::Duration};
+    /// fn count_with_timeout(conn: &Connection) -> Result<Option<i64>> {
+    ///     let handle = conn.interrupt_handle();
+    ///     thread::spawn(move || {
+    ///         thread::sleep(Duration::from_secs(10));
+    ///         handle.interrupt();
+    ///     });
+    ///     match conn.query_row("SELECT count(*) FROM 'huge.parquet'", [], |r| r.get(0)) {
+    ///         Err(Error::Interrupted) => Ok(None),
+    ///         result => result.map(Some),
+    ///     }
+    /// }
+    /// ```
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.db.borrow().get_interrupt_handle()
+    }
+
+    /// Sets a handler that is called while statements run on this connection,
+    /// with the progress of the running query as a fraction between 0 and 1,
+    /// or -1 while DuckDB can't estimate it yet. Returning `false` cancels
+    /// the query, which then fails with [`Error::Interrupted`]. `None`
+    /// removes the handler.
+    ///
+    /// The handler runs on the thread executing the statement, between the
+    /// tasks DuckDB runs there, which may be many times a second: keep it
+    /// cheap. It can't use this connection. It must be `Send` because the
+    /// connection is, and it isn't called by a streaming result while its
+    /// rows are read.
+    ///
+    /// Setting a handler enables DuckDB's `enable_progress_bar` setting,
+    /// without printing it, and removing it disables the setting. While a
+    /// handler is set, results are read from DuckDB a chunk at a time instead
+    /// of being converted to Arrow at once.
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn create_summary(conn: &Connection) -> Result<()> {
+    ///     conn.set_progress_handler(Some(Box::new(|progress| {
+    ///         if progress >= 0.0 {
+    ///             eprint!("\r{:3.0}%", progress * 100.0);
+    ///         }
+    ///         true
+    ///     })))?;
+    ///     conn.execute_batch("CREATE TABLE summary AS SELECT k, count(*) FROM 'events.parquet' GROUP BY k")?;
+    ///     conn.set_progress_handler(None)
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the setting can't be changed.
+    pub fn set_progress_handler(&self, handler: Option<Box<dyn FnMut(f64) -> bool + Send>>) -> Result<()> {
+        self.db.borrow_mut().set_progress_handler(handler)
+    }
+
     /// Test for auto-commit mode.
-    /// Autocommit mode is on by default.
+    /// Autocommit mode is on by default, and off while a [`Transaction`] is
+    /// open. Transactions started by a plain `BEGIN` statement aren't tracked.
     #[inline]
     pub fn is_autocommit(&self) -> bool {
         self.db.borrow().is_autocommit()
     }
 
+    /// Whether a transaction started by a plain `BEGIN` statement is open,
+    /// which [`is_autocommit`](Connection::is_autocommit) doesn't track.
+    /// Outside of a transaction each query runs in its own, with its own id.
+    /// Probing with a `BEGIN` isn't an option, as its failure would abort
+    /// the open transaction.
+    pub(crate) fn in_untracked_transaction(&self) -> Result<bool> {
+        let txid = || self.query_row("SELECT txid_current()", [], |r| r.get::<_, i64>(0));
+        Ok(txid()? == txid()?)
+    }
+
     /// Creates a new connection to the already-opened database.
     pub fn try_clone(&self) -> Result<Self> {
         let inner = self.db.borrow().try_clone()?;
@@ -557,6 +924,35 @@ impl Connection {
     pub fn version(&self) -> Result<String> {
         self.query_row("PRAGMA version", [], |row| row.get(0))
     }
+
+    /// Sets where `NULL`s sort in an `ORDER BY` that doesn't say, for this
+    /// connection. See also [`Config::default_null_order`].
+    pub fn set_null_sort(&self, null_order: DefaultNullOrder) -> Result<()> {
+        self.execute_batch(&format!("SET default_null_order = '{null_order}'"))
+    }
+
+    /// Configures the database so that queries return the same results, in
+    /// the same order, on every run, e.g. for tests comparing query output:
+    ///
+    /// - a single thread, so that even unordered aggregates and queries
+    ///   without an `ORDER BY` are computed in a fixed order,
+    /// - `NULL`s sorted last when an `ORDER BY` doesn't say,
+    /// - insertion order preserved,
+    /// - no progress bar printed while queries run.
+    ///
+    /// The thread count and insertion order apply to the whole database, not
+    /// just this connection. A single thread makes large queries much slower,
+    /// so this is meant for tests. A
+    /// [progress handler](Connection::set_progress_handler) keeps being
+    /// called.
+    pub fn enable_deterministic_mode(&self) -> Result<()> {
+        self.set_null_sort(DefaultNullOrder::NullsLast)?;
+        self.execute_batch(
+            "SET threads = 1;
+             SET preserve_insertion_order = true;
+             SET enable_progress_bar_print = false;",
+        )
+    }
 }
 
 impl fmt::Debug for Connection {
@@ -565,6 +961,72 @@ impl fmt::Debug for Connection {
     }
 }
 
+/// Whether `sql` starts with the given keywords, ignoring case, whitespace,
+/// comments and opening parentheses.
+fn starts_with_keywords(mut sql: &str, keywords: &[&str]) -> bool {
+    for keyword in keywords {
+        loop {
+            sql = sql.trim_start_matches(|c: char| c.is_whitespace() || c == '(');
+            if let Some(rest) = sql.strip_prefix("--") {
+                sql = rest.find('\n').map_or("", |i| &rest[i..]);
+            } else if let Some(rest) = sql.strip_prefix("/*") {
+                sql = rest.find("*/").map_or("", |i| &rest[i + 2..]);
+            } else {
+                break;
+            }
+        }
+        let len = sql
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(sql.len());
+        if !sql[..len].eq_ignore_ascii_case(keyword) {
+            return false;
+        }
+        sql = &sql[len..];
+    }
+    true
+}
+
+/// Wraps the query `sql` in parentheses, to be used as a subquery. Trailing
+/// `;`s are dropped, and the newline ends a trailing `--` comment.
+fn subquery(sql: &str) -> String {
+    let sql = sql.trim_end_matches(|c: char| c == ';' || c.is_whitespace());
+    format!("(\n{sql}\n)")
+}
+
+/// Interrupts the query running on a [`Connection`], see
+/// [`Connection::interrupt_handle`].
+///
+/// The handle can be cloned and sent to other threads. It doesn't keep the
+/// connection open: interrupting after the connection is closed does nothing.
+#[derive(Clone)]
+pub struct InterruptHandle {
+    con: Arc<Mutex<ffi::duckdb_connection>>,
+}
+
+unsafe impl Send for InterruptHandle {}
+unsafe impl Sync for InterruptHandle {}
+
+impl InterruptHandle {
+    /// Interrupts the query running on the connection, which then fails with
+    /// [`Error::Interrupted`]. Does nothing if no query is running.
+    ///
+    /// Interrupting takes a lock shared with the connection, so this isn't
+    /// async-signal-safe: from a signal handler, set a flag that another
+    /// thread turns into the call.
+    pub fn interrupt(&self) {
+        let con = self.con.lock().unwrap();
+        if !con.is_null() {
+            unsafe { ffi::duckdb_interrupt(*con) };
+        }
+    }
+}
+
+impl fmt::Debug for InterruptHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InterruptHandle").finish_non_exhaustive()
+    }
+}
+
 #[cfg(doctest)]
 doc_comment::doctest!("../../../README.md");
 
@@ -746,6 +1208,119 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_interrupt() -> Result<()> {
+        use std::{
+            sync::atomic::{AtomicBool, Ordering},
+            thread,
+            time::{Duration, Instant},
+        };
+
+        let db = Connection::open_in_memory()?;
+        let handle = db.interrupt_handle();
+        // Interrupting when no query runs doesn't affect the next one
+        handle.interrupt();
+        assert_eq!(db.query_row("SELECT 1", [], |r| r.get::<_, i32>(0))?, 1);
+
+        let (started, stop) = (Instant::now(), Arc::new(AtomicBool::new(false)));
+        let interrupter = {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(50));
+                    handle.interrupt();
+                }
+            })
+        };
+        let result = db.query_row("SELECT count(*) FROM range(10000000000) t1, range(10) t2", [], |r| {
+            r.get::<_, i64>(0)
+        });
+        stop.store(true, Ordering::Relaxed);
+        interrupter.join().unwrap();
+        assert_eq!(result.unwrap_err(), Error::Interrupted);
+        assert!(started.elapsed() < Duration::from_secs(60));
+        assert_eq!(db.query_row("SELECT 2", [], |r| r.get::<_, i32>(0))?, 2);
+
+        // The handle doesn't keep the connection open
+        let handle = db.interrupt_handle();
+        db.close().map_err(|(_, err)| err)?;
+        handle.interrupt();
+        Ok(())
+    }
+
+    #[test]
+    fn test_progress_handler() -> Result<()> {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE t AS SELECT range AS x FROM range(5000)")?;
+        let heavy = "SELECT count(*) FROM t t1, t t2 WHERE t1.x + t2.x = 4999";
+
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let seen = progress.clone();
+        db.set_progress_handler(Some(Box::new(move |p| {
+            seen.lock().unwrap().push(p);
+            true
+        })))?;
+        assert_eq!(db.query_row(heavy, [], |r| r.get::<_, i64>(0))?, 5000);
+        assert_eq!(db.query_scalar::<i64, _>(heavy, [])?, 5000);
+        db.execute_batch(&format!("CREATE TABLE c AS {heavy}"))?;
+        // Results of several chunks are read in full
+        let mut stmt = db.prepare("SELECT x FROM t ORDER BY x")?;
+        let xs = stmt
+            .query_map([], |r| r.get::<_, i64>(0))?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(xs, (0..5000).collect::<Vec<_>>());
+        assert_eq!(stmt.row_count(), 5000);
+        let progress = std::mem::take(&mut *progress.lock().unwrap());
+        assert!(!progress.is_empty());
+        assert!(
+            progress.iter().all(|&p| p == -1.0 || (0.0..=1.0).contains(&p)),
+            "{progress:?}"
+        );
+
+        // Returning false cancels the statement, and later ones run again
+        let cancel = Arc::new(AtomicBool::new(true));
+        let cancelling = cancel.clone();
+        db.set_progress_handler(Some(Box::new(move |_| !cancelling.load(Ordering::Relaxed))))?;
+        assert_eq!(db.query_row(heavy, [], |r| r.get::<_, i64>(0)), Err(Error::Interrupted));
+        assert_eq!(
+            db.execute_batch(&format!("CREATE TABLE d AS {heavy}")),
+            Err(Error::Interrupted)
+        );
+        cancel.store(false, Ordering::Relaxed);
+        assert_eq!(db.query_row(heavy, [], |r| r.get::<_, i64>(0))?, 5000);
+
+        db.set_progress_handler(None)?;
+        assert_eq!(db.query_row(heavy, [], |r| r.get::<_, i64>(0))?, 5000);
+        assert!(!db.query_row("SELECT current_setting('enable_progress_bar')", [], |r| r
+            .get::<_, bool>(0))?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_checkpoint_and_close() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.db");
+        let wal = temp_dir.path().join("test.db.wal");
+
+        let db = Connection::open(&path)?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER); INSERT INTO foo VALUES (1)")?;
+        assert!(wal.exists());
+        db.checkpoint()?;
+        assert!(!wal.exists() || wal.metadata().unwrap().len() == 0);
+
+        db.execute_batch("BEGIN; INSERT INTO foo VALUES (2)")?;
+        let err = db.checkpoint().unwrap_err();
+        assert!(err.to_string().contains("transaction local changes"), "{err}");
+        db.close().map_err(|(_, err)| err)?;
+
+        let db = Connection::open(&path)?;
+        let count: i64 = db.query_row("SELECT count(*) FROM foo", [], |r| r.get(0))?;
+        assert_eq!(count, 1);
+        Ok(())
+    }
+
     #[test]
     fn test_execute_batch() -> Result<()> {
         let db = checked_memory_handle();
@@ -782,6 +1357,161 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "derive")]
+    fn test_derive_to_sql_row() -> Result<()> {
+        use crate::ToSqlRow;
+
+        #[derive(ToSqlRow)]
+        struct Person<'a> {
+            id: i32,
+            #[column(skip)]
+            #[allow(dead_code)]
+            cached: Vec<u8>,
+            name: &'a str,
+            email: Option<String>,
+        }
+
+        #[derive(ToSqlRow)]
+        struct Pair(i32, #[column(skip)] (), &'static str);
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE person (id INTEGER, name TEXT, email TEXT)")?;
+        let person = Person {
+            id: 1,
+            cached: vec![],
+            name: "Ada",
+            email: None,
+        };
+        db.execute("INSERT INTO person VALUES (?, ?, ?)", person.to_params())?;
+        db.execute(
+            "INSERT INTO person (id, name) VALUES (?, ?)",
+            Pair(2, (), "Bob").to_params(),
+        )?;
+
+        let rows = db
+            .prepare("SELECT id, name, email FROM person ORDER BY id")?
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?
+            .collect::<Result<Vec<(i32, String, Option<String>)>>>()?;
+        assert_eq!(rows, [(1, "Ada".to_owned(), None), (2, "Bob".to_owned(), None)]);
+
+        let err = db.query_row("SELECT ?, ?", person.to_params(), |_| Ok(())).unwrap_err();
+        assert!(matches!(err, Error::InvalidParameterCount(3, 2)), "{err:?}");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "derive")]
+    fn test_derive_from_row() -> Result<()> {
+        use crate::FromRow;
+
+        #[derive(Debug, PartialEq, FromRow)]
+        struct Person {
+            id: i32,
+            #[column(rename = "full_name")]
+            name: String,
+            email: Option<String>,
+            r#type: String,
+            #[column(skip)]
+            cached: Vec<u8>,
+        }
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE person (id INTEGER, full_name TEXT, email TEXT, type TEXT);
+             INSERT INTO person VALUES (1, 'Ada', 'ada@example.com', 'admin'), (2, 'Bob', NULL, 'user');",
+        )?;
+        let people = db
+            .prepare("SELECT * FROM person ORDER BY id")?
+            .query_map([], Person::from_row)?
+            .collect::<Result<Vec<_>>>()?;
+        let person = |id, name: &str, email: Option<&str>, ty: &str| Person {
+            id,
+            name: name.to_owned(),
+            email: email.map(str::to_owned),
+            r#type: ty.to_owned(),
+            cached: vec![],
+        };
+        assert_eq!(
+            people,
+            [
+                person(1, "Ada", Some("ada@example.com"), "admin"),
+                person(2, "Bob", None, "user")
+            ]
+        );
+
+        let err = db
+            .query_row("SELECT id, full_name, type FROM person", [], Person::from_row)
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidColumnName(ref name) if name == "email"),
+            "{err:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn test_query_json() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let json = db.query_json(
+            "SELECT i, i::VARCHAR AS s, [i, NULL] AS l, {'x': 1.5} AS st FROM range(?) t(i)",
+            [2],
+        )?;
+        assert_eq!(
+            json,
+            r#"[{"i":0,"s":"0","l":[0,null],"st":{"x":1.5}},{"i":1,"s":"1","l":[1,null],"st":{"x":1.5}}]"#
+        );
+        assert_eq!(db.query_json("SELECT 1 AS a WHERE false", [])?, "[]");
+        assert_eq!(
+            db.query_json("SELECT {'b': 1, 'a': MAP {2: 'x'}} AS z, 0 AS y", [])?,
+            r#"[{"z":{"b":1,"a":{"2":"x"}},"y":0}]"#
+        );
+
+        let mut out = Vec::new();
+        db.write_json(&mut out, "SELECT * FROM range(3000)", [])?;
+        let rows: Vec<serde_json::Value> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(rows.len(), 3000);
+        assert_eq!(rows[2999], serde_json::json!({"range": 2999}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_read_only() -> Result<()> {
+        let db = checked_memory_handle();
+        db.execute_batch("CREATE TABLE foo(x INTEGER); INSERT INTO foo VALUES (1)")?;
+
+        db.execute_read_only("SELECT * FROM foo WHERE x = ?", [1])?;
+        db.execute_read_only("WITH t AS (SELECT 1) SELECT * FROM t", [])?;
+        db.execute_read_only(" -- comment\n/* block */ EXPLAIN SELECT * FROM foo", [])?;
+        let rows: i64 = db
+            .prepare_read_only("SELECT count(*) FROM foo")?
+            .query_row([], |r| r.get(0))?;
+        assert_eq!(rows, 1);
+
+        for (sql, kind) in [
+            ("INSERT INTO foo VALUES (2)", StatementType::Insert),
+            (
+                "WITH t AS (SELECT 2) INSERT INTO foo SELECT * FROM t",
+                StatementType::Insert,
+            ),
+            ("DELETE FROM foo", StatementType::Delete),
+            ("DROP TABLE foo", StatementType::Drop),
+            ("SET threads = 1", StatementType::Set),
+            ("PRAGMA table_info('foo')", StatementType::Select),
+            ("explain analyze INSERT INTO foo VALUES (2)", StatementType::Explain),
+        ] {
+            assert_eq!(
+                db.execute_read_only(sql, []).unwrap_err(),
+                Error::ReadOnlyViolation(kind),
+                "{sql}"
+            );
+        }
+        let rows: i64 = db.query_row("SELECT count(*) FROM foo", [], |r| r.get(0))?;
+        assert_eq!(rows, 1);
+        Ok(())
+    }
+
     #[test]
     fn test_prepare_column_names() -> Result<()> {
         let db = checked_memory_handle();
@@ -904,6 +1634,96 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_query_row_borrowed() -> Result<()> {
+        let db = checked_memory_handle();
+        db.execute_batch("CREATE TABLE foo(b BLOB, t TEXT); INSERT INTO foo VALUES ('\\x01\\x02\\xFF'::BLOB, 'duck')")?;
+
+        let (sum, len) = db.query_row("SELECT b, t FROM foo", [], |r| {
+            let blob: &[u8] = r.get_ref(0)?.as_blob()?;
+            let text: &str = r.get_ref("t")?.as_str()?;
+            Ok((blob.iter().map(|&b| b as u32).sum::<u32>(), text.len()))
+        })?;
+        assert_eq!((sum, len), (0x102, 4));
+
+        let err = db.query_row("SELECT 1", [], |r| Ok(r.get_ref(0)?.as_blob()?.len()));
+        assert!(err.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_enable_deterministic_mode() -> Result<()> {
+        let db = checked_memory_handle();
+        db.execute_batch("CREATE TABLE foo(x INTEGER); INSERT INTO foo VALUES (2), (NULL), (1)")?;
+
+        // the null order is applied when a statement is prepared
+        let sorted = || -> Result<Vec<Option<i32>>> {
+            let mut stmt = db.prepare("SELECT x FROM foo ORDER BY x")?;
+            let rows = stmt.iter([])?.collect();
+            rows
+        };
+        db.set_null_sort(DefaultNullOrder::NullsFirst)?;
+        assert_eq!(sorted()?, [None, Some(1), Some(2)]);
+
+        db.enable_deterministic_mode()?;
+        assert_eq!(sorted()?, [Some(1), Some(2), None]);
+        let settings = db.query_row(
+            "SELECT current_setting('threads'), current_setting('preserve_insertion_order'),
+                    current_setting('enable_progress_bar_print')",
+            [],
+            |r| Ok((r.get::<_, i64>(0)?, r.get::<_, bool>(1)?, r.get::<_, bool>(2)?)),
+        )?;
+        assert_eq!(settings, (1, true, false));
+        Ok(())
+    }
+
+    #[test]
+    fn test_deterministic_mode_keeps_progress_handler() -> Result<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE t AS SELECT range AS x FROM range(5000)")?;
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = calls.clone();
+        db.set_progress_handler(Some(Box::new(move |_| {
+            counted.fetch_add(1, Ordering::Relaxed);
+            true
+        })))?;
+        db.enable_deterministic_mode()?;
+
+        let heavy = "SELECT count(*) FROM t t1, t t2 WHERE t1.x + t2.x = 4999";
+        assert_eq!(db.query_row(heavy, [], |r| r.get::<_, i64>(0))?, 5000);
+        assert!(calls.load(Ordering::Relaxed) > 0);
+        assert!(db.query_row("SELECT current_setting('enable_progress_bar')", [], |r| r
+            .get::<_, bool>(0))?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_returning() -> Result<()> {
+        let db = checked_memory_handle();
+        db.execute_batch(
+            "CREATE SEQUENCE people_id START 10;
+             CREATE TABLE people(id INTEGER DEFAULT nextval('people_id'), name TEXT)",
+        )?;
+
+        let id: i32 = db.execute_returning_one("INSERT INTO people (name) VALUES (?) RETURNING id", ["Ann"])?;
+        assert_eq!(id, 10);
+        let ids: Vec<i32> =
+            db.execute_returning("INSERT INTO people (name) VALUES (?), (?) RETURNING id", ["Bob", "Cat"])?;
+        assert_eq!(ids, [11, 12]);
+
+        let err = db
+            .execute_returning_one::<i32, _>("UPDATE people SET name = upper(name) RETURNING id", [])
+            .unwrap_err();
+        assert_eq!(err, Error::StatementChangedRows(3));
+        let err = db
+            .execute_returning_one::<i32, _>("DELETE FROM people WHERE id > 100 RETURNING id", [])
+            .unwrap_err();
+        assert_eq!(err, Error::StatementChangedRows(0));
+        Ok(())
+    }
+
     #[test]
     fn test_optional() -> Result<()> {
         let db = checked_memory_handle();
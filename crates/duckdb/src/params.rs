@@ -86,6 +86,26 @@ use sealed::Sealed;
 /// }
 /// ```
 ///
+/// ## Named parameters
+///
+/// Parameters written as `$name` in the SQL can be bound by name, using the
+/// [`duckdb::named_params!`](crate::named_params!) macro, or a slice of
+/// `(name, value)` pairs, `&[(&str, &dyn ToSql)]`. The leading `$` of a name
+/// may be omitted. Naming a parameter the statement doesn't have is an
+/// [`Error::InvalidParameterName`](crate::Error::InvalidParameterName), and
+/// leaving one out makes executing the statement fail.
+///
+/// ### Example (named)
+///
+/// ```rust,no_run
+/// # use duckdb::{Connection, Result, named_params};
+/// fn insert(conn: &Connection) -> Result<()> {
+///     let mut stmt = conn.prepare("INSERT INTO test (id, name) VALUES ($id, $name)")?;
+///     stmt.execute(named_params! { "$id": 5, "$name": "foo" })?;
+///     Ok(())
+/// }
+/// ```
+///
 /// ## No parameters
 ///
 /// You can just use an empty array literal for no params. The
@@ -141,6 +161,14 @@ impl Params for [&dyn ToSql; 0] {
     }
 }
 
+impl Sealed for &[(&str, &dyn ToSql)] {}
+impl Params for &[(&str, &dyn ToSql)] {
+    #[inline]
+    fn __bind_in(self, stmt: &mut Statement<'_>) -> Result<()> {
+        stmt.bind_parameters_named(self)
+    }
+}
+
 impl Sealed for &[&dyn ToSql] {}
 impl Params for &[&dyn ToSql] {
     #[inline]
@@ -303,3 +331,29 @@ where
         stmt.bind_parameters(self.0)
     }
 }
+
+/// A struct whose fields can be bound as the parameters of a statement, in
+/// declaration order.
+///
+/// With the `derive` feature, this can be derived with `#[derive(ToSqlRow)]`,
+/// leaving out fields marked `#[column(skip)]`:
+///
+/// ```rust,ignore
+/// use duckdb::{Connection, Result, ToSqlRow};
+///
+/// #[derive(ToSqlRow)]
+/// struct Person {
+///     id: i32,
+///     name: String,
+///     #[column(skip)]
+///     cached_age: Option<u32>,
+/// }
+///
+/// fn insert(conn: &Connection, person: &Person) -> Result<usize> {
+///     conn.execute("INSERT INTO person (id, name) VALUES (?, ?)", person.to_params())
+/// }
+/// ```
+pub trait ToSqlRow {
+    /// Returns the fields to bind, in order.
+    fn to_params(&self) -> ParamsFromIter<Vec<&dyn ToSql>>;
+}
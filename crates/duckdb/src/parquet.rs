@@ -0,0 +1,138 @@
+use std::path::Path;
+
+use crate::{subquery, Connection, Error, Result};
+
+impl Connection {
+    /// Writes the result of the query `sql` to the Parquet file at `path`,
+    /// returning the number of rows written. An existing file is overwritten.
+    ///
+    /// The Parquet writer is provided by the `parquet` extension, which is
+    /// built in with the `parquet` feature and autoloaded otherwise.
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, ParquetCompression, ParquetOptions, Result};
+    /// fn export_report(conn: &Connection) -> Result<usize> {
+    ///     let options = ParquetOptions::default()
+    ///         .compression(ParquetCompression::Zstd)
+    ///         .row_group_size(100_000);
+    ///     conn.copy_to_parquet("SELECT * FROM sales WHERE day = today()", "reports/daily sales.parquet", &options)
+    /// }
+    /// ```
+    ///
+    /// ## Failure
+    ///
+    /// Will return [`Error::InvalidPath`] if `path` isn't valid UTF-8, and
+    /// `Err` if `sql` isn't a single query or the file can't be written.
+    pub fn copy_to_parquet<P: AsRef<Path>>(&self, sql: &str, path: P, options: &ParquetOptions) -> Result<usize> {
+        let path = path.as_ref();
+        let path = path.to_str().ok_or_else(|| Error::InvalidPath(path.to_owned()))?;
+        self.execute(&options.copy_statement(sql, path), [])
+    }
+}
+
+/// The compression codec of a Parquet file, see [`ParquetOptions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParquetCompression {
+    /// No compression
+    Uncompressed,
+    /// Snappy, DuckDB's default
+    Snappy,
+    /// Gzip
+    Gzip,
+    /// Zstandard
+    Zstd,
+    /// LZ4, written as `LZ4_RAW`
+    Lz4,
+}
+
+impl ParquetCompression {
+    fn as_str(self) -> &'static str {
+        match self {
+            ParquetCompression::Uncompressed => "UNCOMPRESSED",
+            ParquetCompression::Snappy => "SNAPPY",
+            ParquetCompression::Gzip => "GZIP",
+            ParquetCompression::Zstd => "ZSTD",
+            ParquetCompression::Lz4 => "LZ4",
+        }
+    }
+}
+
+/// Options of the Parquet files written by [`Connection::copy_to_parquet`].
+/// Options that aren't set keep DuckDB's defaults.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParquetOptions {
+    compression: Option<ParquetCompression>,
+    row_group_size: Option<usize>,
+}
+
+impl ParquetOptions {
+    /// The compression codec of the columns (`COMPRESSION`).
+    pub fn compression(mut self, compression: ParquetCompression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// The number of rows in each row group (`ROW_GROUP_SIZE`).
+    pub fn row_group_size(mut self, rows: usize) -> Self {
+        self.row_group_size = Some(rows);
+        self
+    }
+
+    /// The `COPY` statement writing the result of `sql` to `path`.
+    fn copy_statement(&self, sql: &str, path: &str) -> String {
+        let mut options = "FORMAT PARQUET".to_owned();
+        if let Some(compression) = self.compression {
+            options += &format!(", COMPRESSION {}", compression.as_str());
+        }
+        if let Some(rows) = self.row_group_size {
+            options += &format!(", ROW_GROUP_SIZE {rows}");
+        }
+        format!("COPY {} TO '{}' ({options})", subquery(sql), path.replace('\'', "''"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ParquetCompression, ParquetOptions};
+
+    #[test]
+    fn test_copy_statement() {
+        let options = ParquetOptions::default();
+        assert_eq!(
+            options.copy_statement("SELECT 1;", "out.parquet"),
+            "COPY (\nSELECT 1\n) TO 'out.parquet' (FORMAT PARQUET)"
+        );
+        let options = options.compression(ParquetCompression::Zstd).row_group_size(1000);
+        assert_eq!(
+            options.copy_statement("SELECT 1 -- one", "it's a dir/out file.parquet"),
+            "COPY (\nSELECT 1 -- one\n) TO 'it''s a dir/out file.parquet' (FORMAT PARQUET, COMPRESSION ZSTD, ROW_GROUP_SIZE 1000)"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "parquet")]
+    fn test_copy_to_parquet() -> crate::Result<()> {
+        use crate::Connection;
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo AS SELECT range AS x FROM range(5000)")?;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daily report's.parquet");
+        let options = ParquetOptions::default()
+            .compression(ParquetCompression::Zstd)
+            .row_group_size(1000);
+        assert_eq!(
+            db.copy_to_parquet("SELECT x FROM foo WHERE x % 2 = 0;", &path, &options)?,
+            2500
+        );
+
+        let sql = format!(
+            "SELECT count(*), max(x) FROM read_parquet('{}')",
+            path.to_str().unwrap().replace('\'', "''")
+        );
+        let (count, max): (i64, i64) = db.query_row(&sql, [], |r| Ok((r.get(0)?, r.get(1)?)))?;
+        assert_eq!((count, max), (2500, 4998));
+        Ok(())
+    }
+}
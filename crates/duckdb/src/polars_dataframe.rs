@@ -83,4 +83,22 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_query_polars_df() -> Result<()> {
+        let db = checked_memory_handle();
+        db.execute_batch("CREATE TABLE test AS SELECT i % 3 AS k, i AS v FROM range(10000) t(i)")?;
+
+        let mut stmt = db.prepare("SELECT k, v FROM test WHERE v >= ?")?;
+        let df = stmt.query_polars_df([100])?;
+        assert_eq!(df.height(), 9900);
+        assert_eq!(df.get_column_names(), ["k", "v"]);
+        assert_eq!(df.column("v").unwrap().i64().unwrap().sum().unwrap(), 49_995_000 - 4950);
+
+        let df = stmt.query_polars_df([10_000])?;
+        assert_eq!(df.height(), 0);
+        assert_eq!(df.get_column_names(), ["k", "v"]);
+        assert_eq!(df.column("v").unwrap().dtype(), &DataType::Int64);
+        Ok(())
+    }
 }
@@ -2,10 +2,12 @@
 
 use std::ops::Deref;
 
+#[cfg(feature = "vtab")]
+use crate::vtab::LogicalTypeId;
 use crate::{
     error::Error,
     ffi,
-    types::{ToSql, ToSqlOutput, ValueRef},
+    types::{ToSql, ToSqlOutput, Type, ValueRef},
     Connection, DatabaseName, Result, Row,
 };
 
@@ -61,6 +63,12 @@ impl Sql {
         let value = match value {
             ToSqlOutput::Borrowed(v) => v,
             ToSqlOutput::Owned(ref v) => ValueRef::from(v),
+            ToSqlOutput::List(..) => {
+                return Err(Error::DuckDBFailure(
+                    ffi::Error::new(ffi::DuckDBError),
+                    Some("Unsupported value: LIST".to_owned()),
+                ))
+            }
         };
         match value {
             ValueRef::BigInt(i) => {
@@ -133,6 +141,18 @@ impl Sql {
     }
 }
 
+/// Memory usage of a database, as returned by
+/// [`Connection::total_memory_usage`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Bytes held in memory by the buffer manager.
+    pub used: u64,
+    /// The `memory_limit` setting, in bytes.
+    pub limit: u64,
+    /// Bytes spilled to temporary files.
+    pub temporary: u64,
+}
+
 impl Deref for Sql {
     type Target = str;
 
@@ -254,6 +274,177 @@ impl Connection {
         sql.push_value(pragma_value)?;
         self.query_row(&sql, [], f)
     }
+
+    /// Query the version of the DuckDB library as a `(major, minor, patch)` tuple.
+    ///
+    /// Any pre-release or build suffix (e.g. `-dev123`) is ignored, so callers
+    /// can compare the tuple directly to decide which features to rely on.
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn has_uhugeint(conn: &Connection) -> Result<bool> {
+    ///     Ok(conn.pragma_version()? >= (0, 10, 0))
+    /// }
+    /// ```
+    pub fn pragma_version(&self) -> Result<(u32, u32, u32)> {
+        let version: String = self.pragma_query_value(None, "version", |row| row.get(0))?;
+        parse_version(&version).ok_or_else(|| {
+            Error::FromSqlConversionFailure(0, Type::Text, format!("invalid DuckDB version: {version}").into())
+        })
+    }
+
+    /// Query the `default_collation` setting, or `None` if strings are compared
+    /// byte-wise.
+    ///
+    /// Collations are applied by DuckDB's own operators only: strings handed to
+    /// Rust callbacks such as table functions are raw UTF-8, so code comparing
+    /// them has to apply the collation itself, e.g. with
+    /// [`normalize_collation`](Connection::normalize_collation).
+    pub fn default_collation(&self) -> Result<Option<String>> {
+        let collation: String = self.query_row("SELECT current_setting('default_collation')", [], |row| row.get(0))?;
+        Ok(Some(collation).filter(|c| !c.is_empty() && !c.eq_ignore_ascii_case("binary")))
+    }
+
+    /// Normalize `value` according to the connection's default collation, so
+    /// that two strings compare equal under the collation exactly when their
+    /// normalized forms are equal.
+    ///
+    /// The built-in `nocase`, `noaccent` and `nfc` collations, and chains of
+    /// them such as `nocase.noaccent`, are supported. This runs a query, so
+    /// prefer normalizing values once rather than on every comparison.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` for any other collation, e.g. ICU locales.
+    pub fn normalize_collation(&self, value: &str) -> Result<String> {
+        let mut expr = String::from("?");
+        for collation in self.default_collation()?.iter().flat_map(|c| c.split('.')) {
+            let function = match collation.to_ascii_lowercase().as_str() {
+                "nocase" => "lower",
+                "noaccent" => "strip_accents",
+                "nfc" => "nfc_normalize",
+                "binary" => continue,
+                _ => {
+                    return Err(Error::DuckDBFailure(
+                        ffi::Error::new(ffi::DuckDBError),
+                        Some(format!("Unsupported collation \"{collation}\"")),
+                    ))
+                }
+            };
+            expr = format!("{function}({expr})");
+        }
+        self.query_row(&format!("SELECT {expr}"), [value], |row| row.get(0))
+    }
+
+    /// Seed the connection's random number generator, so that `random()`,
+    /// `gen_random_uuid()` and sampling return the same values on every run.
+    ///
+    /// `seed` must be between -1.0 and 1.0, inclusive. Each thread draws its
+    /// own stream from the seeded generator, so results are only reproducible
+    /// when the query runs single-threaded, e.g. after `SET threads = 1`.
+    pub fn set_seed(&self, seed: f64) -> Result<()> {
+        self.query_row("SELECT setseed(?)", [seed], |_| Ok(()))
+    }
+
+    /// Query the memory usage of the database this connection belongs to.
+    ///
+    /// Memory is accounted per database, so every connection to the same
+    /// database reports the same figures. The tables of an in-memory database
+    /// count towards `used` until they are spilled to temporary files, while
+    /// those of a file database can be evicted back to the file, so an
+    /// in-memory database reports more memory for the same data.
+    ///
+    /// DuckDB reports `limit` rounded down to a tenth of its unit, e.g.
+    /// `12.3 GiB`, so it is only exact for round values.
+    pub fn total_memory_usage(&self) -> Result<MemoryUsage> {
+        let sql = "SELECT sum(memory_usage_bytes)::UBIGINT, sum(temporary_storage_bytes)::UBIGINT, \
+                   current_setting('memory_limit') FROM duckdb_memory()";
+        let (used, temporary, limit): (u64, u64, String) =
+            self.query_row(sql, [], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        let limit = parse_bytes(&limit).ok_or_else(|| {
+            Error::FromSqlConversionFailure(2, Type::Text, format!("invalid memory limit: {limit}").into())
+        })?;
+        Ok(MemoryUsage { used, limit, temporary })
+    }
+
+    /// Check whether `type_id` can be used on this connection.
+    ///
+    /// This probes the database by casting `NULL` to a representative type of
+    /// that kind rather than comparing versions, so types provided by loaded
+    /// extensions are reported correctly as well.
+    #[cfg(feature = "vtab")]
+    pub fn supports_type(&self, type_id: LogicalTypeId) -> bool {
+        let type_name = match type_id {
+            LogicalTypeId::Boolean => "BOOLEAN",
+            LogicalTypeId::Tinyint => "TINYINT",
+            LogicalTypeId::Smallint => "SMALLINT",
+            LogicalTypeId::Integer => "INTEGER",
+            LogicalTypeId::Bigint => "BIGINT",
+            LogicalTypeId::UTinyint => "UTINYINT",
+            LogicalTypeId::USmallint => "USMALLINT",
+            LogicalTypeId::UInteger => "UINTEGER",
+            LogicalTypeId::UBigint => "UBIGINT",
+            LogicalTypeId::Float => "FLOAT",
+            LogicalTypeId::Double => "DOUBLE",
+            LogicalTypeId::Timestamp => "TIMESTAMP",
+            LogicalTypeId::Date => "DATE",
+            LogicalTypeId::Time => "TIME",
+            LogicalTypeId::Interval => "INTERVAL",
+            LogicalTypeId::Hugeint => "HUGEINT",
+            LogicalTypeId::UHugeint => "UHUGEINT",
+            LogicalTypeId::Varchar => "VARCHAR",
+            LogicalTypeId::Blob => "BLOB",
+            LogicalTypeId::Decimal => "DECIMAL(18, 3)",
+            LogicalTypeId::TimestampS => "TIMESTAMP_S",
+            LogicalTypeId::TimestampMs => "TIMESTAMP_MS",
+            LogicalTypeId::TimestampNs => "TIMESTAMP_NS",
+            LogicalTypeId::Enum => "ENUM('a')",
+            LogicalTypeId::List => "INTEGER[]",
+            LogicalTypeId::Struct => "STRUCT(a INTEGER)",
+            LogicalTypeId::Map => "MAP(INTEGER, INTEGER)",
+            LogicalTypeId::Uuid => "UUID",
+            LogicalTypeId::Union => "UNION(a INTEGER)",
+            LogicalTypeId::TimestampTZ => "TIMESTAMPTZ",
+            LogicalTypeId::TimeTZ => "TIMETZ",
+            LogicalTypeId::Array => "INTEGER[2]",
+        };
+        self.prepare(&format!("SELECT CAST(NULL AS {type_name})")).is_ok()
+    }
+}
+
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let version = version.split(['-', '+']).next()?;
+    let mut parts = version.split('.').map(str::parse::<u32>);
+    let version = (parts.next()?.ok()?, parts.next()?.ok()?, parts.next()?.ok()?);
+    parts.next().is_none().then_some(version)
+}
+
+/// Parse a size formatted like DuckDB's `BytesToHumanReadableString`, e.g.
+/// `12.3 GiB`, `1.5 MB` or `512 bytes`.
+fn parse_bytes(size: &str) -> Option<u64> {
+    let (number, unit) = size.trim().split_once(' ')?;
+    let multiplier: u64 = match unit {
+        "byte" | "bytes" => 1,
+        "KiB" => 1 << 10,
+        "MiB" => 1 << 20,
+        "GiB" => 1 << 30,
+        "TiB" => 1 << 40,
+        "PiB" => 1 << 50,
+        "kB" | "KB" => 1_000,
+        "MB" => 1_000_000,
+        "GB" => 1_000_000_000,
+        "TB" => 1_000_000_000_000,
+        "PB" => 1_000_000_000_000_000,
+        _ => return None,
+    };
+    let (whole, tenths) = match number.split_once('.') {
+        Some((whole, tenths)) if tenths.len() == 1 => (whole, tenths.parse::<u64>().ok()?),
+        Some(_) => return None,
+        None => (number, 0),
+    };
+    let whole = whole.parse::<u64>().ok()?.checked_mul(multiplier)?;
+    whole.checked_add(tenths * multiplier / 10)
 }
 
 fn is_identifier(s: &str) -> bool {
@@ -291,6 +482,41 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn pragma_version() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let version = db.pragma_version()?;
+        assert!(version >= (0, 10, 0));
+        if cfg!(feature = "bundled") {
+            let expected = env!("CARGO_PKG_VERSION")
+                .split('.')
+                .map(|p| p.parse().unwrap())
+                .collect::<Vec<u32>>();
+            assert_eq!(version, (expected[0], expected[1], expected[2]));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(pragma::parse_version("v0.10.2"), Some((0, 10, 2)));
+        assert_eq!(pragma::parse_version("v1.1.0-dev1234"), Some((1, 1, 0)));
+        assert_eq!(pragma::parse_version("0.9.2"), Some((0, 9, 2)));
+        assert_eq!(pragma::parse_version("v0.10"), None);
+        assert_eq!(pragma::parse_version("v0.10.x"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "vtab")]
+    fn supports_type() {
+        use crate::vtab::LogicalTypeId;
+
+        let db = Connection::open_in_memory().unwrap();
+        assert!(db.supports_type(LogicalTypeId::Integer));
+        assert!(db.supports_type(LogicalTypeId::Union));
+        assert!(db.supports_type(LogicalTypeId::UHugeint));
+    }
+
     #[test]
     #[ignore = "not supported"]
     fn pragma_query_with_schema() -> Result<()> {
@@ -367,4 +593,66 @@ mod test {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_collation() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        assert_eq!(db.default_collation()?, None);
+        assert_eq!(db.normalize_collation("Héllo")?, "Héllo");
+
+        db.execute_batch("SET default_collation = 'nocase.noaccent'")?;
+        assert_eq!(db.default_collation()?.as_deref(), Some("nocase.noaccent"));
+        assert_eq!(db.normalize_collation("Héllo")?, "hello");
+        let equal: bool = db.query_row("SELECT 'Héllo' = 'HELLO'", [], |r| r.get(0))?;
+        assert!(equal);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_bytes() {
+        assert_eq!(pragma::parse_bytes("512 bytes"), Some(512));
+        assert_eq!(pragma::parse_bytes("1 byte"), Some(1));
+        assert_eq!(pragma::parse_bytes("1.5 KiB"), Some(1536));
+        assert_eq!(
+            pragma::parse_bytes("12.3 GiB"),
+            Some(12 * (1 << 30) + 3 * (1 << 30) / 10)
+        );
+        assert_eq!(pragma::parse_bytes("2.0 MB"), Some(2_000_000));
+        assert_eq!(pragma::parse_bytes("2.0"), None);
+        assert_eq!(pragma::parse_bytes("1.25 GiB"), None);
+    }
+
+    #[test]
+    fn test_total_memory_usage() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("SET memory_limit = '1GiB'")?;
+        let before = db.total_memory_usage()?;
+        assert_eq!(before.limit, 1 << 30);
+        assert_eq!(before.temporary, 0);
+
+        db.execute_batch("CREATE TABLE t AS SELECT range AS i, range::VARCHAR AS s FROM range(1000000)")?;
+        let after = db.total_memory_usage()?;
+        assert!(after.used > before.used, "{before:?} {after:?}");
+        assert!(after.used < after.limit);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_seed() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let sample = || -> Result<Vec<f64>> {
+            let mut stmt = db.prepare("SELECT random() FROM range(5)")?;
+            let rows = stmt.query_map([], |r| r.get(0))?;
+            rows.collect()
+        };
+        db.set_seed(0.42)?;
+        let first = sample()?;
+        db.set_seed(0.42)?;
+        assert_eq!(sample()?, first);
+        db.set_seed(-0.42)?;
+        assert_ne!(sample()?, first);
+
+        assert!(db.set_seed(2.0).is_err());
+        Ok(())
+    }
 }
@@ -1,15 +1,25 @@
-use std::{ffi::CStr, ptr, rc::Rc, sync::Arc};
+use std::{
+    cell::{Cell, RefCell},
+    ffi::{CStr, CString},
+    mem, ptr,
+    rc::Rc,
+    sync::Arc,
+};
 
 use arrow::{
     array::StructArray,
-    datatypes::{DataType, Schema, SchemaRef},
+    datatypes::{DataType, Field, IntervalUnit, Schema, SchemaRef, TimeUnit, UnionMode},
     ffi::{from_ffi, FFI_ArrowArray, FFI_ArrowSchema},
 };
 
-use super::{ffi, Result};
+use super::{ffi, types::Value, Error, Result};
 #[cfg(feature = "polars")]
 use crate::arrow2;
-use crate::error::result_from_duckdb_arrow;
+use crate::{
+    error::{error_from_duckdb_code, result_from_duckdb_arrow, result_from_duckdb_pending, result_from_duckdb_result},
+    inner_connection::InnerConnection,
+    StatementType,
+};
 
 // Private newtype for raw sqlite3_stmts that finalize themselves when dropped.
 // TODO: destroy statement and result
@@ -17,7 +27,17 @@ use crate::error::result_from_duckdb_arrow;
 pub struct RawStatement {
     ptr: ffi::duckdb_prepared_statement,
     result: Option<ffi::duckdb_arrow>,
+    // Result of a statement executed with `streaming` set. Chunks are pulled
+    // from it one at a time and converted to arrow on the fly.
+    stream_result: Option<ffi::duckdb_result>,
+    streaming: bool,
+    stream_chunk: Cell<u64>,
+    stream_consumed: Cell<bool>,
+    // Error which ended a streaming result early, see `result_error`.
+    stream_error: RefCell<Option<Error>>,
     schema: Option<SchemaRef>,
+    // The SQL this statement was prepared from, if known.
+    sql: Option<CString>,
     // Cached SQL (trimmed) that we use as the key when we're in the statement
     // cache. This is None for statements which didn't come from the statement
     // cache.
@@ -37,7 +57,13 @@ impl RawStatement {
         RawStatement {
             ptr: stmt,
             result: None,
+            stream_result: None,
+            streaming: false,
+            stream_chunk: Cell::new(0),
+            stream_consumed: Cell::new(false),
+            stream_error: RefCell::new(None),
             schema: None,
+            sql: None,
             statement_cache_key: None,
         }
     }
@@ -52,6 +78,11 @@ impl RawStatement {
         self.ptr
     }
 
+    #[inline]
+    pub(crate) fn set_sql(&mut self, sql: CString) {
+        self.sql = Some(sql);
+    }
+
     #[inline]
     pub(crate) fn set_statement_cache_key(&mut self, p: impl Into<Arc<str>>) {
         self.statement_cache_key = Some(p.into());
@@ -67,18 +98,53 @@ impl RawStatement {
         unsafe { ffi::duckdb_clear_bindings(self.ptr) }
     }
 
+    #[inline]
+    pub fn set_streaming(&mut self, streaming: bool) {
+        self.streaming = streaming;
+    }
+
+    #[inline]
+    pub fn is_fetch_streaming(&self) -> bool {
+        self.streaming
+    }
+
+    #[inline]
+    pub fn is_streaming(&self) -> bool {
+        self.stream_result.is_some()
+    }
+
+    /// Whether a streaming result has been read until the end after at least
+    /// one chunk was returned.
+    #[inline]
+    pub fn is_stream_consumed(&self) -> bool {
+        self.stream_consumed.get()
+    }
+
     #[inline]
     pub fn result_unwrap(&self) -> ffi::duckdb_arrow {
         self.result.unwrap()
     }
 
+    /// NOTE: the row count of a streaming result is not known upfront, so 0 is returned
     #[inline]
     pub fn row_count(&self) -> usize {
+        if let Some(mut result) = self.stream_result {
+            return unsafe {
+                if ffi::duckdb_result_is_streaming(result) {
+                    0
+                } else {
+                    ffi::duckdb_row_count(&mut result) as usize
+                }
+            };
+        }
         unsafe { ffi::duckdb_arrow_row_count(self.result_unwrap()) as usize }
     }
 
     #[inline]
     pub fn step(&self) -> Option<StructArray> {
+        if let Some(result) = self.stream_result {
+            return self.stream_step(result);
+        }
         self.result?;
         unsafe {
             let mut arrays = FFI_ArrowArray::empty();
@@ -110,9 +176,67 @@ impl RawStatement {
         }
     }
 
+    /// Fetches the next chunk of a streaming result, which the caller must
+    /// destroy.
+    fn stream_fetch_chunk(&self, result: ffi::duckdb_result) -> Option<ffi::duckdb_data_chunk> {
+        if self.stream_consumed.get() {
+            return None;
+        }
+        unsafe {
+            // Statements which can't be streamed (e.g. DML) still come back materialized
+            let mut chunk = if ffi::duckdb_result_is_streaming(result) {
+                ffi::duckdb_stream_fetch_chunk(result)
+            } else {
+                ffi::duckdb_result_get_chunk(result, self.stream_chunk.get())
+            };
+            if chunk.is_null() || ffi::duckdb_data_chunk_get_size(chunk) == 0 {
+                if !chunk.is_null() {
+                    ffi::duckdb_destroy_data_chunk(&mut chunk);
+                }
+                if self.stream_chunk.get() > 0 {
+                    self.stream_consumed.set(true);
+                }
+                return None;
+            }
+            self.stream_chunk.set(self.stream_chunk.get() + 1);
+            Some(chunk)
+        }
+    }
+
+    fn stream_step(&self, result: ffi::duckdb_result) -> Option<StructArray> {
+        let mut chunk = self.stream_fetch_chunk(result)?;
+        unsafe {
+            let mut arrays = FFI_ArrowArray::empty();
+            ffi::duckdb_result_arrow_array(
+                result,
+                chunk,
+                &mut std::ptr::addr_of_mut!(arrays) as *mut _ as *mut ffi::duckdb_arrow_array,
+            );
+            ffi::duckdb_destroy_data_chunk(&mut chunk);
+
+            let array_data = FFI_ArrowSchema::try_from(DataType::Struct(self.schema().fields().clone()))
+                .and_then(|schema| from_ffi(arrays, &schema));
+            match array_data {
+                Ok(array_data) => Some(StructArray::from(array_data)),
+                Err(err) => self.fail_stream(err),
+            }
+        }
+    }
+
+    /// Ends a streaming result early because of `err`, which is then returned
+    /// by [`result_error`](RawStatement::result_error).
+    fn fail_stream<T>(&self, err: impl Into<Error>) -> Option<T> {
+        self.stream_consumed.set(true);
+        *self.stream_error.borrow_mut() = Some(err.into());
+        None
+    }
+
     #[cfg(feature = "polars")]
     #[inline]
     pub fn step2(&self) -> Option<arrow2::array::StructArray> {
+        if let Some(result) = self.stream_result {
+            return self.stream_step2(result);
+        }
         self.result?;
 
         unsafe {
@@ -163,14 +287,46 @@ impl RawStatement {
         }
     }
 
-    #[inline]
-    pub fn column_count(&self) -> usize {
-        unsafe { ffi::duckdb_arrow_column_count(self.result_unwrap()) as usize }
+    #[cfg(feature = "polars")]
+    fn stream_step2(&self, result: ffi::duckdb_result) -> Option<arrow2::array::StructArray> {
+        let data_type = match self.arrow2_data_type() {
+            Ok(data_type) => data_type,
+            Err(err) => return self.fail_stream(err),
+        };
+        let mut chunk = self.stream_fetch_chunk(result)?;
+        unsafe {
+            let mut ffi_arrow2_array = arrow2::ffi::ArrowArray::empty();
+            ffi::duckdb_result_arrow_array(
+                result,
+                chunk,
+                &mut std::ptr::addr_of_mut!(ffi_arrow2_array) as *mut _ as *mut ffi::duckdb_arrow_array,
+            );
+            ffi::duckdb_destroy_data_chunk(&mut chunk);
+
+            let arrow2_array = match arrow2::ffi::import_array_from_c(ffi_arrow2_array, data_type) {
+                Ok(arrow2_array) => arrow2_array,
+                Err(err) => return self.fail_stream(err),
+            };
+            // The array was imported with a struct type
+            let arrow2_struct_array = arrow2_array
+                .as_any()
+                .downcast_ref::<arrow2::array::StructArray>()
+                .expect("Failed to downcast arrow2 Array to arrow2 StructArray")
+                .to_owned();
+            Some(arrow2_struct_array)
+        }
     }
 
-    #[inline]
-    pub fn column_type(&self, idx: usize) -> DataType {
-        self.schema().field(idx).data_type().to_owned()
+    /// The arrow2 type of the struct arrays of the result, converted from its
+    /// schema through the C data interface. The statement must have been
+    /// executed.
+    #[cfg(feature = "polars")]
+    pub fn arrow2_data_type(&self) -> Result<arrow2::datatypes::ArrowDataType> {
+        let schema = FFI_ArrowSchema::try_from(DataType::Struct(self.schema().fields().clone()))?;
+        let field = unsafe {
+            arrow2::ffi::import_field_from_c(&*(std::ptr::addr_of!(schema) as *const arrow2::ffi::ArrowSchema))?
+        };
+        Ok(field.data_type)
     }
 
     #[inline]
@@ -178,18 +334,16 @@ impl RawStatement {
         self.schema.clone().unwrap()
     }
 
+    /// The schema of the current result, if the statement has been executed.
     #[inline]
-    #[allow(dead_code)]
-    pub fn column_decltype(&self, _idx: usize) -> Option<&CStr> {
-        panic!("not implemented")
+    pub fn executed_schema(&self) -> Option<&SchemaRef> {
+        self.schema.as_ref()
     }
 
     #[inline]
-    pub fn column_name(&self, idx: usize) -> Option<&String> {
-        if idx >= self.column_count() {
-            return None;
-        }
-        Some(self.schema.as_ref().unwrap().field(idx).name())
+    #[allow(dead_code)]
+    pub fn column_decltype(&self, _idx: usize) -> Option<&CStr> {
+        panic!("not implemented")
     }
 
     #[allow(dead_code)]
@@ -222,6 +376,9 @@ impl RawStatement {
     /// NOTE: if execute failed, we shouldn't call any other methods which depends on result
     pub fn execute(&mut self) -> Result<usize> {
         self.reset_result();
+        if self.streaming {
+            return self.execute_pending(None);
+        }
         unsafe {
             let mut out: ffi::duckdb_arrow = ptr::null_mut();
             let rc = ffi::duckdb_execute_prepared_arrow(self.ptr, &mut out);
@@ -231,8 +388,16 @@ impl RawStatement {
             let mut c_schema = Rc::into_raw(Rc::new(FFI_ArrowSchema::empty()));
             let rc = ffi::duckdb_query_arrow_schema(out, &mut c_schema as *mut _ as *mut ffi::duckdb_arrow_schema);
             if rc != ffi::DuckDBSuccess {
+                // DuckDB doesn't set an error on the result when the schema
+                // can't be converted, so there is nothing to report but this.
                 Rc::from_raw(c_schema);
-                result_from_duckdb_arrow(rc, out)?;
+                ffi::duckdb_destroy_arrow(&mut out);
+                return Err(Error::DuckDBFailure(
+                    ffi::Error::new(rc),
+                    Some(
+                        "the result has a column of a type that can't be converted to Arrow, such as TIMETZ".to_owned(),
+                    ),
+                ));
             }
             self.schema = Some(Arc::new(Schema::try_from(&*c_schema).unwrap()));
             Rc::from_raw(c_schema);
@@ -242,9 +407,115 @@ impl RawStatement {
         }
     }
 
+    /// Executes the statement without converting the result to Arrow, and
+    /// returns the value of the first column of the first row, if it has a
+    /// type [`scalar_value`] reads.
+    ///
+    /// Otherwise, the result is kept to be read like a streaming one, which
+    /// reads the chunks of a materialized result in turn.
+    pub fn execute_scalar(&mut self) -> Result<Option<Value>> {
+        self.reset_result();
+        unsafe {
+            let mut out: ffi::duckdb_result = mem::zeroed();
+            let rc = ffi::duckdb_execute_prepared(self.ptr, &mut out);
+            result_from_duckdb_result(rc, &mut out)?;
+            self.stream_result = Some(out);
+            self.schema = Some(Arc::new(streaming_schema(&mut out)?));
+
+            let mut chunk = ffi::duckdb_result_get_chunk(out, 0);
+            if chunk.is_null() {
+                return Ok(None);
+            }
+            let value = if ffi::duckdb_data_chunk_get_size(chunk) > 0 && ffi::duckdb_column_count(&mut out) > 0 {
+                scalar_value(ffi::duckdb_data_chunk_get_vector(chunk, 0))
+            } else {
+                None
+            };
+            ffi::duckdb_destroy_data_chunk(&mut chunk);
+            Ok(value)
+        }
+    }
+
+    /// Executes the statement like [`execute`](RawStatement::execute), calling
+    /// the progress handler of `conn` while it runs.
+    ///
+    /// The result isn't converted to Arrow: like a streaming one, it's read a
+    /// chunk at a time, from memory unless streaming is set.
+    pub fn execute_with_progress(&mut self, conn: &mut InnerConnection) -> Result<usize> {
+        self.reset_result();
+        self.execute_pending(Some(conn))
+    }
+
+    fn execute_pending(&mut self, conn: Option<&mut InnerConnection>) -> Result<usize> {
+        unsafe {
+            let mut pending: ffi::duckdb_pending_result = ptr::null_mut();
+            let rc = if self.streaming {
+                ffi::duckdb_pending_prepared_streaming(self.ptr, &mut pending)
+            } else {
+                ffi::duckdb_pending_prepared(self.ptr, &mut pending)
+            };
+            result_from_duckdb_pending(rc, pending)?;
+
+            let mut out = match conn {
+                Some(conn) => conn.execute_pending(pending)?,
+                None => {
+                    let mut out: ffi::duckdb_result = mem::zeroed();
+                    let rc = ffi::duckdb_execute_pending(pending, &mut out);
+                    ffi::duckdb_destroy_pending(&mut pending);
+                    result_from_duckdb_result(rc, &mut out)?;
+                    out
+                }
+            };
+
+            let rows_changed = if ffi::duckdb_result_is_streaming(out) {
+                0
+            } else {
+                ffi::duckdb_rows_changed(&mut out)
+            };
+            // `duckdb_prepared_arrow_schema` only describes the parameters, so
+            // the result schema is derived from the column types instead.
+            let schema = match streaming_schema(&mut out) {
+                Ok(schema) => schema,
+                Err(e) => {
+                    ffi::duckdb_destroy_result(&mut out);
+                    return Err(e);
+                }
+            };
+            self.schema = Some(Arc::new(schema));
+
+            self.stream_result = Some(out);
+            Ok(rows_changed as usize)
+        }
+    }
+
+    /// Returns the error of the current result, set when fetching a chunk of
+    /// a streaming result or converting it to Arrow fails.
+    pub fn result_error(&self) -> Result<()> {
+        if let Some(err) = self.stream_error.borrow_mut().take() {
+            return Err(err);
+        }
+        let Some(mut result) = self.stream_result else {
+            return Ok(());
+        };
+        unsafe {
+            let c_err = ffi::duckdb_result_error(&mut result);
+            if c_err.is_null() {
+                return Ok(());
+            }
+            let message = CStr::from_ptr(c_err).to_string_lossy().into_owned();
+            error_from_duckdb_code(ffi::DuckDBError, Some(message))
+        }
+    }
+
     #[inline]
     pub fn reset_result(&mut self) {
         self.schema = None;
+        self.stream_chunk.set(0);
+        self.stream_consumed.set(false);
+        self.stream_error.take();
+        if let Some(mut result) = self.stream_result.take() {
+            unsafe { ffi::duckdb_destroy_result(&mut result) };
+        }
         if self.result.is_some() {
             unsafe {
                 ffi::duckdb_destroy_arrow(&mut self.result_unwrap());
@@ -253,6 +524,19 @@ impl RawStatement {
         }
     }
 
+    #[inline]
+    pub fn statement_type(&self) -> StatementType {
+        unsafe { ffi::duckdb_prepared_statement_type(self.ptr) }.into()
+    }
+
+    /// The type DuckDB inferred for the parameter `idx` (one-based) from the
+    /// statement, or `DUCKDB_TYPE_INVALID` if it takes the type of the bound
+    /// value. Only reliable before the statement is executed.
+    #[inline]
+    pub fn parameter_type(&self, idx: usize) -> ffi::duckdb_type {
+        unsafe { ffi::duckdb_param_type(self.ptr, idx as u64) }
+    }
+
     #[inline]
     pub fn bind_parameter_count(&self) -> usize {
         unsafe { ffi::duckdb_nparams(self.ptr) as usize }
@@ -260,7 +544,7 @@ impl RawStatement {
 
     #[inline]
     pub fn sql(&self) -> Option<&CStr> {
-        panic!("not supported")
+        self.sql.as_deref()
     }
 }
 
@@ -272,3 +556,145 @@ impl Drop for RawStatement {
         }
     }
 }
+
+/// Builds the arrow schema of a `duckdb_result`, mirroring the types DuckDB's
+/// arrow converter produces for each chunk.
+unsafe fn streaming_schema(result: &mut ffi::duckdb_result) -> Result<Schema> {
+    let mut fields = Vec::new();
+    for i in 0..ffi::duckdb_column_count(result) {
+        let name = CStr::from_ptr(ffi::duckdb_column_name(result, i)).to_string_lossy();
+        let data_type = with_logical_type(ffi::duckdb_column_logical_type(result, i), |ty| arrow_data_type(ty))?;
+        fields.push(Field::new(name, data_type, true));
+    }
+    Ok(Schema::new(fields))
+}
+
+/// Reads the first row of `vector`, if it is a boolean, numeric, `VARCHAR` or
+/// `BLOB` vector.
+unsafe fn scalar_value(vector: ffi::duckdb_vector) -> Option<Value> {
+    let type_id = with_logical_type(ffi::duckdb_vector_get_column_type(vector), |ty| {
+        ffi::duckdb_get_type_id(ty)
+    });
+    let data = ffi::duckdb_vector_get_data(vector);
+    let valid = ffi::duckdb_validity_row_is_valid(ffi::duckdb_vector_get_validity(vector), 0);
+    let value = match type_id {
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN => Value::Boolean(*data.cast::<u8>() != 0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_TINYINT => Value::TinyInt(*data.cast()),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT => Value::SmallInt(*data.cast()),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_INTEGER => Value::Int(*data.cast()),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_BIGINT => Value::BigInt(*data.cast()),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => Value::UTinyInt(*data.cast()),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => Value::USmallInt(*data.cast()),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER => Value::UInt(*data.cast()),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_UBIGINT => Value::UBigInt(*data.cast()),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_FLOAT => Value::Float(*data.cast()),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE => Value::Double(*data.cast()),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR | ffi::DUCKDB_TYPE_DUCKDB_TYPE_BLOB if valid => {
+            let string = &*data.cast::<ffi::duckdb_string_t>();
+            let len = string.value.inlined.length as usize;
+            let ptr = if ffi::duckdb_string_is_inlined(*string) {
+                string.value.inlined.inlined.as_ptr()
+            } else {
+                string.value.pointer.ptr
+            };
+            let bytes = std::slice::from_raw_parts(ptr.cast::<u8>(), len);
+            if type_id == ffi::DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR {
+                Value::Text(String::from_utf8_lossy(bytes).into_owned())
+            } else {
+                Value::Blob(bytes.to_vec())
+            }
+        }
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR | ffi::DUCKDB_TYPE_DUCKDB_TYPE_BLOB => Value::Null,
+        _ => return None,
+    };
+    Some(if valid { value } else { Value::Null })
+}
+
+unsafe fn with_logical_type<T>(mut ty: ffi::duckdb_logical_type, f: impl FnOnce(ffi::duckdb_logical_type) -> T) -> T {
+    let res = f(ty);
+    ffi::duckdb_destroy_logical_type(&mut ty);
+    res
+}
+
+unsafe fn owned_c_string(ptr: *mut std::os::raw::c_char) -> String {
+    let s = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+    ffi::duckdb_free(ptr as *mut std::os::raw::c_void);
+    s
+}
+
+unsafe fn arrow_data_type(ty: ffi::duckdb_logical_type) -> Result<DataType> {
+    Ok(match ffi::duckdb_get_type_id(ty) {
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN => DataType::Boolean,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_TINYINT => DataType::Int8,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT => DataType::Int16,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_INTEGER => DataType::Int32,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_BIGINT => DataType::Int64,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => DataType::UInt8,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => DataType::UInt16,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER => DataType::UInt32,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_UBIGINT => DataType::UInt64,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_FLOAT => DataType::Float32,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE => DataType::Float64,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT => DataType::Decimal128(38, 0),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_DECIMAL => {
+            DataType::Decimal128(ffi::duckdb_decimal_width(ty), ffi::duckdb_decimal_scale(ty) as i8)
+        }
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR | ffi::DUCKDB_TYPE_DUCKDB_TYPE_UUID => DataType::Utf8,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_BLOB | ffi::DUCKDB_TYPE_DUCKDB_TYPE_BIT => DataType::Binary,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_DATE => DataType::Date32,
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_TIME => DataType::Time64(TimeUnit::Microsecond),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP => DataType::Timestamp(TimeUnit::Microsecond, None),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_S => DataType::Timestamp(TimeUnit::Second, None),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_MS => DataType::Timestamp(TimeUnit::Millisecond, None),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_NS => DataType::Timestamp(TimeUnit::Nanosecond, None),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_TZ => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_INTERVAL => DataType::Interval(IntervalUnit::MonthDayNano),
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_LIST => {
+            let child = with_logical_type(ffi::duckdb_list_type_child_type(ty), |c| arrow_data_type(c))?;
+            DataType::List(Arc::new(Field::new("l", child, true)))
+        }
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_ARRAY => {
+            let child = with_logical_type(ffi::duckdb_array_type_child_type(ty), |c| arrow_data_type(c))?;
+            let size = ffi::duckdb_array_type_array_size(ty) as i32;
+            DataType::FixedSizeList(Arc::new(Field::new("", child, true)), size)
+        }
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_STRUCT => {
+            let mut fields = Vec::new();
+            for i in 0..ffi::duckdb_struct_type_child_count(ty) {
+                let name = owned_c_string(ffi::duckdb_struct_type_child_name(ty, i));
+                let child = with_logical_type(ffi::duckdb_struct_type_child_type(ty, i), |c| arrow_data_type(c))?;
+                fields.push(Field::new(name, child, true));
+            }
+            DataType::Struct(fields.into())
+        }
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_MAP => {
+            let key = with_logical_type(ffi::duckdb_map_type_key_type(ty), |c| arrow_data_type(c))?;
+            let value = with_logical_type(ffi::duckdb_map_type_value_type(ty), |c| arrow_data_type(c))?;
+            let entries = DataType::Struct(vec![Field::new("key", key, true), Field::new("value", value, true)].into());
+            DataType::Map(Arc::new(Field::new("entries", entries, true)), false)
+        }
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_UNION => {
+            let mut fields = Vec::new();
+            for i in 0..ffi::duckdb_union_type_member_count(ty) {
+                let name = owned_c_string(ffi::duckdb_union_type_member_name(ty, i));
+                let child = with_logical_type(ffi::duckdb_union_type_member_type(ty, i), |c| arrow_data_type(c))?;
+                fields.push((i as i8, Arc::new(Field::new(name, child, true))));
+            }
+            DataType::Union(fields.into_iter().collect(), UnionMode::Sparse)
+        }
+        ffi::DUCKDB_TYPE_DUCKDB_TYPE_ENUM => {
+            let key = match ffi::duckdb_enum_internal_type(ty) {
+                ffi::DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => DataType::UInt8,
+                ffi::DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => DataType::UInt16,
+                _ => DataType::UInt32,
+            };
+            DataType::Dictionary(Box::new(key), Box::new(DataType::Utf8))
+        }
+        id => {
+            return Err(Error::DuckDBFailure(
+                ffi::Error::new(ffi::DuckDBError),
+                Some(format!("unsupported column type {id} in streaming result")),
+            ))
+        }
+    })
+}
@@ -9,7 +9,6 @@ use arrow::{
 };
 use fallible_iterator::FallibleIterator;
 use fallible_streaming_iterator::FallibleStreamingIterator;
-use rust_decimal::prelude::*;
 
 /// An handle for the resulting rows of a query.
 #[must_use = "Rows is lazy and will do nothing unless consumed"]
@@ -220,6 +219,27 @@ impl<'stmt> FallibleStreamingIterator for Rows<'stmt> {
     #[inline]
     fn advance(&mut self) -> Result<()> {
         match self.stmt {
+            Some(stmt) if stmt.stmt.is_streaming() => {
+                if self.current_batch_row >= self.batch_row_count() {
+                    if self.current_row == 0 && stmt.stmt.is_stream_consumed() {
+                        return Err(Error::StreamingResultConsumed);
+                    }
+                    self.arr = Arc::new(stmt.step());
+                    if self.arr.is_none() {
+                        self.row = None;
+                        return Ok(());
+                    }
+                    self.current_batch_row = 0;
+                }
+                self.row = Some(Row {
+                    stmt,
+                    arr: self.arr.clone(),
+                    current_row: self.current_batch_row,
+                });
+                self.current_row += 1;
+                self.current_batch_row += 1;
+                Ok(())
+            }
             Some(stmt) => {
                 if self.current_row < stmt.row_count() {
                     if self.current_batch_row >= self.batch_row_count() {
@@ -300,17 +320,50 @@ impl<'stmt> Row<'stmt> {
     pub fn get<I: RowIndex, T: FromSql>(&self, idx: I) -> Result<T> {
         let idx = idx.idx(self.stmt)?;
         let value = self.value_ref(self.current_row, idx);
-        FromSql::column_result(value).map_err(|err| match err {
-            FromSqlError::InvalidType => {
-                Error::InvalidColumnType(idx, self.stmt.column_name_unwrap(idx).into(), value.data_type())
-            }
-            FromSqlError::OutOfRange(i) => Error::IntegralValueOutOfRange(idx, i),
-            FromSqlError::Other(err) => Error::FromSqlConversionFailure(idx, value.data_type(), err),
-            #[cfg(feature = "uuid")]
-            FromSqlError::InvalidUuidSize(_) => {
-                Error::InvalidColumnType(idx, self.stmt.column_name_unwrap(idx).into(), value.data_type())
-            }
-        })
+        self.convert(idx, value)
+    }
+
+    /// Get the value of a single field of a `STRUCT` column of the result row,
+    /// without reading the other fields.
+    ///
+    /// A `NULL` struct is read as a `NULL` field, so use `Option<T>` to accept
+    /// both.
+    ///
+    /// ## Failure
+    ///
+    /// Returns an `Error::InvalidColumnType` if the column is not a `STRUCT`,
+    /// or if the field (or the whole struct) is `NULL` or otherwise not a valid
+    /// source for `T`.
+    ///
+    /// Returns an `Error::InvalidColumnName` if the struct has no field named
+    /// `field`, or if `idx` is not a valid column name for this row.
+    ///
+    /// Returns an `Error::InvalidColumnIndex` if `idx` is outside the valid
+    /// column range for this row.
+    pub fn get_struct_field<I: RowIndex, T: FromSql>(&self, idx: I, field: &str) -> Result<T> {
+        let idx = idx.idx(self.stmt)?;
+        let column = self.arr.as_ref().as_ref().unwrap().column(idx);
+        let Some(array) = column.as_any().downcast_ref::<StructArray>() else {
+            let data_type = self.value_ref(self.current_row, idx).try_data_type()?;
+            return Err(Error::InvalidColumnType(
+                idx,
+                self.stmt.column_name_unwrap(idx).into(),
+                data_type,
+            ));
+        };
+        let child = array
+            .column_by_name(field)
+            .ok_or_else(|| Error::InvalidColumnName(format!("{}.{field}", self.stmt.column_name_unwrap(idx))))?;
+        let value = if array.is_null(self.current_row) {
+            ValueRef::Null
+        } else {
+            Self::value_ref_internal(self.current_row, idx, child)
+        };
+        self.convert(idx, value)
+    }
+
+    fn convert<T: FromSql>(&self, idx: usize, value: ValueRef<'_>) -> Result<T> {
+        convert_column(self.stmt, idx, value)
     }
 
     /// Get the value of a particular column of the result row as a `ValueRef`,
@@ -381,6 +434,14 @@ impl<'stmt> Row<'stmt> {
                 }
                 ValueRef::Blob(array.value(row))
             }
+            DataType::FixedSizeBinary(_) => {
+                let array = column.as_any().downcast_ref::<array::FixedSizeBinaryArray>().unwrap();
+
+                if array.is_null(row) {
+                    return ValueRef::Null;
+                }
+                ValueRef::Blob(array.value(row))
+            }
             DataType::Boolean => {
                 let array = column.as_any().downcast_ref::<array::BooleanArray>().unwrap();
 
@@ -487,7 +548,11 @@ impl<'stmt> Row<'stmt> {
                 if array.scale() == 0 {
                     return ValueRef::HugeInt(array.value(row));
                 }
-                ValueRef::Decimal(Decimal::from_i128_with_scale(array.value(row), array.scale() as u32))
+                ValueRef::Decimal {
+                    value: array.value(row),
+                    width: array.precision(),
+                    scale: array.scale() as u8,
+                }
             }
             DataType::Timestamp(unit, _) if *unit == TimeUnit::Second => {
                 let array = column.as_any().downcast_ref::<array::TimestampSecondArray>().unwrap();
@@ -644,6 +709,26 @@ impl<'stmt> Row<'stmt> {
     }
 }
 
+/// Converts the value of column `idx` of a result of `stmt`, turning a
+/// [`FromSqlError`] into the corresponding [`Error`].
+pub(crate) fn convert_column<T: FromSql>(stmt: &Statement<'_>, idx: usize, value: ValueRef<'_>) -> Result<T> {
+    FromSql::column_result(value).map_err(|err| {
+        let data_type = match value.try_data_type() {
+            Ok(t) => t,
+            Err(err) => return err,
+        };
+        match err {
+            FromSqlError::InvalidType => Error::InvalidColumnType(idx, stmt.column_name_unwrap(idx).into(), data_type),
+            FromSqlError::OutOfRange(i) => Error::IntegralValueOutOfRange(idx, i),
+            FromSqlError::Other(err) => Error::FromSqlConversionFailure(idx, data_type, err),
+            #[cfg(feature = "uuid")]
+            FromSqlError::InvalidUuidSize(_) => {
+                Error::InvalidColumnType(idx, stmt.column_name_unwrap(idx).into(), data_type)
+            }
+        }
+    })
+}
+
 impl<'stmt> AsRef<Statement<'stmt>> for Row<'stmt> {
     fn as_ref(&self) -> &Statement<'stmt> {
         self.stmt
@@ -685,6 +770,37 @@ impl RowIndex for &'_ str {
     }
 }
 
+/// A type that can be built from a result row, e.g. to pass
+/// `MyRow::from_row` to [`Statement::query_map`].
+///
+/// With the `derive` feature, this can be derived with `#[derive(FromRow)]`
+/// for structs with named fields, reading each field from the column of the
+/// same name, or the one given by `#[column(rename = "...")]`. Fields marked
+/// `#[column(skip)]` are set to their `Default`.
+///
+/// ```rust,ignore
+/// use duckdb::{Connection, FromRow, Result};
+///
+/// #[derive(FromRow)]
+/// struct Person {
+///     id: i32,
+///     #[column(rename = "full_name")]
+///     name: String,
+///     email: Option<String>,
+/// }
+///
+/// fn people(conn: &Connection) -> Result<Vec<Person>> {
+///     let mut stmt = conn.prepare("SELECT id, full_name, email FROM person")?;
+///     let rows = stmt.query_map([], Person::from_row)?;
+///     rows.collect()
+/// }
+/// ```
+pub trait FromRow: Sized {
+    /// Builds a value from `row`, failing if a column is missing or can't be
+    /// converted.
+    fn from_row(row: &Row<'_>) -> Result<Self>;
+}
+
 macro_rules! tuple_try_from_row {
     ($($field:ident),*) => {
         impl<'a, $($field,)*> convert::TryFrom<&'a Row<'a>> for ($($field,)*) where $($field: FromSql,)* {
@@ -842,4 +958,82 @@ mod tests {
         // We don't test one bigger because it's unimplemented
         Ok(())
     }
+
+    #[test]
+    fn test_get_struct_field() -> Result<()> {
+        use crate::Error;
+
+        let conn = Connection::open_in_memory()?;
+        let mut stmt =
+            conn.prepare("SELECT s, 1 AS i FROM (VALUES ({'a': 1, 'b': 'x'}), ({'a': NULL, 'b': 'y'}), (NULL)) t(s)")?;
+        let mut rows = stmt.query([])?;
+
+        let row = rows.next()?.unwrap();
+        assert_eq!(row.get_struct_field::<_, i32>(0, "a")?, 1);
+        assert_eq!(row.get_struct_field::<_, String>("s", "b")?, "x");
+        assert!(matches!(
+            row.get_struct_field::<_, i32>(0, "c"),
+            Err(Error::InvalidColumnName(name)) if name == "s.c"
+        ));
+        assert!(matches!(
+            row.get_struct_field::<_, i32>(1, "a"),
+            Err(Error::InvalidColumnType(1, ..))
+        ));
+
+        let row = rows.next()?.unwrap();
+        assert_eq!(row.get_struct_field::<_, Option<i32>>(0, "a")?, None);
+        assert!(matches!(
+            row.get_struct_field::<_, i32>(0, "a"),
+            Err(Error::InvalidColumnType(0, ..))
+        ));
+
+        let row = rows.next()?.unwrap();
+        assert_eq!(row.get_struct_field::<_, Option<String>>(0, "b")?, None);
+        assert!(row.get_struct_field::<_, String>(0, "b").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_column_type_name() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        let err = conn
+            .query_row("SELECT 1 AS id, TIMESTAMP '2024-01-01' AS created_at", [], |row| {
+                row.get::<_, Vec<u8>>(1)
+            })
+            .unwrap_err();
+        assert_eq!(format!("{err:?}"), r#"InvalidColumnType(1, "created_at", Timestamp)"#);
+        assert_eq!(
+            err.to_string(),
+            "Invalid column type Timestamp at index: 1, name: created_at"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_borrowed_text() -> Result<()> {
+        use crate::types::ValueRef;
+
+        let conn = Connection::open_in_memory()?;
+        let mut stmt =
+            conn.prepare("SELECT * FROM (VALUES ('a', 'x'::BLOB), (NULL, NULL), ('é', '\\xFF'::BLOB)) t(s, b)")?;
+        let mut rows = stmt.query([])?;
+
+        let row = rows.next()?.unwrap();
+        let (s, b) = (row.get_ref(0)?, row.get_ref(1)?);
+        assert_eq!(s.as_str()?, "a");
+        assert_eq!(s.as_bytes()?, b"a");
+        assert_eq!(b.as_bytes()?, b"x");
+        assert!(b.as_str().is_err());
+
+        let row = rows.next()?.unwrap();
+        assert_eq!(row.get_ref(0)?, ValueRef::Null);
+        assert!(row.get_ref(0)?.as_str().is_err());
+        assert!(row.get_ref(1)?.as_bytes().is_err());
+
+        let row = rows.next()?.unwrap();
+        let s = row.get_ref(0)?.as_str()?;
+        let b = row.get_ref(1)?.as_bytes()?;
+        assert_eq!((s, b), ("é", &b"\xFF"[..]));
+        Ok(())
+    }
 }
@@ -1,4 +1,11 @@
-use std::{convert, ffi::c_void, fmt, mem, os::raw::c_char, ptr, str};
+use std::{
+    cell::OnceCell,
+    convert,
+    ffi::{c_void, CString},
+    fmt, mem,
+    os::raw::c_char,
+    ptr, result, str,
+};
 
 use arrow::{array::StructArray, datatypes::SchemaRef};
 
@@ -6,15 +13,119 @@ use super::{ffi, AndThenRows, Connection, Error, MappedRows, Params, RawStatemen
 #[cfg(feature = "polars")]
 use crate::{arrow2, polars_dataframe::Polars};
 use crate::{
-    arrow_batch::Arrow,
+    arrow_batch::{Arrow, ArrowStream},
+    column::ColumnDescription,
     error::result_from_duckdb_prepare,
-    types::{TimeUnit, ToSql, ToSqlOutput},
+    row::convert_column,
+    types::{decimal_to_string, list_element_type, FromSql, OrderedMap, TimeUnit, ToSql, ToSqlOutput, Type, Value},
 };
+#[cfg(feature = "polars")]
+use polars::prelude::DataFrame;
+
+/// The kind of a SQL statement, see [`Statement::statement_type`] and
+/// [`Connection::extract_statement_types`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum StatementType {
+    /// An invalid or unknown statement
+    Invalid,
+    /// `SELECT`
+    Select,
+    /// `INSERT`
+    Insert,
+    /// `UPDATE`
+    Update,
+    /// `EXPLAIN`
+    Explain,
+    /// `DELETE`
+    Delete,
+    /// `PREPARE`
+    Prepare,
+    /// `CREATE` of a table, view, schema, ...
+    Create,
+    /// `EXECUTE`
+    Execute,
+    /// `ALTER`
+    Alter,
+    /// `BEGIN`, `COMMIT` or `ROLLBACK`
+    Transaction,
+    /// `COPY`
+    Copy,
+    /// `ANALYZE`
+    Analyze,
+    /// `SET VARIABLE`
+    VariableSet,
+    /// `CREATE MACRO` or `CREATE FUNCTION`
+    CreateFunc,
+    /// `DROP`
+    Drop,
+    /// `EXPORT DATABASE`
+    Export,
+    /// `PRAGMA`
+    Pragma,
+    /// `VACUUM`
+    Vacuum,
+    /// `CALL`
+    Call,
+    /// `SET` or `RESET`
+    Set,
+    /// `LOAD` or `INSTALL`
+    Load,
+    /// A relation built through the relational API
+    Relation,
+    /// A statement handled by a parser extension
+    Extension,
+    /// A logical plan
+    LogicalPlan,
+    /// `ATTACH`
+    Attach,
+    /// `DETACH`
+    Detach,
+    /// Multiple statements
+    Multi,
+}
+
+impl From<ffi::duckdb_statement_type> for StatementType {
+    fn from(value: ffi::duckdb_statement_type) -> Self {
+        match value {
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_SELECT => Self::Select,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_INSERT => Self::Insert,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_UPDATE => Self::Update,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_EXPLAIN => Self::Explain,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_DELETE => Self::Delete,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_PREPARE => Self::Prepare,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_CREATE => Self::Create,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_EXECUTE => Self::Execute,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_ALTER => Self::Alter,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_TRANSACTION => Self::Transaction,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_COPY => Self::Copy,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_ANALYZE => Self::Analyze,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_VARIABLE_SET => Self::VariableSet,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_CREATE_FUNC => Self::CreateFunc,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_DROP => Self::Drop,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_EXPORT => Self::Export,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_PRAGMA => Self::Pragma,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_VACUUM => Self::Vacuum,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_CALL => Self::Call,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_SET => Self::Set,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_LOAD => Self::Load,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_RELATION => Self::Relation,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_EXTENSION => Self::Extension,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_LOGICAL_PLAN => Self::LogicalPlan,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_ATTACH => Self::Attach,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_DETACH => Self::Detach,
+            ffi::duckdb_statement_type_DUCKDB_STATEMENT_TYPE_MULTI => Self::Multi,
+            _ => Self::Invalid,
+        }
+    }
+}
 
 /// A prepared statement.
 pub struct Statement<'conn> {
-    conn: &'conn Connection,
+    pub(crate) conn: &'conn Connection,
     pub(crate) stmt: RawStatement,
+    // The columns of the query before it's executed, see `describe`.
+    pub(crate) described: OnceCell<Vec<ColumnDescription>>,
 }
 
 impl Statement<'_> {
@@ -87,6 +198,44 @@ impl Statement<'_> {
         }
     }
 
+    /// Execute the prepared statement once for each set of parameters,
+    /// returning the total number of rows that were changed.
+    ///
+    /// Outside of a transaction, the executions run inside one which is only
+    /// committed once all of them succeeded, so a failing set of parameters
+    /// leaves the database untouched. Inside a [`Transaction`](crate::Transaction),
+    /// or one started by a plain `BEGIN` statement, it's up to the caller to
+    /// roll it back.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn insert_names(conn: &Connection, names: &[&str]) -> Result<usize> {
+    ///     let mut stmt = conn.prepare("INSERT INTO people (name) VALUES (?)")?;
+    ///     stmt.execute_batch_params(names.iter().map(|name| [name]))
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return the first `Err` of [`execute`](Statement::execute).
+    pub fn execute_batch_params<P: Params>(&mut self, rows: impl IntoIterator<Item = P>) -> Result<usize> {
+        let tx = if self.conn.is_autocommit() && !self.conn.in_untracked_transaction()? {
+            Some(self.conn.unchecked_transaction()?)
+        } else {
+            None
+        };
+        let mut changes = 0;
+        for params in rows {
+            changes += self.execute(params)?;
+        }
+        if let Some(tx) = tx {
+            tx.commit()?;
+        }
+        Ok(changes)
+    }
+
     /// Execute the prepared statement, returning a handle to the resulting
     /// vector of arrow RecordBatch
     ///
@@ -109,6 +258,44 @@ impl Statement<'_> {
         Ok(Arrow::new(self))
     }
 
+    /// Execute the prepared statement as a streaming result, returning an
+    /// iterator over its RecordBatches.
+    ///
+    /// Unlike [`query_arrow`](Statement::query_arrow), the result isn't
+    /// materialized: each batch is computed by DuckDB as the iterator is
+    /// advanced, so results larger than memory can be processed. An error
+    /// while computing a batch is returned by the iterator, which then ends.
+    /// Whether this statement streams its other results is unchanged, see
+    /// [`set_fetch_streaming`](Statement::set_fetch_streaming).
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Result, Connection};
+    /// fn count_rows(conn: &Connection) -> Result<usize> {
+    ///     let mut stmt = conn.prepare("SELECT * FROM 'huge.parquet'")?;
+    ///     let mut rows = 0;
+    ///     for batch in stmt.query_arrow_streaming([])? {
+    ///         rows += batch?.num_rows();
+    ///     }
+    ///     Ok(rows)
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if binding parameters fails or the statement can't
+    /// be executed.
+    pub fn query_arrow_streaming<P: Params>(&mut self, params: P) -> Result<ArrowStream<'_>> {
+        params.__bind_in(self)?;
+        let streaming = self.stmt.is_fetch_streaming();
+        self.stmt.set_streaming(true);
+        let result = self.execute_with_bound_parameters();
+        self.stmt.set_streaming(streaming);
+        result?;
+        Ok(ArrowStream::new(&mut self.stmt))
+    }
+
     /// Execute the prepared statement, returning a handle to the resulting
     /// vector of polars DataFrame.
     ///
@@ -153,6 +340,60 @@ impl Statement<'_> {
         Ok(Polars::new(self))
     }
 
+    /// Execute the prepared statement, returning its whole result as one
+    /// polars DataFrame.
+    ///
+    /// The result is streamed: every chunk fetched from DuckDB is appended
+    /// to the DataFrame without being copied, so it has one chunk per DuckDB
+    /// vector. Use [`DataFrame::as_single_chunk_par`] to make its columns
+    /// contiguous if needed. A query without rows gives an empty DataFrame
+    /// with the columns of the result.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Result, Connection};
+    /// # use polars::prelude::DataFrame;
+    /// fn get_sales(conn: &Connection) -> Result<DataFrame> {
+    ///     conn.prepare("SELECT region, amount FROM sales WHERE year = ?")?
+    ///         .query_polars_df([2024])
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if binding parameters fails, the statement can't be
+    /// executed or fails while its result is read, or a chunk can't be
+    /// converted to a DataFrame.
+    #[cfg(feature = "polars")]
+    pub fn query_polars_df<P: Params>(&mut self, params: P) -> Result<DataFrame> {
+        params.__bind_in(self)?;
+        let streaming = self.stmt.is_fetch_streaming();
+        self.stmt.set_streaming(true);
+        let result = self.execute_with_bound_parameters();
+        self.stmt.set_streaming(streaming);
+        result?;
+
+        let mut df: Option<DataFrame> = None;
+        while let Some(struct_array) = self.step2() {
+            let chunk = DataFrame::try_from(struct_array)?;
+            match df {
+                Some(ref mut df) => {
+                    df.vstack_mut(&chunk)?;
+                }
+                None => df = Some(chunk),
+            }
+        }
+        self.stmt.result_error()?;
+        match df {
+            Some(df) => Ok(df),
+            None => {
+                let empty = arrow2::array::StructArray::new_empty(self.stmt.arrow2_data_type()?);
+                Ok(DataFrame::try_from(empty)?)
+            }
+        }
+    }
+
     /// Execute the prepared statement, returning a handle to the resulting
     /// rows.
     ///
@@ -255,6 +496,30 @@ impl Statement<'_> {
         self.query(params).map(|rows| rows.mapped(f))
     }
 
+    /// Executes the prepared statement, returning an iterator over the first
+    /// column of the resulting rows converted to `T`.
+    ///
+    /// This is equivalent to `stmt.query_map(params, |row| row.get(0))`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn get_ids(conn: &Connection) -> Result<Vec<i64>> {
+    ///     let mut stmt = conn.prepare("SELECT id FROM people WHERE age > ?")?;
+    ///     stmt.iter::<i64>([18])?.collect()
+    /// }
+    /// ```
+    ///
+    /// ## Failure
+    ///
+    /// Will return `Err` if binding parameters fails. Each item is an `Err`
+    /// if the column can't be converted to `T`.
+    #[inline]
+    pub fn iter<T: FromSql>(&mut self, params: impl Params) -> Result<MappedRows<'_, FirstColumn<T>>> {
+        self.query_map(params, first_column::<T> as FirstColumn<T>)
+    }
+
     /// Executes the prepared statement and maps a function over the resulting
     /// rows, where the function returns a `Result` with `Error` type
     /// implementing `std::convert::From<Error>` (so errors can be unified).
@@ -325,6 +590,75 @@ impl Statement<'_> {
         self.query(params)?.get_expected_row().and_then(f)
     }
 
+    /// Convenience method to execute a query that is expected to return a
+    /// single value, returning the first column of the first row converted
+    /// to `T`. This is `self.query_row(params, |row| row.get(0))`, made
+    /// faster for values of a boolean, numeric, `VARCHAR` or `BLOB` column,
+    /// such as counts, which are read without converting the result to
+    /// Arrow. Values of other types are read like `query_row` does.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn count_orders(conn: &Connection, customer: i64) -> Result<i64> {
+    ///     let mut stmt = conn.prepare_cached("SELECT count(*) FROM orders WHERE customer = ?")?;
+    ///     stmt.query_scalar([customer])
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Returns `Err(QueryReturnedNoRows)` if no rows are returned, and `Err`
+    /// if the value can't be converted to `T` or the underlying DuckDB call
+    /// fails.
+    pub fn query_scalar<T: FromSql, P: Params>(&mut self, params: P) -> Result<T> {
+        params.__bind_in(self)?;
+        if self.conn.db.borrow().has_progress_handler() {
+            self.execute_with_bound_parameters()?;
+            return Rows::new(self).get_expected_row()?.get(0);
+        }
+        match self.stmt.execute_scalar()? {
+            Some(value) => convert_column(self, 0, ValueRef::from(&value)),
+            None => Rows::new(self).get_expected_row()?.get(0),
+        }
+    }
+
+    /// Choose whether subsequent executions of this statement produce a
+    /// streaming result (`true`) or a fully materialized one (`false`, the
+    /// default).
+    ///
+    /// A streaming result pulls chunks from DuckDB as rows are read, which
+    /// keeps memory bounded for large result sets.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn sum_large(conn: &Connection) -> Result<i64> {
+    ///     let mut stmt = conn.prepare("SELECT i FROM range(100000000) t(i)")?;
+    ///     stmt.set_fetch_streaming(true);
+    ///     let mut rows = stmt.query([])?;
+    ///     let mut sum = 0;
+    ///     while let Some(row) = rows.next()? {
+    ///         sum += row.get::<_, i64>(0)? % 7;
+    ///     }
+    ///     Ok(sum)
+    /// }
+    /// ```
+    ///
+    /// # Caveats
+    ///
+    /// A streaming result can only be consumed once, forward-only.
+    /// [`row_count`](Statement::row_count) is always 0 for it, and iterating
+    /// it again (e.g. through [`raw_query`](Statement::raw_query)) returns
+    /// [`Error::StreamingResultConsumed`](crate::Error::StreamingResultConsumed).
+    /// Execute the statement again to get a fresh result.
+    #[inline]
+    pub fn set_fetch_streaming(&mut self, streaming: bool) {
+        self.stmt.set_streaming(streaming);
+    }
+
     /// Return the row count
     #[inline]
     pub fn row_count(&self) -> usize {
@@ -366,12 +700,46 @@ impl Statement<'_> {
         }
     }
 
+    /// Returns the kind of this statement, e.g. [`StatementType::Select`].
+    #[inline]
+    pub fn statement_type(&self) -> StatementType {
+        self.stmt.statement_type()
+    }
+
     /// Return the number of parameters that can be bound to this statement.
     #[inline]
     pub fn parameter_count(&self) -> usize {
         self.stmt.bind_parameter_count()
     }
 
+    /// Returns the one-based index of the parameter called `name`, such as
+    /// `$id` in `SELECT * FROM test WHERE id = $id`, or `None` if the statement
+    /// has no such parameter. The leading `$` may be omitted, and names are
+    /// matched case-insensitively.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `name` contains a nul byte.
+    pub fn parameter_index(&self, name: &str) -> Result<Option<usize>> {
+        let name = CString::new(name.strip_prefix('$').unwrap_or(name))?;
+        let mut index = 0;
+        let rc = unsafe { ffi::duckdb_bind_parameter_index(self.stmt.ptr(), &mut index, name.as_ptr()) };
+        Ok((rc == ffi::DuckDBSuccess).then_some(index as usize))
+    }
+
+    pub(crate) fn bind_parameters_named<T: ?Sized + ToSql>(&mut self, params: &[(&str, &T)]) -> Result<()> {
+        // Values bound by a previous execution would otherwise be reused for
+        // the parameters missing from `params`.
+        self.stmt.clear_bindings();
+        for &(name, value) in params {
+            match self.parameter_index(name)? {
+                Some(index) => self.bind_parameter(value, index)?,
+                None => return Err(Error::InvalidParameterName(name.to_owned())),
+            }
+        }
+        Ok(())
+    }
+
     /// Low level API to directly bind a parameter to a given index.
     ///
     /// Note that the index is one-based, that is, the first parameter index is
@@ -466,9 +834,35 @@ impl Statement<'_> {
         let value = param.to_sql()?;
 
         let ptr = unsafe { self.stmt.ptr() };
+        let nested = match value {
+            ToSqlOutput::List(ref ty, ref items) => Some(unsafe { list_value(ty, items) }),
+            ToSqlOutput::Owned(Value::List(ref items)) => Some(unsafe { list_value(&list_element_type(items), items) }),
+            ToSqlOutput::Owned(ref v @ (Value::Struct(..) | Value::Array(..))) => {
+                Some(unsafe { element_value(&v.data_type(), v) })
+            }
+            ToSqlOutput::Owned(ref v @ (Value::Map(..) | Value::Union(..))) => {
+                return Err(list_bind_error(format!(
+                    "{} values cannot be bound as parameters",
+                    v.data_type()
+                )))
+            }
+            _ => None,
+        };
+        if let Some(nested) = nested {
+            return unsafe {
+                let (mut nested, mut ty) = nested?;
+                let rc = ffi::duckdb_bind_value(ptr, col as u64, nested);
+                ffi::duckdb_destroy_value(&mut nested);
+                ffi::duckdb_destroy_logical_type(&mut ty);
+                result_from_duckdb_prepare(rc, ptr)
+            };
+        }
         let value = match value {
             ToSqlOutput::Borrowed(v) => v,
+            // enum labels are cast to the enum type by DuckDB
+            ToSqlOutput::Owned(Value::Enum(ref label)) => ValueRef::Text(label.as_bytes()),
             ToSqlOutput::Owned(ref v) => ValueRef::from(v),
+            ToSqlOutput::List(..) => return Err(list_bind_error("unexpected list parameter".to_owned())),
         };
         // TODO: bind more
         let rc = match value {
@@ -497,6 +891,13 @@ impl Statement<'_> {
             ValueRef::Blob(b) => unsafe {
                 ffi::duckdb_bind_blob(ptr, col as u64, b.as_ptr() as *const c_void, b.len() as u64)
             },
+            ValueRef::Decimal { value, width, scale } => unsafe {
+                let value = ffi::duckdb_hugeint {
+                    lower: value as u64,
+                    upper: (value >> 64) as i64,
+                };
+                ffi::duckdb_bind_decimal(ptr, col as u64, ffi::duckdb_decimal { width, scale, value })
+            },
             ValueRef::Timestamp(u, i) => unsafe {
                 let micros = match u {
                     TimeUnit::Second => i * 1_000_000,
@@ -510,13 +911,27 @@ impl Statement<'_> {
                 let micros = nanos / 1_000;
                 ffi::duckdb_bind_interval(ptr, col as u64, ffi::duckdb_interval { months, days, micros })
             },
-            _ => unreachable!("not supported: {}", value.data_type()),
+            ValueRef::Date32(days) => unsafe { ffi::duckdb_bind_date(ptr, col as u64, ffi::duckdb_date { days }) },
+            ValueRef::Time64(u, v) => unsafe {
+                ffi::duckdb_bind_time(ptr, col as u64, ffi::duckdb_time { micros: u.to_micros(v) })
+            },
+            _ => {
+                return Err(list_bind_error(format!(
+                    "{} values cannot be bound as parameters",
+                    value.try_data_type()?
+                )))
+            }
         };
         result_from_duckdb_prepare(rc, ptr)
     }
 
     #[inline]
     fn execute_with_bound_parameters(&mut self) -> Result<usize> {
+        let mut db = self.conn.db.borrow_mut();
+        if db.has_progress_handler() {
+            return self.stmt.execute_with_progress(&mut db);
+        }
+        drop(db);
         self.stmt.execute()
     }
 
@@ -533,11 +948,7 @@ impl Statement<'_> {
 
 impl fmt::Debug for Statement<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let sql = if self.stmt.is_null() {
-            Ok("")
-        } else {
-            str::from_utf8(self.stmt.sql().unwrap().to_bytes())
-        };
+        let sql = self.stmt.sql().map_or(Ok(""), |sql| str::from_utf8(sql.to_bytes()));
         f.debug_struct("Statement")
             .field("conn", self.conn)
             .field("stmt", &self.stmt)
@@ -549,13 +960,213 @@ impl fmt::Debug for Statement<'_> {
 impl Statement<'_> {
     #[inline]
     pub(super) fn new(conn: &Connection, stmt: RawStatement) -> Statement<'_> {
-        Statement { conn, stmt }
+        Statement {
+            conn,
+            stmt,
+            described: OnceCell::new(),
+        }
+    }
+}
+
+/// The row mapper used by [`Statement::iter`].
+pub type FirstColumn<T> = fn(&Row<'_>) -> Result<T>;
+
+fn first_column<T: FromSql>(row: &Row<'_>) -> Result<T> {
+    row.get(0)
+}
+
+fn list_bind_error(msg: String) -> Error {
+    Error::ToSqlConversionFailure(msg.into())
+}
+
+/// Creates the DuckDB type for list elements of type `ty`.
+unsafe fn element_type(ty: &Type) -> Result<ffi::duckdb_logical_type> {
+    let id = match ty {
+        Type::Boolean => ffi::DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN,
+        Type::TinyInt => ffi::DUCKDB_TYPE_DUCKDB_TYPE_TINYINT,
+        Type::SmallInt => ffi::DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT,
+        Type::Int => ffi::DUCKDB_TYPE_DUCKDB_TYPE_INTEGER,
+        Type::BigInt => ffi::DUCKDB_TYPE_DUCKDB_TYPE_BIGINT,
+        Type::HugeInt => ffi::DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT,
+        Type::UTinyInt => ffi::DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT,
+        Type::USmallInt => ffi::DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT,
+        Type::UInt => ffi::DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER,
+        Type::UBigInt => ffi::DUCKDB_TYPE_DUCKDB_TYPE_UBIGINT,
+        Type::Float => ffi::DUCKDB_TYPE_DUCKDB_TYPE_FLOAT,
+        Type::Double => ffi::DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE,
+        Type::Text => ffi::DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR,
+        Type::Blob => ffi::DUCKDB_TYPE_DUCKDB_TYPE_BLOB,
+        // an empty list with no element type, typed like top-level lists
+        Type::Null => ffi::DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR,
+        // DuckDB's default for a bare `DECIMAL`
+        Type::Decimal => return Ok(ffi::duckdb_create_decimal_type(18, 3)),
+        Type::List(child) => {
+            let mut child = element_type(child)?;
+            let list = ffi::duckdb_create_list_type(child);
+            ffi::duckdb_destroy_logical_type(&mut child);
+            return Ok(list);
+        }
+        Type::Array(child, size) => {
+            let mut child = element_type(child)?;
+            let array = ffi::duckdb_create_array_type(child, *size as u64);
+            ffi::duckdb_destroy_logical_type(&mut child);
+            return Ok(array);
+        }
+        _ => return Err(list_bind_error(format!("{ty} list elements are not supported"))),
+    };
+    Ok(ffi::duckdb_create_logical_type(id))
+}
+
+/// Builds each of `items` as an element of type `ty`, returning them together
+/// with the element type, which is that of the first element, or `ty` for
+/// empty lists. All results must be destroyed by the caller.
+unsafe fn element_values(ty: &Type, items: &[Value]) -> Result<(Vec<ffi::duckdb_value>, ffi::duckdb_logical_type)> {
+    let mut values = Vec::with_capacity(items.len());
+    let mut child_type: ffi::duckdb_logical_type = ptr::null_mut();
+    for item in items {
+        match element_value(ty, item) {
+            Ok((value, mut item_type)) => {
+                values.push(value);
+                if child_type.is_null() {
+                    child_type = item_type;
+                } else {
+                    ffi::duckdb_destroy_logical_type(&mut item_type);
+                }
+            }
+            Err(err) => {
+                values.iter_mut().for_each(|v| ffi::duckdb_destroy_value(v));
+                if !child_type.is_null() {
+                    ffi::duckdb_destroy_logical_type(&mut child_type);
+                }
+                return Err(err);
+            }
+        }
+    }
+    if child_type.is_null() {
+        child_type = element_type(ty)?;
+    }
+    Ok((values, child_type))
+}
+
+/// Builds a `LIST` value from `items`, returning it together with its type.
+/// The element type is that of the first element, or `ty` for empty lists, and
+/// all elements are cast to it. Both results must be destroyed by the caller.
+unsafe fn list_value(ty: &Type, items: &[Value]) -> Result<(ffi::duckdb_value, ffi::duckdb_logical_type)> {
+    let (mut values, mut child_type) = element_values(ty, items)?;
+    let list = ffi::duckdb_create_list_value(child_type, values.as_mut_ptr(), values.len() as u64);
+    values.iter_mut().for_each(|v| ffi::duckdb_destroy_value(v));
+    let mut list_type = ffi::duckdb_create_list_type(child_type);
+    ffi::duckdb_destroy_logical_type(&mut child_type);
+    if list.is_null() {
+        ffi::duckdb_destroy_logical_type(&mut list_type);
+        return Err(list_bind_error(
+            "list elements could not be cast to a common type".to_owned(),
+        ));
+    }
+    Ok((list, list_type))
+}
+
+/// Builds an `ARRAY` value from `items`, like [`list_value`].
+unsafe fn array_value(ty: &Type, items: &[Value]) -> Result<(ffi::duckdb_value, ffi::duckdb_logical_type)> {
+    let (mut values, mut child_type) = element_values(ty, items)?;
+    let array = ffi::duckdb_create_array_value(child_type, values.as_mut_ptr(), values.len() as u64);
+    values.iter_mut().for_each(|v| ffi::duckdb_destroy_value(v));
+    let mut array_type = ffi::duckdb_create_array_type(child_type, values.len() as u64);
+    ffi::duckdb_destroy_logical_type(&mut child_type);
+    if array.is_null() {
+        ffi::duckdb_destroy_logical_type(&mut array_type);
+        return Err(list_bind_error(
+            "array elements could not be cast to a common type".to_owned(),
+        ));
     }
+    Ok((array, array_type))
+}
+
+/// Builds a `STRUCT` value from `fields`, typed by the fields' values.
+unsafe fn struct_value(fields: &OrderedMap<String, Value>) -> Result<(ffi::duckdb_value, ffi::duckdb_logical_type)> {
+    let names = fields
+        .keys()
+        .map(|name| CString::new(name.as_str()))
+        .collect::<result::Result<Vec<_>, _>>()?;
+    let mut names: Vec<*const c_char> = names.iter().map(|name| name.as_ptr()).collect();
+    let mut values = Vec::with_capacity(names.len());
+    let mut types = Vec::with_capacity(names.len());
+    for field in fields.values() {
+        match element_value(&field.data_type(), field) {
+            Ok((value, ty)) => {
+                values.push(value);
+                types.push(ty);
+            }
+            Err(err) => {
+                values.iter_mut().for_each(|v| ffi::duckdb_destroy_value(v));
+                types.iter_mut().for_each(|t| ffi::duckdb_destroy_logical_type(t));
+                return Err(err);
+            }
+        }
+    }
+    let mut struct_type = ffi::duckdb_create_struct_type(types.as_mut_ptr(), names.as_mut_ptr(), names.len() as u64);
+    types.iter_mut().for_each(|t| ffi::duckdb_destroy_logical_type(t));
+    let value = ffi::duckdb_create_struct_value(struct_type, values.as_mut_ptr());
+    values.iter_mut().for_each(|v| ffi::duckdb_destroy_value(v));
+    if value.is_null() {
+        ffi::duckdb_destroy_logical_type(&mut struct_type);
+        return Err(list_bind_error("struct could not be created".to_owned()));
+    }
+    Ok((value, struct_type))
+}
+
+/// Builds a single list element of type `ty`. DuckDB only has constructors for
+/// `BIGINT` and `VARCHAR` values, so everything else is created from one of
+/// those and cast to the returned type by [`list_value`].
+unsafe fn element_value(ty: &Type, value: &Value) -> Result<(ffi::duckdb_value, ffi::duckdb_logical_type)> {
+    let int = |i: i64, ty| (ffi::duckdb_create_int64(i), ffi::duckdb_create_logical_type(ty));
+    let varchar = |s: &str| ffi::duckdb_create_varchar_length(s.as_ptr() as *const c_char, s.len() as u64);
+    let text = |s: &str, ty| (varchar(s), ffi::duckdb_create_logical_type(ty));
+    Ok(match value {
+        Value::Boolean(b) => int(*b as i64, ffi::DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN),
+        Value::TinyInt(i) => int(*i as i64, ffi::DUCKDB_TYPE_DUCKDB_TYPE_TINYINT),
+        Value::SmallInt(i) => int(*i as i64, ffi::DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT),
+        Value::Int(i) => int(*i as i64, ffi::DUCKDB_TYPE_DUCKDB_TYPE_INTEGER),
+        Value::BigInt(i) => int(*i, ffi::DUCKDB_TYPE_DUCKDB_TYPE_BIGINT),
+        Value::UTinyInt(i) => int(*i as i64, ffi::DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT),
+        Value::USmallInt(i) => int(*i as i64, ffi::DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT),
+        Value::UInt(i) => int(*i as i64, ffi::DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER),
+        Value::UBigInt(i) => text(&i.to_string(), ffi::DUCKDB_TYPE_DUCKDB_TYPE_UBIGINT),
+        Value::HugeInt(i) => text(&i.to_string(), ffi::DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT),
+        Value::Float(f) => text(&f.to_string(), ffi::DUCKDB_TYPE_DUCKDB_TYPE_FLOAT),
+        Value::Double(f) => text(&f.to_string(), ffi::DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE),
+        Value::Text(s) => text(s, ffi::DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR),
+        Value::Blob(b) => {
+            let escaped: String = b.iter().map(|b| format!("\\x{b:02X}")).collect();
+            text(&escaped, ffi::DUCKDB_TYPE_DUCKDB_TYPE_BLOB)
+        }
+        Value::Decimal { value, width, scale } => (
+            varchar(&decimal_to_string(*value, *scale)),
+            ffi::duckdb_create_decimal_type(*width, *scale),
+        ),
+        Value::Enum(s) => text(s, ffi::DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR),
+        Value::List(items) => match ty {
+            Type::List(child) => list_value(child, items)?,
+            _ => list_value(&Type::Text, items)?,
+        },
+        Value::Array(items) => match ty {
+            Type::Array(child, _) => array_value(child, items)?,
+            _ => array_value(&Type::Text, items)?,
+        },
+        Value::Struct(fields) => struct_value(fields)?,
+        // the C API has no way to create a NULL value
+        Value::Null => {
+            return Err(list_bind_error(
+                "NULL values cannot be nested in list or struct parameters".to_owned(),
+            ))
+        }
+        _ => return Err(list_bind_error(format!("unsupported list element {value:?}"))),
+    })
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{params_from_iter, types::ToSql, Connection, Error, Result};
+    use crate::{named_params, params_from_iter, types::ToSql, Connection, Error, Result};
 
     #[test]
     fn test_execute() -> Result<()> {
@@ -577,6 +1188,53 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_execute_batch_params() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER PRIMARY KEY)")?;
+
+        let mut stmt = db.prepare("INSERT INTO foo(x) VALUES (?)")?;
+        assert_eq!(stmt.execute_batch_params([[1i32], [2], [3]])?, 3);
+        assert!(db.is_autocommit());
+
+        // The duplicate key fails the batch, and none of its rows are kept.
+        assert!(stmt.execute_batch_params([[4i32], [1], [5]]).is_err());
+        assert!(db.is_autocommit());
+        let count: i64 = db.query_row("SELECT count(*) FROM foo", [], |r| r.get(0))?;
+        assert_eq!(count, 3);
+
+        assert_eq!(stmt.execute_batch_params(Vec::<[i32; 1]>::new())?, 0);
+        drop(stmt);
+
+        // Inside a transaction, the rows are only kept if the caller commits.
+        let mut db = db;
+        let tx = db.transaction()?;
+        assert!(!tx.is_autocommit());
+        {
+            let mut stmt = tx.prepare("INSERT INTO foo(x) VALUES (?)")?;
+            assert_eq!(stmt.execute_batch_params([[4i32], [5]])?, 2);
+        }
+        let count: i64 = tx.query_row("SELECT count(*) FROM foo", [], |r| r.get(0))?;
+        assert_eq!(count, 5);
+        tx.rollback()?;
+        assert!(db.is_autocommit());
+        let count: i64 = db.query_row("SELECT count(*) FROM foo", [], |r| r.get(0))?;
+        assert_eq!(count, 3);
+
+        // Likewise inside a transaction started by a plain `BEGIN`.
+        db.execute_batch("BEGIN")?;
+        {
+            let mut stmt = db.prepare("INSERT INTO foo(x) VALUES (?)")?;
+            assert_eq!(stmt.execute_batch_params([[4i32], [5]])?, 2);
+        }
+        let count: i64 = db.query_row("SELECT count(*) FROM foo", [], |r| r.get(0))?;
+        assert_eq!(count, 5);
+        db.execute_batch("ROLLBACK")?;
+        let count: i64 = db.query_row("SELECT count(*) FROM foo", [], |r| r.get(0))?;
+        assert_eq!(count, 3);
+        Ok(())
+    }
+
     #[test]
     fn test_stmt_execute() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -612,6 +1270,24 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_iter() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let mut stmt = db
+            .prepare("SELECT * FROM (VALUES (1, 'a'), (2, 'b'), (NULL, 'c')) v(id, name) WHERE id IS NULL OR id > ?")?;
+
+        let ids: Vec<Option<i64>> = stmt.iter([0])?.collect::<Result<_>>()?;
+        assert_eq!(ids, [Some(1), Some(2), None]);
+
+        let ids: Vec<i64> = stmt.iter([1])?.filter_map(Result::ok).collect();
+        assert_eq!(ids, [2]);
+
+        let mut strings = stmt.iter::<String>([5])?;
+        assert!(matches!(strings.next(), Some(Err(Error::InvalidColumnType(..)))));
+        assert!(strings.next().is_none());
+        Ok(())
+    }
+
     #[test]
     fn test_query_and_then() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -657,6 +1333,70 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_named_parameters() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE test (id INTEGER, name TEXT)")?;
+
+        let mut stmt = db.prepare("INSERT INTO test (id, name) VALUES ($id, $name)")?;
+        assert_eq!(stmt.parameter_index("$name")?, stmt.parameter_index("NAME")?);
+        assert_eq!(stmt.parameter_index("$missing")?, None);
+        stmt.execute(named_params! { "$id": 5, "$name": "foo" })?;
+        stmt.execute(named_params! { "name": "bar", "id": 6 })?;
+
+        let err = stmt.execute(named_params! { "$id": 7, "$nmae": "baz" }).unwrap_err();
+        assert_eq!(err, Error::InvalidParameterName("$nmae".to_owned()));
+        // `$name` isn't reused from the last execution
+        let err = stmt.execute(named_params! { "$id": 7 }).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("not provided for the following prepared statement parameters: name"));
+
+        let rows = db
+            .prepare("SELECT id, name FROM test ORDER BY id")?
+            .query_map([], |r| Ok((r.get::<_, i32>(0)?, r.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(rows, [(5, "foo".to_owned()), (6, "bar".to_owned())]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_scalar() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE t (id INTEGER, name TEXT); INSERT INTO t VALUES (1, 'a'), (2, NULL)")?;
+
+        let mut stmt = db.prepare("SELECT count(*) FROM t WHERE id = ?")?;
+        assert_eq!(stmt.query_scalar::<i64, _>([1])?, 1);
+        assert_eq!(stmt.query_scalar::<i64, _>([3])?, 0);
+        let mut stmt = db.prepare("SELECT name FROM t WHERE id = ?")?;
+        assert_eq!(stmt.query_scalar::<String, _>([1])?, "a");
+        assert_eq!(stmt.query_scalar::<Option<String>, _>([2])?, None);
+        assert_eq!(stmt.query_scalar::<String, _>([3]), Err(Error::QueryReturnedNoRows));
+        // the statement is still usable as usual
+        assert_eq!(stmt.query_row([1], |r| r.get::<_, String>(0))?, "a");
+
+        assert_eq!(db.query_scalar::<f64, _>("SELECT 1.5::DOUBLE", [])?, 1.5);
+        assert!(db.query_scalar::<bool, _>("SELECT EXISTS (SELECT 1 FROM t)", [])?);
+        assert_eq!(db.query_scalar::<Vec<u8>, _>("SELECT 'abc'::BLOB", [])?, b"abc");
+        assert_eq!(db.query_scalar::<Option<i32>, _>("SELECT NULL::INTEGER", [])?, None);
+        assert!(matches!(
+            db.query_scalar::<String, _>("SELECT 42 AS answer", []),
+            Err(Error::InvalidColumnType(0, ref name, _)) if name == "answer"
+        ));
+
+        // other types are read through Arrow
+        assert_eq!(db.query_scalar::<Vec<i32>, _>("SELECT [1, 2, 3]", [])?, [1, 2, 3]);
+        assert_eq!(
+            db.query_scalar::<Option<Vec<i32>>, _>("SELECT NULL::INTEGER[]", [])?,
+            None
+        );
+        assert_eq!(
+            db.query_scalar::<i128, _>("SELECT 170141183460469231731687303715884105727::HUGEINT", [])?,
+            i128::MAX
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_insert_empty_text_is_none() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -937,4 +1677,280 @@ mod test {
         assert_eq!(expected, actual);
         Ok(())
     }
+
+    #[test]
+    fn test_fetch_streaming() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let mut stmt = db.prepare("SELECT i FROM range(10000) t(i) WHERE i >= ?")?;
+        stmt.set_fetch_streaming(true);
+
+        let mut sum = 0i64;
+        let mut count = 0;
+        let mut rows = stmt.query([5000])?;
+        while let Some(row) = rows.next()? {
+            sum += row.get::<_, i64>(0)?;
+            count += 1;
+        }
+        assert_eq!(count, 5000);
+        assert_eq!(sum, (5000..10000).sum::<i64>());
+        assert!(rows.next()?.is_none());
+
+        // The stream is forward-only, iterating it again is an error
+        let mut rows = stmt.raw_query();
+        assert!(matches!(rows.next(), Err(Error::StreamingResultConsumed)));
+
+        // Executing again yields a fresh stream
+        let batches = stmt.query_arrow([9990])?.collect::<Vec<_>>();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 10);
+
+        stmt.set_fetch_streaming(false);
+        let count: usize = stmt.query_map([0], |_| Ok(()))?.count();
+        assert_eq!(count, 10000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_streaming_execute() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER)")?;
+        let mut stmt = db.prepare("INSERT INTO foo SELECT * FROM range(3)")?;
+        stmt.set_fetch_streaming(true);
+        assert_eq!(stmt.execute([])?, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_arrow_streaming() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("SET threads = 1")?;
+        let mut stmt = db.prepare("SELECT range AS x FROM range(?)")?;
+        let mut total = 0;
+        let mut batches = 0;
+        for batch in stmt.query_arrow_streaming([10_000])? {
+            let batch = batch?;
+            assert_eq!(batch.schema().field(0).name(), "x");
+            total += batch.num_rows();
+            batches += 1;
+        }
+        assert_eq!((total, batches), (10_000, 5));
+        // The statement's own setting is kept, and it can run again
+        assert!(!stmt.stmt.is_fetch_streaming());
+        assert_eq!(stmt.query_arrow([3])?.map(|b| b.num_rows()).sum::<usize>(), 3);
+
+        // An error in a later batch ends the iteration
+        let mut stmt =
+            db.prepare("SELECT CASE WHEN range = 9000000 THEN error('boom') ELSE range END FROM range(10000000)")?;
+        let mut stream = stmt.query_arrow_streaming([])?;
+        assert!(stream.next().unwrap().is_ok());
+        let err = stream.find_map(|batch| batch.err()).unwrap();
+        assert!(err.to_string().contains("boom"), "{err}");
+        assert!(stream.next().is_none());
+        drop(stream);
+        assert!(!stmt.stmt.is_streaming());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_streaming_schema() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("SET TimeZone = 'UTC'; CREATE TYPE mood AS ENUM ('sad', 'happy');")?;
+        let sql = "SELECT 1::HUGEINT a, 1.5::DECIMAL(9, 2) b, 'happy'::mood c, [1, 2] d, {'x': 'y'} e, \
+                   MAP {'k': 1} f, [1, 2]::INTEGER[2] g, INTERVAL 1 DAY h, now() i, gen_random_uuid() j, \
+                   union_value(n := 1) k";
+        let mut stmt = db.prepare(sql)?;
+        let expected = stmt.query_arrow([])?.get_schema();
+        stmt.set_fetch_streaming(true);
+        let batches = stmt.query_arrow([])?.collect::<Vec<_>>();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].schema(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_statement_type() -> Result<()> {
+        use crate::StatementType;
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER)")?;
+        assert_eq!(db.prepare("SELECT * FROM foo")?.statement_type(), StatementType::Select);
+        assert_eq!(
+            db.prepare("INSERT INTO foo VALUES (1)")?.statement_type(),
+            StatementType::Insert
+        );
+
+        let types = db.extract_statement_types(
+            "SELECT 1; UPDATE foo SET x = 2; DROP TABLE foo; SET threads = 2; EXPLAIN SELECT 1;",
+        )?;
+        assert_eq!(
+            types,
+            vec![
+                StatementType::Select,
+                StatementType::Update,
+                StatementType::Drop,
+                StatementType::Set,
+                StatementType::Explain,
+            ]
+        );
+        assert!(db.extract_statement_types("")?.is_empty());
+        assert!(db.extract_statement_types("SELEC 1").is_err());
+        assert!(db.extract_statement_types("SELECT * FROM missing").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bind_list() -> Result<()> {
+        use crate::types::{List, Value};
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE foo(id INTEGER, name TEXT); INSERT INTO foo SELECT range, range::VARCHAR FROM range(10)",
+        )?;
+
+        let ids = vec![2i64, 3, 5, 7];
+        let count: i64 = db.query_row("SELECT count(*) FROM foo WHERE id = ANY(?)", [List(&ids)], |r| r.get(0))?;
+        assert_eq!(count, 4);
+
+        let names = ["1", "8", "nope"];
+        let count: i64 = db.query_row("SELECT count(*) FROM foo WHERE name = ANY(?)", [List(&names)], |r| {
+            r.get(0)
+        })?;
+        assert_eq!(count, 2);
+
+        let empty: &[i32] = &[];
+        let count: i64 = db.query_row("SELECT count(*) FROM foo WHERE id = ANY(?)", [List(empty)], |r| {
+            r.get(0)
+        })?;
+        assert_eq!(count, 0);
+
+        let list: Vec<f64> = db.query_row("SELECT ?", [List(&[1.5f64, -0.25])], |r| r.get(0))?;
+        assert_eq!(list, vec![1.5, -0.25]);
+        let list: Vec<u64> = db.query_row("SELECT ?", [List(&[u64::MAX])], |r| r.get(0))?;
+        assert_eq!(list, vec![u64::MAX]);
+        let list: Vec<bool> = db.query_row("SELECT ?", [List(&[true, false])], |r| r.get(0))?;
+        assert_eq!(list, vec![true, false]);
+        // a list of `u8` rather than a BLOB
+        let ty: String = db.query_row("SELECT typeof(?)", [List(&[1u8, 2])], |r| r.get(0))?;
+        assert_eq!(ty, "UTINYINT[]");
+
+        // the C API can't create NULL values, so they can't be list elements
+        let err = db
+            .query_row("SELECT ?", [Value::List(vec![Value::Int(1), Value::Null])], |r| {
+                r.get::<_, Vec<i32>>(0)
+            })
+            .unwrap_err();
+        assert!(matches!(err, Error::ToSqlConversionFailure(_)), "{err:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bind_borrowed_values() -> Result<()> {
+        use crate::types::{OrderedMap, Value};
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TYPE mood AS ENUM ('sad', 'happy');
+             CREATE TABLE t(s STRUCT(a INTEGER, b VARCHAR[]), l STRUCT(x INTEGER)[], a INTEGER[2], m mood)",
+        )?;
+        let s = Value::Struct(OrderedMap::from(vec![
+            ("a".to_owned(), Value::Int(1)),
+            ("b".to_owned(), Value::List(vec![Value::Text("x".to_owned())])),
+        ]));
+        let point = |x| Value::Struct(OrderedMap::from(vec![("x".to_owned(), Value::Int(x))]));
+        let params = vec![
+            s.clone(),
+            Value::List(vec![point(1), point(2)]),
+            Value::Array(vec![Value::Int(3), Value::Int(4)]),
+            Value::Enum("happy".to_owned()),
+        ];
+        // the same parameters can be bound again, as they are only borrowed
+        let mut stmt = db.prepare("INSERT INTO t VALUES (?, ?, ?, ?)")?;
+        stmt.execute(params_from_iter(&params))?;
+        stmt.execute(params_from_iter(params.iter()))?;
+
+        let rows = db
+            .prepare("SELECT s, l, a, m::VARCHAR FROM t")?
+            .query_map([], |r| {
+                Ok((r.get::<_, Value>(0)?, r.get::<_, Value>(1)?, r.get::<_, String>(3)?))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], (s, params[1].clone(), "happy".to_owned()));
+        let sum: i64 = db.query_row("SELECT sum(a[1] + a[2] + l[2].x) FROM t", [], |r| r.get(0))?;
+        assert_eq!(sum, 2 * (3 + 4 + 2));
+
+        let map = Value::Map(OrderedMap::from(vec![(Value::Int(1), Value::Int(2))]));
+        let err = db.query_row("SELECT ?", [&map], |r| r.get::<_, Value>(0)).unwrap_err();
+        assert!(matches!(err, Error::ToSqlConversionFailure(_)), "{err:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bind_owned_values() -> Result<()> {
+        use crate::types::{OrderedMap, ToSqlOutput, Value};
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TYPE mood AS ENUM ('sad', 'happy'); CREATE TABLE t(m mood)")?;
+        // values returned as `ToSqlOutput::Owned` by a `ToSql` impl
+        let list = ToSqlOutput::Owned(Value::List(vec![Value::Int(1), Value::Int(2)]));
+        let list: Vec<i32> = db.query_row("SELECT ?::INTEGER[]", [list], |r| r.get(0))?;
+        assert_eq!(list, [1, 2]);
+        db.execute(
+            "INSERT INTO t VALUES (?)",
+            [ToSqlOutput::Owned(Value::Enum("sad".to_owned()))],
+        )?;
+        let mood: String = db.query_row("SELECT m::VARCHAR FROM t", [], |r| r.get(0))?;
+        assert_eq!(mood, "sad");
+
+        let map = ToSqlOutput::Owned(Value::Map(OrderedMap::from(vec![(Value::Int(1), Value::Int(2))])));
+        let err = db.query_row("SELECT ?", [map], |r| r.get::<_, Value>(0)).unwrap_err();
+        assert!(matches!(err, Error::ToSqlConversionFailure(_)), "{err:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bind_nested_struct() -> Result<()> {
+        use crate::types::{OrderedMap, Value};
+
+        let db = Connection::open_in_memory()?;
+        let st = |fields: Vec<(&str, Value)>| {
+            Value::Struct(OrderedMap::from(
+                fields.into_iter().map(|(k, v)| (k.to_owned(), v)).collect::<Vec<_>>(),
+            ))
+        };
+        let point = st(vec![
+            ("x", Value::Int(1)),
+            (
+                "tags",
+                Value::List(vec![st(vec![("name", Value::Text("a".to_owned()))])]),
+            ),
+            ("empty", Value::List(vec![])),
+        ]);
+        let outer = st(vec![("p", point)]);
+        let s: String = db.query_row("SELECT ?::VARCHAR", [&outer], |r| r.get(0))?;
+        assert_eq!(s, "{'p': {'x': 1, 'tags': [{'name': a}], 'empty': []}}");
+
+        let ty = "STRUCT(p STRUCT(x BIGINT, tags STRUCT(name VARCHAR)[], empty INTEGER[]))";
+        let (x, name, empty): (i64, String, i64) = db.query_row(
+            &format!("SELECT s.p.x, s.p.tags[1].name, len(s.p.empty) FROM (SELECT ?::{ty} AS s)"),
+            [&outer],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )?;
+        assert_eq!((x, name.as_str(), empty), (1, "a", 0));
+
+        let x: i32 = db.query_row(
+            "SELECT (?::STRUCT(x INTEGER)).x",
+            [&st(vec![("x", Value::Int(2))])],
+            |r| r.get(0),
+        )?;
+        assert_eq!(x, 2);
+
+        let s: String = db.query_row("SELECT ?::VARCHAR", [&st(vec![])], |r| r.get(0))?;
+        assert_eq!(s, "{}");
+
+        let err = db
+            .query_row("SELECT ?", [&st(vec![("n", Value::Null)])], |r| r.get::<_, Value>(0))
+            .unwrap_err();
+        assert!(matches!(err, Error::ToSqlConversionFailure(_)), "{err:?}");
+        Ok(())
+    }
 }
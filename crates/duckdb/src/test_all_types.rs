@@ -1,5 +1,4 @@
 use pretty_assertions::assert_eq;
-use rust_decimal::Decimal;
 
 use crate::{
     types::{OrderedMap, TimeUnit, Type, Value, ValueRef},
@@ -20,9 +19,9 @@ fn test_large_arrow_types() -> crate::Result<()> {
 }
 
 fn test_with_database(database: &Connection) -> crate::Result<()> {
-    // uhugeint, time_tz, and dec38_10 aren't supported in the duckdb arrow layer
+    // uhugeint and time_tz aren't supported in the duckdb arrow layer
     // union is currently blocked by https://github.com/duckdb/duckdb/pull/11326
-    let excluded = ["uhugeint", "time_tz", "dec38_10", "union"];
+    let excluded = ["uhugeint", "time_tz", "union"];
 
     let mut binding = database.prepare(&format!(
         "SELECT * EXCLUDE ({}) FROM test_all_types()",
@@ -40,7 +39,7 @@ fn test_with_database(database: &Connection) -> crate::Result<()> {
         for column in row.stmt.column_names() {
             let value = row.get_ref_unwrap(row.stmt.column_index(&column)?);
             if idx != 2 {
-                assert_ne!(value.data_type(), Type::Null, "column {column} is null: {value:?}");
+                assert_ne!(value.try_data_type()?, Type::Null, "column {column} is null: {value:?}");
             }
             test_single(&mut idx, column, value);
         }
@@ -116,11 +115,6 @@ fn test_single(idx: &mut i32, column: String, value: ValueRef) {
             1 => assert_eq!(value, ValueRef::Double(1.7976931348623157e308)),
             _ => assert_eq!(value, ValueRef::Null),
         },
-        "decimal" => match idx {
-            0 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(0, 0))),
-            1 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(1, 0))),
-            _ => assert_eq!(value, ValueRef::Null),
-        },
         "date" => match idx {
             0 => assert_eq!(value, ValueRef::Date32(-2147483646)),
             1 => assert_eq!(value, ValueRef::Date32(2147483646)),
@@ -157,23 +151,78 @@ fn test_single(idx: &mut i32, column: String, value: ValueRef) {
             _ => assert_eq!(value, ValueRef::Null),
         },
         "dec_4_1" => match idx {
-            0 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(-9999, 1))),
-            1 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(9999, 1))),
+            0 => assert_eq!(
+                value,
+                ValueRef::Decimal {
+                    value: -9999,
+                    width: 4,
+                    scale: 1
+                }
+            ),
+            1 => assert_eq!(
+                value,
+                ValueRef::Decimal {
+                    value: 9999,
+                    width: 4,
+                    scale: 1
+                }
+            ),
             _ => assert_eq!(value, ValueRef::Null),
         },
         "dec_9_4" => match idx {
-            0 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(-999999999, 4))),
-            1 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(999999999, 4))),
+            0 => assert_eq!(
+                value,
+                ValueRef::Decimal {
+                    value: -999999999,
+                    width: 9,
+                    scale: 4
+                }
+            ),
+            1 => assert_eq!(
+                value,
+                ValueRef::Decimal {
+                    value: 999999999,
+                    width: 9,
+                    scale: 4
+                }
+            ),
             _ => assert_eq!(value, ValueRef::Null),
         },
         "dec_18_6" => match idx {
             0 => assert_eq!(
                 value,
-                ValueRef::Decimal(Decimal::from_i128_with_scale(-999999999999999999, 6))
+                ValueRef::Decimal {
+                    value: -999999999999999999,
+                    width: 18,
+                    scale: 6
+                }
             ),
             1 => assert_eq!(
                 value,
-                ValueRef::Decimal(Decimal::from_i128_with_scale(999999999999999999, 6))
+                ValueRef::Decimal {
+                    value: 999999999999999999,
+                    width: 18,
+                    scale: 6
+                }
+            ),
+            _ => assert_eq!(value, ValueRef::Null),
+        },
+        "dec38_10" => match idx {
+            0 => assert_eq!(
+                value,
+                ValueRef::Decimal {
+                    value: -99999999999999999999999999999999999999,
+                    width: 38,
+                    scale: 10
+                }
+            ),
+            1 => assert_eq!(
+                value,
+                ValueRef::Decimal {
+                    value: 99999999999999999999999999999999999999,
+                    width: 38,
+                    scale: 10
+                }
             ),
             _ => assert_eq!(value, ValueRef::Null),
         },
@@ -651,7 +700,7 @@ fn test_single(idx: &mut i32, column: String, value: ValueRef) {
         },
         "medium_enum" => match idx {
             0 => assert_eq!(value.to_owned(), Value::Enum("enum_0".to_string())),
-            1 => assert_eq!(value.to_owned(), Value::Enum("enum_1".to_string())),
+            1 => assert_eq!(value.to_owned(), Value::Enum("enum_299".to_string())),
             _ => assert_eq!(value, ValueRef::Null),
         },
         "large_enum" => match idx {
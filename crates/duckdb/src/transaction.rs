@@ -68,6 +68,10 @@ pub struct Transaction<'conn> {
 ///
 /// ## Note
 ///
+/// DuckDB doesn't support savepoints yet: starting one returns a parser error
+/// and leaves the enclosing transaction as it was. The API is kept so code
+/// written against rusqlite compiles, and works once DuckDB supports them.
+///
 /// Savepoints will roll back by default. Use `commit` method to explicitly
 /// commit the savepoint, or use `set_drop_behavior` to change what happens
 /// when the savepoint is dropped.
@@ -121,7 +125,9 @@ impl Transaction<'_> {
         //     TransactionBehavior::Exclusive => "BEGIN EXCLUSIVE",
         // };
         let query = "BEGIN Transaction";
-        conn.execute_batch(query).map(move |_| Transaction {
+        conn.execute_batch(query)?;
+        conn.db.borrow().set_in_transaction(true);
+        Ok(Transaction {
             conn,
             drop_behavior: DropBehavior::Rollback,
         })
@@ -132,6 +138,8 @@ impl Transaction<'_> {
     ///
     /// ## Note
     ///
+    /// DuckDB doesn't support savepoints yet, see [`Savepoint`].
+    ///
     /// Just like outer level transactions, savepoint transactions rollback by
     /// default.
     ///
@@ -187,8 +195,10 @@ impl Transaction<'_> {
 
     #[inline]
     fn commit_(&mut self) -> Result<()> {
-        self.conn.execute_batch("COMMIT")?;
-        Ok(())
+        // DuckDB ends the transaction even if the commit fails.
+        let r = self.conn.execute_batch("COMMIT");
+        self.conn.db.borrow().set_in_transaction(false);
+        r
     }
 
     /// A convenience method which consumes and rolls back a transaction.
@@ -199,8 +209,9 @@ impl Transaction<'_> {
 
     #[inline]
     fn rollback_(&mut self) -> Result<()> {
-        self.conn.execute_batch("ROLLBACK")?;
-        Ok(())
+        let r = self.conn.execute_batch("ROLLBACK");
+        self.conn.db.borrow().set_in_transaction(false);
+        r
     }
 
     /// Consumes the transaction, committing or rolling back according to the
@@ -669,6 +680,38 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_savepoint_unsupported() -> Result<()> {
+        let mut db = checked_no_autocommit_memory_handle()?;
+        let mut tx = db.transaction()?;
+        insert(1, &tx)?;
+        let err = tx.savepoint().err().unwrap();
+        assert!(
+            err.to_string().contains("syntax error at or near \"SAVEPOINT\""),
+            "{err}"
+        );
+        // The transaction is still usable, and rolls back when dropped
+        insert(2, &tx)?;
+        assert_current_sum(3, &tx)?;
+        drop(tx);
+        assert_eq!(db.query_row("SELECT count(*) FROM foo", [], |r| r.get::<_, i64>(0))?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_early_return_rolls_back() -> Result<()> {
+        fn fails(db: &mut Connection) -> Result<()> {
+            let tx = db.transaction()?;
+            insert(1, &tx)?;
+            tx.execute_batch("INSERT INTO foo VALUES ('not a number')")?;
+            tx.commit()
+        }
+        let mut db = checked_no_autocommit_memory_handle()?;
+        assert!(fails(&mut db).is_err());
+        assert_eq!(db.query_row("SELECT count(*) FROM foo", [], |r| r.get::<_, i64>(0))?, 0);
+        Ok(())
+    }
+
     #[test]
     fn test_rc() -> Result<()> {
         use std::rc::Rc;
@@ -0,0 +1,529 @@
+//! Conversions between [`Value`] and Arrow arrays.
+
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayData, ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, FixedSizeBinaryArray,
+        FixedSizeListArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+        IntervalMonthDayNanoArray, LargeBinaryArray, LargeListArray, LargeStringArray, ListArray, MapArray, NullArray,
+        StringArray, StructArray, Time64MicrosecondArray, Time64NanosecondArray, TimestampMicrosecondArray,
+        TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray, UInt16Array, UInt32Array,
+        UInt64Array, UInt8Array,
+    },
+    buffer::{NullBuffer, OffsetBuffer},
+    compute::cast,
+    datatypes::{DataType, IntervalMonthDayNano, IntervalUnit, TimeUnit as ArrowTimeUnit},
+};
+
+use super::{OrderedMap, TimeUnit, Value};
+use crate::{Error, Result, Row};
+
+impl Value {
+    /// Read the value at `row` of an Arrow array, including nested types such
+    /// as lists, structs and maps.
+    ///
+    /// # Failure
+    ///
+    /// Will return [`Error::UnsupportedArrowType`] if the array's type, or that
+    /// of a nested field, has no `Value` equivalent, e.g. `Duration`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds.
+    pub fn from_arrow(array: &ArrayRef, row: usize) -> Result<Value> {
+        assert!(
+            row < array.len(),
+            "row {row} out of bounds for an array of length {}",
+            array.len()
+        );
+        if array.data_type() == &DataType::Null {
+            return Ok(Value::Null);
+        }
+        if !readable(array.data_type()) {
+            return Err(Error::UnsupportedArrowType(array.data_type().clone()));
+        }
+        Ok(Row::value_ref_internal(row, 0, array).to_owned())
+    }
+
+    /// Convert this value into a single-element Arrow array of type
+    /// `data_type`, see [`Value::arrow_array`].
+    pub fn to_arrow(&self, data_type: &DataType) -> Result<ArrayRef> {
+        Value::arrow_array(std::slice::from_ref(self), data_type)
+    }
+
+    /// Convert `values` into an Arrow array of type `data_type`, e.g. to build a
+    /// column of a record batch. [`Value::Null`] is accepted for any type.
+    ///
+    /// # Failure
+    ///
+    /// Will return [`Error::UnsupportedArrowType`] if `data_type` has no `Value`
+    /// equivalent, or [`Error::ToSqlConversionFailure`] if a value doesn't fit
+    /// it, e.g. a [`Value::Text`] for an `Int32` array or `300` for a `UInt8`
+    /// array.
+    pub fn arrow_array(values: &[Value], data_type: &DataType) -> Result<ArrayRef> {
+        let array: ArrayRef = match data_type {
+            DataType::Null => {
+                if let Some(value) = values.iter().find(|v| **v != Value::Null) {
+                    return Err(mismatch(value, data_type));
+                }
+                Arc::new(NullArray::new(values.len()))
+            }
+            DataType::Boolean => Arc::new(BooleanArray::from(collect(values, data_type, |v| match v {
+                Value::Boolean(b) => Some(*b),
+                _ => None,
+            })?)),
+            DataType::Int8 => Arc::new(Int8Array::from(collect(values, data_type, integer)?)),
+            DataType::Int16 => Arc::new(Int16Array::from(collect(values, data_type, integer)?)),
+            DataType::Int32 => Arc::new(Int32Array::from(collect(values, data_type, integer)?)),
+            DataType::Int64 => Arc::new(Int64Array::from(collect(values, data_type, integer)?)),
+            DataType::UInt8 => Arc::new(UInt8Array::from(collect(values, data_type, integer)?)),
+            DataType::UInt16 => Arc::new(UInt16Array::from(collect(values, data_type, integer)?)),
+            DataType::UInt32 => Arc::new(UInt32Array::from(collect(values, data_type, integer)?)),
+            DataType::UInt64 => Arc::new(UInt64Array::from(collect(values, data_type, integer)?)),
+            DataType::Float32 => Arc::new(Float32Array::from(collect(values, data_type, |v| {
+                float(v).map(|f| f as f32)
+            })?)),
+            DataType::Float64 => Arc::new(Float64Array::from(collect(values, data_type, float)?)),
+            DataType::Decimal128(precision, scale) => Arc::new(
+                Decimal128Array::from(collect(values, data_type, |v| decimal(v, *scale))?)
+                    .with_precision_and_scale(*precision, *scale)
+                    .map_err(|err| Error::ToSqlConversionFailure(err.into()))?,
+            ),
+            DataType::Timestamp(unit, tz) => {
+                let values = collect(values, data_type, |v| match v {
+                    Value::Timestamp(u, t) => convert_unit(*t, nanos_per(*u), arrow_nanos_per(unit)),
+                    _ => None,
+                })?;
+                match unit {
+                    ArrowTimeUnit::Second => Arc::new(TimestampSecondArray::from(values).with_timezone_opt(tz.clone())),
+                    ArrowTimeUnit::Millisecond => {
+                        Arc::new(TimestampMillisecondArray::from(values).with_timezone_opt(tz.clone()))
+                    }
+                    ArrowTimeUnit::Microsecond => {
+                        Arc::new(TimestampMicrosecondArray::from(values).with_timezone_opt(tz.clone()))
+                    }
+                    ArrowTimeUnit::Nanosecond => {
+                        Arc::new(TimestampNanosecondArray::from(values).with_timezone_opt(tz.clone()))
+                    }
+                }
+            }
+            DataType::Date32 => Arc::new(Date32Array::from(collect(values, data_type, |v| match v {
+                Value::Date32(d) => Some(*d),
+                _ => None,
+            })?)),
+            DataType::Time64(unit @ (ArrowTimeUnit::Microsecond | ArrowTimeUnit::Nanosecond)) => {
+                let values = collect(values, data_type, |v| match v {
+                    Value::Time64(u, t) => convert_unit(*t, nanos_per(*u), arrow_nanos_per(unit)),
+                    _ => None,
+                })?;
+                match unit {
+                    ArrowTimeUnit::Microsecond => Arc::new(Time64MicrosecondArray::from(values)),
+                    _ => Arc::new(Time64NanosecondArray::from(values)),
+                }
+            }
+            DataType::Interval(IntervalUnit::MonthDayNano) => Arc::new(IntervalMonthDayNanoArray::from(collect(
+                values,
+                data_type,
+                |v| match v {
+                    Value::Interval { months, days, nanos } => Some(IntervalMonthDayNano::new(*months, *days, *nanos)),
+                    _ => None,
+                },
+            )?)),
+            DataType::Utf8 => Arc::new(StringArray::from(collect(values, data_type, text)?)),
+            DataType::LargeUtf8 => Arc::new(LargeStringArray::from(collect(values, data_type, text)?)),
+            DataType::Binary => Arc::new(BinaryArray::from(collect(values, data_type, blob)?)),
+            DataType::LargeBinary => Arc::new(LargeBinaryArray::from(collect(values, data_type, blob)?)),
+            DataType::FixedSizeBinary(size) => Arc::new(
+                FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                    collect(values, data_type, |v| blob(v).filter(|b| b.len() == *size as usize))?.into_iter(),
+                    *size,
+                )
+                .map_err(|err| Error::ToSqlConversionFailure(err.into()))?,
+            ),
+            DataType::Dictionary(_, value_type) if matches!(**value_type, DataType::Utf8) => {
+                let strings = StringArray::from(collect(values, data_type, |v| match v {
+                    Value::Enum(s) | Value::Text(s) => Some(s.as_str()),
+                    _ => None,
+                })?);
+                cast(&strings, data_type).map_err(|err| Error::ToSqlConversionFailure(err.into()))?
+            }
+            DataType::List(field) => {
+                let (offsets, children, nulls) = flatten(values, data_type, |v| match v {
+                    Value::List(items) => Some(items),
+                    _ => None,
+                })?;
+                let children = Value::arrow_array(&children, field.data_type())?;
+                Arc::new(
+                    ListArray::try_new(field.clone(), OffsetBuffer::from_lengths(offsets), children, nulls)
+                        .map_err(|err| Error::ToSqlConversionFailure(err.into()))?,
+                )
+            }
+            DataType::LargeList(field) => {
+                let (offsets, children, nulls) = flatten(values, data_type, |v| match v {
+                    Value::List(items) => Some(items),
+                    _ => None,
+                })?;
+                let children = Value::arrow_array(&children, field.data_type())?;
+                Arc::new(
+                    LargeListArray::try_new(field.clone(), OffsetBuffer::from_lengths(offsets), children, nulls)
+                        .map_err(|err| Error::ToSqlConversionFailure(err.into()))?,
+                )
+            }
+            DataType::FixedSizeList(field, size) => {
+                let padding = vec![Value::Null; *size as usize];
+                let (_, children, nulls) = flatten(values, data_type, |v| match v {
+                    Value::Array(items) if items.len() == *size as usize => Some(items),
+                    Value::Null => Some(&padding),
+                    _ => None,
+                })?;
+                let children = Value::arrow_array(&children, field.data_type())?;
+                Arc::new(
+                    FixedSizeListArray::try_new(field.clone(), *size, children, nulls)
+                        .map_err(|err| Error::ToSqlConversionFailure(err.into()))?,
+                )
+            }
+            DataType::Struct(fields) => {
+                let mut entries = Vec::with_capacity(values.len());
+                for value in values {
+                    match value {
+                        Value::Struct(map) => entries.push(Some(map)),
+                        Value::Null => entries.push(None),
+                        _ => return Err(mismatch(value, data_type)),
+                    }
+                }
+                let columns = fields
+                    .iter()
+                    .map(|field| {
+                        let column: Vec<Value> = entries
+                            .iter()
+                            .map(|entry| {
+                                entry
+                                    .and_then(|map| map.get(field.name()))
+                                    .cloned()
+                                    .unwrap_or(Value::Null)
+                            })
+                            .collect();
+                        Value::arrow_array(&column, field.data_type())
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let nulls = NullBuffer::from(entries.iter().map(Option::is_some).collect::<Vec<_>>());
+                Arc::new(
+                    StructArray::try_new(fields.clone(), columns, Some(nulls))
+                        .map_err(|err| Error::ToSqlConversionFailure(err.into()))?,
+                )
+            }
+            DataType::Map(field, _) => {
+                let DataType::Struct(entry_fields) = field.data_type() else {
+                    return Err(Error::UnsupportedArrowType(data_type.clone()));
+                };
+                let mut lengths = Vec::with_capacity(values.len());
+                let mut valid = Vec::with_capacity(values.len());
+                let mut entries = Vec::new();
+                for value in values {
+                    match value {
+                        Value::Map(map) => {
+                            lengths.push(map.iter().count());
+                            valid.push(true);
+                            entries.extend(map.iter().map(|(k, v)| {
+                                Value::Struct(OrderedMap::from(vec![
+                                    (entry_fields[0].name().clone(), k.clone()),
+                                    (entry_fields[1].name().clone(), v.clone()),
+                                ]))
+                            }));
+                        }
+                        Value::Null => {
+                            lengths.push(0);
+                            valid.push(false);
+                        }
+                        _ => return Err(mismatch(value, data_type)),
+                    }
+                }
+                let entries = Value::arrow_array(&entries, field.data_type())?;
+                // `MapArray::try_new` rejects nullable entry fields, which DuckDB produces.
+                let data = ArrayData::builder(data_type.clone())
+                    .len(values.len())
+                    .add_buffer(OffsetBuffer::<i32>::from_lengths(lengths).into_inner().into_inner())
+                    .add_child_data(entries.to_data())
+                    .nulls(Some(NullBuffer::from(valid)))
+                    .build()
+                    .map_err(|err| Error::ToSqlConversionFailure(err.into()))?;
+                Arc::new(MapArray::from(data))
+            }
+            _ => return Err(Error::UnsupportedArrowType(data_type.clone())),
+        };
+        Ok(array)
+    }
+}
+
+/// Whether [`Row::value_ref_internal`] can read arrays of this type.
+fn readable(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Boolean
+        | DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::Float16
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Decimal128(..)
+        | DataType::Timestamp(..)
+        | DataType::Date32
+        | DataType::Time64(ArrowTimeUnit::Microsecond)
+        | DataType::Interval(IntervalUnit::MonthDayNano)
+        | DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Binary
+        | DataType::LargeBinary
+        | DataType::FixedSizeBinary(_) => true,
+        DataType::List(field)
+        | DataType::LargeList(field)
+        | DataType::FixedSizeList(field, _)
+        | DataType::Map(field, _) => readable(field.data_type()),
+        DataType::Struct(fields) => fields.iter().all(|f| readable(f.data_type())),
+        DataType::Union(fields, _) => fields.iter().all(|(_, f)| readable(f.data_type())),
+        DataType::Dictionary(key, value) => {
+            matches!(**key, DataType::UInt8 | DataType::UInt16 | DataType::UInt32) && readable(value)
+        }
+        _ => false,
+    }
+}
+
+fn mismatch(value: &Value, data_type: &DataType) -> Error {
+    Error::ToSqlConversionFailure(format!("cannot convert {value:?} to an Arrow {data_type} value").into())
+}
+
+/// Convert each non-null value with `f`, failing for values it rejects.
+fn collect<'a, T>(
+    values: &'a [Value],
+    data_type: &DataType,
+    f: impl Fn(&'a Value) -> Option<T>,
+) -> Result<Vec<Option<T>>> {
+    values
+        .iter()
+        .map(|value| match value {
+            Value::Null => Ok(None),
+            value => f(value).map(Some).ok_or_else(|| mismatch(value, data_type)),
+        })
+        .collect()
+}
+
+/// Concatenate the items of list-like values, returning the length of each
+/// list, all items, and which lists are non-null.
+fn flatten<'a>(
+    values: &'a [Value],
+    data_type: &DataType,
+    items: impl Fn(&'a Value) -> Option<&'a Vec<Value>>,
+) -> Result<(Vec<usize>, Vec<Value>, Option<NullBuffer>)> {
+    let mut lengths = Vec::with_capacity(values.len());
+    let mut valid = Vec::with_capacity(values.len());
+    let mut children = Vec::new();
+    for value in values {
+        match (items(value), value) {
+            (Some(list), _) => {
+                lengths.push(list.len());
+                children.extend(list.iter().cloned());
+            }
+            (None, Value::Null) => lengths.push(0),
+            (None, _) => return Err(mismatch(value, data_type)),
+        }
+        valid.push(*value != Value::Null);
+    }
+    Ok((lengths, children, Some(NullBuffer::from(valid))))
+}
+
+fn integer<T: TryFrom<i128>>(value: &Value) -> Option<T> {
+    let i = match *value {
+        Value::TinyInt(i) => i.into(),
+        Value::SmallInt(i) => i.into(),
+        Value::Int(i) => i.into(),
+        Value::BigInt(i) => i.into(),
+        Value::HugeInt(i) => i,
+        Value::UTinyInt(i) => i.into(),
+        Value::USmallInt(i) => i.into(),
+        Value::UInt(i) => i.into(),
+        Value::UBigInt(i) => i.into(),
+        _ => return None,
+    };
+    i.try_into().ok()
+}
+
+fn float(value: &Value) -> Option<f64> {
+    match *value {
+        Value::Float(f) => Some(f.into()),
+        Value::Double(f) => Some(f),
+        _ => integer::<i64>(value).map(|i| i as f64),
+    }
+}
+
+/// The unscaled representation of `value` with `scale` digits after the point.
+fn decimal(value: &Value, scale: i8) -> Option<i128> {
+    let (unscaled, from) = match *value {
+        Value::Decimal { value, scale, .. } => (value, scale as i8),
+        _ => (integer(value)?, 0),
+    };
+    if scale >= from {
+        unscaled.checked_mul(10i128.checked_pow((scale - from) as u32)?)
+    } else {
+        let divisor = 10i128.checked_pow((from - scale) as u32)?;
+        (unscaled % divisor == 0).then(|| unscaled / divisor)
+    }
+}
+
+fn text(value: &Value) -> Option<&str> {
+    match value {
+        Value::Text(s) => Some(s),
+        _ => None,
+    }
+}
+
+fn blob(value: &Value) -> Option<&[u8]> {
+    match value {
+        Value::Blob(b) => Some(b),
+        _ => None,
+    }
+}
+
+fn nanos_per(unit: TimeUnit) -> i64 {
+    match unit {
+        TimeUnit::Second => 1_000_000_000,
+        TimeUnit::Millisecond => 1_000_000,
+        TimeUnit::Microsecond => 1_000,
+        TimeUnit::Nanosecond => 1,
+    }
+}
+
+fn arrow_nanos_per(unit: &ArrowTimeUnit) -> i64 {
+    match unit {
+        ArrowTimeUnit::Second => 1_000_000_000,
+        ArrowTimeUnit::Millisecond => 1_000_000,
+        ArrowTimeUnit::Microsecond => 1_000,
+        ArrowTimeUnit::Nanosecond => 1,
+    }
+}
+
+/// Convert `value` between units, rounding towards negative infinity.
+fn convert_unit(value: i64, from: i64, to: i64) -> Option<i64> {
+    if from >= to {
+        value.checked_mul(from / to)
+    } else {
+        Some(value.div_euclid(to / from))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{Array, ArrayRef, DurationSecondArray},
+        datatypes::{DataType, Field, Fields, TimeUnit as ArrowTimeUnit},
+    };
+
+    use crate::{
+        types::{OrderedMap, TimeUnit, Type, Value},
+        Connection, Error, Result,
+    };
+
+    #[test]
+    fn test_arrow_round_trip() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let mut stmt = db.prepare(
+            "SELECT 42::TINYINT, 'x', [1, NULL, 3], {'a': 1.5, 'b': ['z']}, MAP {'k': 2}, 12.34::DECIMAL(6, 2), \
+             TIMESTAMP '1969-12-31 23:59:59.5', NULL::INTEGER",
+        )?;
+        let batch = stmt.query_arrow([])?.next().unwrap();
+
+        let expected = [
+            Value::TinyInt(42),
+            Value::Text("x".to_owned()),
+            Value::List(vec![Value::Int(1), Value::Null, Value::Int(3)]),
+            Value::Struct(OrderedMap::from(vec![
+                (
+                    "a".to_owned(),
+                    Value::Decimal {
+                        value: 15,
+                        width: 2,
+                        scale: 1,
+                    },
+                ),
+                ("b".to_owned(), Value::List(vec![Value::Text("z".to_owned())])),
+            ])),
+            Value::Map(OrderedMap::from(vec![(Value::Text("k".to_owned()), Value::Int(2))])),
+            Value::Decimal {
+                value: 1234,
+                width: 6,
+                scale: 2,
+            },
+            Value::Timestamp(TimeUnit::Microsecond, -500_000),
+            Value::Null,
+        ];
+        for (column, expected) in batch.columns().iter().zip(expected) {
+            let value = Value::from_arrow(column, 0)?;
+            assert_eq!(value, expected);
+
+            let array = value.to_arrow(column.data_type())?;
+            assert_eq!(array.data_type(), column.data_type());
+            assert_eq!(array.len(), 1);
+            assert_eq!(Value::from_arrow(&array, 0)?, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_arrow_array() -> Result<()> {
+        let data_type = DataType::List(Arc::new(Field::new("item", DataType::Int64, true)));
+        let values = [Value::List(vec![Value::Int(1)]), Value::Null, Value::List(vec![])];
+        let array = Value::arrow_array(&values, &data_type)?;
+        assert_eq!(array.len(), 3);
+        assert!(array.is_null(1));
+        assert_eq!(Value::from_arrow(&array, 0)?, Value::List(vec![Value::BigInt(1)]));
+        assert_eq!(Value::from_arrow(&array, 2)?, Value::List(vec![]));
+
+        let fields = Fields::from(vec![Field::new("a", DataType::Int32, true)]);
+        let value = Value::Struct(OrderedMap::from(vec![("a".to_owned(), Value::Int(1))]));
+        assert_eq!(
+            Value::from_arrow(&value.to_arrow(&DataType::Struct(fields))?, 0)?,
+            value
+        );
+
+        let micros = Value::Timestamp(TimeUnit::Microsecond, 1_500_000);
+        let seconds = micros.to_arrow(&DataType::Timestamp(ArrowTimeUnit::Second, None))?;
+        assert_eq!(Value::from_arrow(&seconds, 0)?, Value::Timestamp(TimeUnit::Second, 1));
+
+        let hash = Value::Blob(vec![1; 32]);
+        let array = hash.to_arrow(&DataType::FixedSizeBinary(32))?;
+        assert_eq!(Type::try_from_arrow(array.data_type())?, Type::Blob);
+        assert_eq!(Value::from_arrow(&array, 0)?, hash);
+        assert!(Value::Blob(vec![1; 3])
+            .to_arrow(&DataType::FixedSizeBinary(32))
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_arrow_unsupported() {
+        let duration: ArrayRef = Arc::new(DurationSecondArray::from(vec![1]));
+        assert!(matches!(
+            Value::from_arrow(&duration, 0),
+            Err(Error::UnsupportedArrowType(DataType::Duration(_)))
+        ));
+        assert!(matches!(
+            Value::Int(1).to_arrow(duration.data_type()),
+            Err(Error::UnsupportedArrowType(_))
+        ));
+        assert!(matches!(
+            Value::Int(300).to_arrow(&DataType::UInt8),
+            Err(Error::ToSqlConversionFailure(_))
+        ));
+        assert!(matches!(
+            Value::Text("a".to_owned()).to_arrow(&DataType::Int32),
+            Err(Error::ToSqlConversionFailure(_))
+        ));
+    }
+}
@@ -1,21 +1,22 @@
 //! Convert most of the [Time Strings](http://sqlite.org/lang_datefunc.html) to chrono types.
 
-use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
 use num_integer::Integer;
 
 use crate::{
     types::{FromSql, FromSqlError, FromSqlResult, TimeUnit, ToSql, ToSqlOutput, ValueRef},
-    Result,
+    Error, Result,
 };
 
 use super::Value;
 
-/// ISO 8601 calendar date without timezone => "YYYY-MM-DD"
+/// Calendar date without timezone => DATE, as days since the Unix epoch.
 impl ToSql for NaiveDate {
     #[inline]
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
-        let date_str = self.format("%F").to_string();
-        Ok(ToSqlOutput::from(date_str))
+        let days = self.signed_duration_since(NaiveDate::default()).num_days();
+        let days = i32::try_from(days).map_err(|err| Error::ToSqlConversionFailure(err.into()))?;
+        Ok(ToSqlOutput::Owned(Value::Date32(days)))
     }
 }
 
@@ -27,12 +28,12 @@ impl FromSql for NaiveDate {
     }
 }
 
-/// ISO 8601 time without timezone => "HH:MM:SS.SSS"
+/// Time without timezone => TIME, as microseconds since midnight.
 impl ToSql for NaiveTime {
     #[inline]
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
-        let date_str = self.format("%T%.f").to_string();
-        Ok(ToSqlOutput::from(date_str))
+        let micros = i64::from(self.num_seconds_from_midnight()) * 1_000_000 + i64::from(self.nanosecond() / 1_000);
+        Ok(ToSqlOutput::Owned(Value::Time64(TimeUnit::Microsecond, micros)))
     }
 }
 
@@ -43,13 +44,15 @@ impl FromSql for NaiveTime {
     }
 }
 
-/// ISO 8601 combined date and time without timezone =>
-/// "YYYY-MM-DD HH:MM:SS.SSS"
+/// Combined date and time without timezone => TIMESTAMP, as microseconds since
+/// the Unix epoch. Sub-microsecond precision is truncated.
 impl ToSql for NaiveDateTime {
     #[inline]
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
-        let date_str = self.format("%F %T%.f").to_string();
-        Ok(ToSqlOutput::from(date_str))
+        Ok(ToSqlOutput::Owned(Value::Timestamp(
+            TimeUnit::Microsecond,
+            self.and_utc().timestamp_micros(),
+        )))
     }
 }
 
@@ -60,13 +63,22 @@ impl FromSql for NaiveDateTime {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         match value {
             ValueRef::Timestamp(tu, t) => {
+                // floor, so that timestamps before 1970 keep a positive sub-second part
                 let (secs, nsecs) = match tu {
                     TimeUnit::Second => (t, 0),
-                    TimeUnit::Millisecond => (t / 1000, (t % 1000) * 1_000_000),
-                    TimeUnit::Microsecond => (t / 1_000_000, (t % 1_000_000) * 1000),
-                    TimeUnit::Nanosecond => (t / 1_000_000_000, t % 1_000_000_000),
+                    TimeUnit::Millisecond => {
+                        let (secs, millis) = t.div_mod_floor(&1000);
+                        (secs, millis * 1_000_000)
+                    }
+                    TimeUnit::Microsecond => {
+                        let (secs, micros) = t.div_mod_floor(&1_000_000);
+                        (secs, micros * 1000)
+                    }
+                    TimeUnit::Nanosecond => t.div_mod_floor(&1_000_000_000),
                 };
-                Ok(DateTime::from_timestamp(secs, nsecs as u32).unwrap().naive_utc())
+                DateTime::from_timestamp(secs, nsecs as u32)
+                    .map(|dt| dt.naive_utc())
+                    .ok_or(FromSqlError::OutOfRange(t.into()))
             }
             ValueRef::Date32(d) => Ok(DateTime::from_timestamp(24 * 3600 * (d as i64), 0).unwrap().naive_utc()),
             ValueRef::Time64(TimeUnit::Microsecond, d) => Ok(DateTime::from_timestamp(
@@ -114,13 +126,28 @@ impl<Tz: TimeZone> ToSql for DateTime<Tz> {
 }
 
 /// RFC3339 ("YYYY-MM-DD HH:MM:SS.SSS[+-]HH:MM") into `DateTime<Utc>`.
+///
+/// `TIMESTAMPTZ` values are UTC instants and are read as such. Text with a
+/// UTC offset, as produced by casting a `TIMESTAMPTZ` to `VARCHAR`, is
+/// shifted to UTC; text without one is taken to be UTC already.
 impl FromSql for DateTime<Utc> {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        if let ValueRef::Text(s) = value {
+            if let Ok(dt) = DateTime::parse_from_str(std::str::from_utf8(s).unwrap_or_default(), "%F %T%.f%#z") {
+                return Ok(dt.with_timezone(&Utc));
+            }
+        }
         NaiveDateTime::column_result(value).map(|dt| Utc.from_utc_datetime(&dt))
     }
 }
 
-/// RFC3339 ("YYYY-MM-DD HH:MM:SS.SSS[+-]HH:MM") into `DateTime<Local>`.
+/// RFC3339 ("YYYY-MM-DD HH:MM:SS.SSS[+-]HH:MM") into `DateTime<Local>`, by
+/// reading a `DateTime<Utc>` and converting it to the system time zone.
+///
+/// Every instant has exactly one local time, so this never has to pick
+/// between the ambiguous or nonexistent local times around a DST transition.
+/// Two instants an hour apart around a fall-back transition read as the same
+/// wall-clock time with different offsets.
 impl FromSql for DateTime<Local> {
     #[inline]
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
@@ -129,13 +156,21 @@ impl FromSql for DateTime<Local> {
     }
 }
 
+/// INTERVAL without a month component into `Duration`.
+///
+/// Months have no fixed length, so intervals with a non-zero month component
+/// fail to convert rather than being approximated.
 impl FromSql for Duration {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         match value {
             ValueRef::Interval { months, days, nanos } => {
-                let days = days + (months * 30);
+                if months != 0 {
+                    return Err(FromSqlError::Other(
+                        format!("Invalid duration: interval has a month component ({months} months)").into(),
+                    ));
+                }
                 let (additional_seconds, nanos) = nanos.div_mod_floor(&NANOS_PER_SECOND);
-                let seconds = additional_seconds + (i64::from(days) * 24 * 3600);
+                let seconds = additional_seconds + (i64::from(days) * SECONDS_PER_DAY);
 
                 match nanos.try_into() {
                     Ok(nanos) => {
@@ -153,18 +188,17 @@ impl FromSql for Duration {
     }
 }
 
-const DAYS_PER_MONTH: i64 = 30;
 const SECONDS_PER_DAY: i64 = 24 * 3600;
 const NANOS_PER_SECOND: i64 = 1_000_000_000;
 const NANOS_PER_DAY: i64 = SECONDS_PER_DAY * NANOS_PER_SECOND;
 
+/// `Duration` into an INTERVAL of days and nanoseconds, leaving months at zero.
 impl ToSql for Duration {
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
         let nanos = self.num_nanoseconds().unwrap();
         let (days, nanos) = nanos.div_mod_floor(&NANOS_PER_DAY);
-        let (months, days) = days.div_mod_floor(&DAYS_PER_MONTH);
         Ok(ToSqlOutput::Owned(Value::Interval {
-            months: months.try_into().unwrap(),
+            months: 0,
             days: days.try_into().unwrap(),
             nanos,
         }))
@@ -174,6 +208,7 @@ impl ToSql for Duration {
 #[cfg(test)]
 mod test {
     use crate::{
+        params,
         types::{FromSql, ToSql, ToSqlOutput, ValueRef},
         Connection, Result,
     };
@@ -234,6 +269,35 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_naive_round_trip_before_epoch() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let d: NaiveDate = db.query_row("SELECT DATE '2020-01-01'", [], |r| r.get(0))?;
+        assert_eq!(d, NaiveDate::from_ymd_opt(2020, 1, 1).unwrap());
+
+        let date = NaiveDate::from_ymd_opt(1901, 12, 13).unwrap();
+        let time = NaiveTime::from_hms_micro_opt(20, 45, 52, 123_456).unwrap();
+        let dt = NaiveDateTime::new(date, time);
+        db.execute("INSERT INTO foo (d, b, tt) VALUES (?, ?, ?)", params![date, dt, time])?;
+
+        let (d, b, tt, s): (NaiveDate, NaiveDateTime, NaiveTime, String) =
+            db.query_row("SELECT d, b, tt, b::VARCHAR FROM foo", [], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?))
+            })?;
+        assert_eq!(d, date);
+        assert_eq!(b, dt);
+        assert_eq!(tt, time);
+        assert_eq!(s, "1901-12-13 20:45:52.123456");
+
+        let same: bool = db.query_row(
+            "SELECT ? = DATE '1901-12-13' AND ? = TIMESTAMP '1901-12-13 20:45:52.123456'",
+            params![date, dt],
+            |r| r.get(0),
+        )?;
+        assert!(same);
+        Ok(())
+    }
+
     #[test]
     fn test_date_time_utc() -> Result<()> {
         let db = checked_memory_handle()?;
@@ -279,11 +343,30 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_interval_duration() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let d: Duration = db.query_row("SELECT INTERVAL 2 DAY + INTERVAL 90 MINUTE", [], |r| r.get(0))?;
+        assert_eq!(d, Duration::days(2) + Duration::minutes(90));
+
+        let d = Duration::days(45) + Duration::nanoseconds(1000);
+        roundtrip_type(d);
+        let back: Duration = db.query_row("SELECT ?", [d], |r| r.get(0))?;
+        assert_eq!(back, d);
+
+        let err = db
+            .query_row("SELECT INTERVAL 1 MONTH", [], |r| r.get::<_, Duration>(0))
+            .unwrap_err();
+        assert!(err.to_string().contains("month"), "{err}");
+        Ok(())
+    }
+
     fn roundtrip_type<T: FromSql + ToSql + Eq + std::fmt::Debug>(td: T) {
         let sqled = td.to_sql().unwrap();
         let value = match sqled {
             ToSqlOutput::Borrowed(v) => v,
             ToSqlOutput::Owned(ref v) => ValueRef::from(v),
+            ToSqlOutput::List(..) => unreachable!("not supported"),
         };
         let reversed = FromSql::column_result(value).unwrap();
 
@@ -308,6 +391,24 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_timestamptz() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let utc = Utc.with_ymd_and_hms(2021, 10, 31, 1, 30, 0).unwrap();
+        let sql = "SELECT TIMESTAMPTZ '2021-10-31 01:30:00+00', TIMESTAMPTZ '2021-10-31 03:30:00+02'::VARCHAR, \
+                   '2021-10-31 07:00:00+05:30'";
+        let (a, b, c): (DateTime<Utc>, DateTime<Utc>, DateTime<Utc>) =
+            db.query_row(sql, [], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?;
+        assert_eq!((a, b, c), (utc, utc, utc));
+
+        // an hour apart, even if the local wall-clock times coincide
+        let sql = "SELECT TIMESTAMPTZ '2021-10-31 00:30:00+00', TIMESTAMPTZ '2021-10-31 01:30:00+00'";
+        let (a, b): (DateTime<Local>, DateTime<Local>) = db.query_row(sql, [], |r| Ok((r.get(0)?, r.get(1)?)))?;
+        assert_eq!(b, utc);
+        assert_eq!(b - a, Duration::hours(1));
+        Ok(())
+    }
+
     #[test]
     fn test_duckdb_datetime_functions() -> Result<()> {
         let db = checked_memory_handle()?;
@@ -1,7 +1,14 @@
 extern crate cast;
 
-use super::{TimeUnit, Value, ValueRef};
-use std::{error::Error, fmt};
+use super::{ListType, TimeUnit, Value, ValueRef};
+use crate::Row;
+use std::{
+    any::Any,
+    collections::HashMap,
+    error::Error,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
 
 /// Enum listing possible errors from [`FromSql`] trait.
 #[derive(Debug)]
@@ -69,6 +76,19 @@ pub trait FromSql: Sized {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self>;
 }
 
+/// Formats an unscaled decimal as a plain string, e.g. `(-1234, 2)` as `-12.34`.
+pub(crate) fn decimal_to_string(value: i128, scale: u8) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+    let scale = scale as usize;
+    if scale == 0 {
+        return format!("{sign}{digits}");
+    }
+    let digits = format!("{digits:0>width$}", width = scale + 1);
+    let (int, frac) = digits.split_at(digits.len() - scale);
+    format!("{sign}{int}.{frac}")
+}
+
 macro_rules! from_sql_integral(
     ($t:ident) => (
         impl FromSql for $t {
@@ -97,8 +117,9 @@ macro_rules! from_sql_integral(
                     ValueRef::Float(i) => Ok(<$t as cast::From<f32>>::cast(i).unwrap()),
                     ValueRef::Double(i) => Ok(<$t as cast::From<f64>>::cast(i).unwrap()),
 
-                    // TODO: more efficient way?
-                    ValueRef::Decimal(i) => Ok(i.to_string().parse::<$t>().unwrap()),
+                    ValueRef::Decimal { value, scale, .. } => {
+                        decimal_to_string(value, scale).parse::<$t>().map_err(|_| FromSqlError::InvalidType)
+                    }
 
                     ValueRef::Timestamp(_, i) => Ok(<$t as cast::From<i64>>::cast(i).unwrap()),
                     ValueRef::Date32(i) => Ok(<$t as cast::From<i32>>::cast(i).unwrap()),
@@ -159,6 +180,7 @@ unwrap_integral!(u8);
 unwrap_integral!(u16);
 unwrap_integral!(u32);
 unwrap_integral!(u64);
+unwrap_integral!(u128);
 unwrap_integral!(usize);
 unwrap_integral!(f32);
 unwrap_integral!(f64);
@@ -173,6 +195,7 @@ from_sql_integral!(u8);
 from_sql_integral!(u16);
 from_sql_integral!(u32);
 from_sql_integral!(u64);
+from_sql_integral!(u128);
 from_sql_integral!(usize);
 from_sql_integral!(f32);
 from_sql_integral!(f64);
@@ -225,27 +248,65 @@ impl FromSql for std::sync::Arc<str> {
     }
 }
 
-impl FromSql for Vec<u8> {
-    #[inline]
+/// Decodes `LIST` and `ARRAY` values element by element, so `NULL` elements
+/// need `Vec<Option<T>>`. `Vec<u8>` reads the bytes of `BLOB` and `TEXT`
+/// values instead.
+impl<T: FromSql + 'static> FromSql for Vec<T> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let (range, idx, values) = match value {
+            ValueRef::List(ListType::Regular(arr), idx) => {
+                let offsets = arr.offsets();
+                (offsets[idx] as usize..offsets[idx + 1] as usize, idx, arr.values())
+            }
+            ValueRef::List(ListType::Large(arr), idx) => {
+                let offsets = arr.offsets();
+                (offsets[idx] as usize..offsets[idx + 1] as usize, idx, arr.values())
+            }
+            ValueRef::Array(arr, idx) => {
+                let len = arr.value_length() as usize;
+                (idx * len..(idx + 1) * len, idx, arr.values())
+            }
+            ValueRef::Blob(..) | ValueRef::Text(..) => {
+                let bytes: Box<dyn Any> = Box::new(value.as_blob()?.to_vec());
+                return bytes.downcast().map(|v| *v).map_err(|_| FromSqlError::InvalidType);
+            }
+            _ => return Err(FromSqlError::InvalidType),
+        };
+        range
+            .map(|row| T::column_result(Row::value_ref_internal(row, idx, values)))
+            .collect()
+    }
+}
+
+/// Decodes `ARRAY` values of exactly `N` elements, e.g. a `FLOAT[3]` column
+/// into `[f32; 3]`. `LIST` values, and `BLOB` values for `[u8; N]`, of that
+/// length are accepted as well, and any other length is an error.
+impl<T: FromSql + 'static, const N: usize> FromSql for [T; N] {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
-        value.as_blob().map(|b| b.to_vec())
+        let items = Vec::<T>::column_result(value)?;
+        let len = items.len();
+        items
+            .try_into()
+            .map_err(|_| FromSqlError::Other(format!("expected {N} elements, got {len}").into()))
     }
 }
 
+/// Reads a 16-byte `BLOB` as the bytes of the UUID, and parses text and any
+/// other `BLOB` as a textual UUID, so that the same code works for `UUID`,
+/// `VARCHAR` and `BLOB` columns.
+///
+/// DuckDB exports `UUID` columns to arrow in their canonical textual form, so
+/// the sign-flipped `HUGEINT` it uses internally never reaches this impl.
 #[cfg(feature = "uuid")]
 impl FromSql for uuid::Uuid {
     #[inline]
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         match value {
-            ValueRef::Text(..) => value
-                .as_str()
-                .and_then(|s| uuid::Uuid::parse_str(s).map_err(|_| FromSqlError::InvalidUuidSize(s.len()))),
-            ValueRef::Blob(..) => value
-                .as_blob()
-                .and_then(|bytes| {
-                    uuid::Builder::from_slice(bytes).map_err(|_| FromSqlError::InvalidUuidSize(bytes.len()))
-                })
-                .map(|builder| builder.into_uuid()),
+            ValueRef::Blob(bytes) if bytes.len() == 16 => Ok(uuid::Uuid::from_slice(bytes).unwrap()),
+            ValueRef::Text(bytes) | ValueRef::Blob(bytes) => {
+                let s = std::str::from_utf8(bytes).map_err(|_| FromSqlError::InvalidUuidSize(bytes.len()))?;
+                uuid::Uuid::parse_str(s).map_err(|e| FromSqlError::Other(format!("invalid UUID {s:?}: {e}").into()))
+            }
             _ => Err(FromSqlError::InvalidType),
         }
     }
@@ -261,6 +322,38 @@ impl<T: FromSql> FromSql for Option<T> {
     }
 }
 
+impl<K, V, S> FromSql for HashMap<K, V, S>
+where
+    K: FromSql + Eq + Hash,
+    V: FromSql,
+    S: BuildHasher + Default,
+{
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Map(arr, idx) => {
+                // A MAP is a LIST of STRUCT(key, value), walk the entries of this row
+                let offsets = arr.offsets();
+                let (start, end) = (offsets[idx] as usize, offsets[idx + 1] as usize);
+                (start..end)
+                    .map(|row| {
+                        let key = K::column_result(Row::value_ref_internal(row, 0, arr.keys()))?;
+                        let value = V::column_result(Row::value_ref_internal(row, 1, arr.values()))?;
+                        Ok((key, value))
+                    })
+                    .collect()
+            }
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+impl<T: FromSql> FromSql for std::num::Wrapping<T> {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        T::column_result(value).map(std::num::Wrapping)
+    }
+}
+
 impl FromSql for Value {
     #[inline]
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
@@ -271,7 +364,7 @@ impl FromSql for Value {
 #[cfg(test)]
 mod test {
     use super::FromSql;
-    use crate::{Connection, Error, Result};
+    use crate::{params, Connection, Error, Result};
 
     #[test]
     fn test_timestamp_raw() -> Result<()> {
@@ -347,6 +440,68 @@ mod test {
         Ok(())
     }
 
+    // Values straddling the 64-bit boundary exercise the sign handling of the lower/upper split.
+    #[test]
+    fn test_hugeint_64bit_boundary() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute("CREATE TABLE huge_int (h hugeint);", [])?;
+        let values: [i128; 6] = [
+            i64::MAX as i128 + 1,
+            i64::MIN as i128 - 1,
+            u64::MAX as i128,
+            -(u64::MAX as i128),
+            1 << 64,
+            -(1 << 64),
+        ];
+        for v in values {
+            db.execute("INSERT INTO huge_int VALUES (?);", [v])?;
+            let read = db.query_row("SELECT h FROM huge_int WHERE h = ?", [v], |row| row.get::<_, i128>(0))?;
+            assert_eq!(read, v);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_hugeint_null() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute("CREATE TABLE huge_int (i INTEGER, h hugeint);", [])?;
+        let values = [None, Some(i128::MIN), None, Some(0), Some(i128::MAX), None];
+        for (i, v) in values.iter().enumerate() {
+            db.execute("INSERT INTO huge_int VALUES (?, ?);", params![i, v])?;
+        }
+        let read = db
+            .prepare("SELECT h FROM huge_int ORDER BY i")?
+            .query_map([], |row| row.get::<_, Option<i128>>(0))?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(read, values);
+
+        let list = db.query_row("SELECT list(h ORDER BY i) FROM huge_int", [], |row| {
+            row.get::<_, Vec<Option<i128>>>(0)
+        })?;
+        assert_eq!(list, values);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hugeint_u128() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute("CREATE TABLE huge_int (h hugeint);", [])?;
+        let v = i128::MAX as u128;
+        db.execute("INSERT INTO huge_int VALUES (?);", [v])?;
+        let read = db.query_row("SELECT h FROM huge_int", [], |row| row.get::<_, u128>(0))?;
+        assert_eq!(read, v);
+
+        assert!(db.execute("INSERT INTO huge_int VALUES (?);", [u128::MAX]).is_err());
+        let err = db
+            .query_row("SELECT -1::hugeint", [], |row| row.get::<_, u128>(0))
+            .unwrap_err();
+        match err {
+            Error::IntegralValueOutOfRange(_, value) => assert_eq!(value, -1),
+            _ => panic!("unexpected error: {err}"),
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_integral_ranges() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -402,6 +557,48 @@ mod test {
         Ok(())
     }
 
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_uuid_round_trip() -> crate::Result<()> {
+        let db = Connection::open_in_memory()?;
+        let id = db.query_row("SELECT gen_random_uuid()", [], |r| r.get::<_, uuid::Uuid>(0))?;
+        assert_eq!(id.get_version_num(), 4);
+
+        // Values on both sides of the sign bit DuckDB flips in its storage format
+        for id in [
+            uuid::Uuid::nil(),
+            uuid::Uuid::max(),
+            uuid::Uuid::parse_str("7fffffff-ffff-ffff-ffff-ffffffffffff").unwrap(),
+            uuid::Uuid::parse_str("80000000-0000-0000-0000-000000000000").unwrap(),
+        ] {
+            let (text, back) = db.query_row("SELECT ?::UUID::VARCHAR, ?::UUID", [id, id], |r| {
+                Ok((r.get::<_, String>(0)?, r.get::<_, uuid::Uuid>(1)?))
+            })?;
+            assert_eq!(text, id.to_string());
+            assert_eq!(back, id);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_uuid_from_text_and_blob() -> crate::Result<()> {
+        let db = Connection::open_in_memory()?;
+        let id = uuid::Uuid::parse_str("47183823-2574-4bfd-b411-99ed177d3e43").unwrap();
+        let get = |sql: &str| db.query_row(sql, [], |r| r.get::<_, uuid::Uuid>(0));
+
+        assert_eq!(get("SELECT '47183823-2574-4bfd-b411-99ed177d3e43'::VARCHAR")?, id);
+        assert_eq!(get("SELECT '47183823-2574-4bfd-b411-99ed177d3e43'::BLOB")?, id);
+        assert_eq!(
+            get("SELECT '\\x47\\x18\\x38\\x23\\x25\\x74\\x4b\\xfd\\xb4\\x11\\x99\\xed\\x17\\x7d\\x3e\\x43'::BLOB")?,
+            id
+        );
+
+        let err = get("SELECT 'not-a-uuid'").unwrap_err();
+        assert!(err.to_string().contains("invalid UUID \"not-a-uuid\""), "{err}");
+        Ok(())
+    }
+
     #[cfg(feature = "uuid")]
     #[test]
     fn test_uuid_from_string() -> crate::Result<()> {
@@ -417,4 +614,178 @@ mod test {
         assert_eq!(v.0.to_string(), "47183823-2574-4bfd-b411-99ed177d3e43");
         Ok(())
     }
+
+    #[test]
+    fn test_list() -> crate::Result<()> {
+        let db = Connection::open_in_memory()?;
+        let v = db.query_row("SELECT [1, 2, 3]", [], |row| row.get::<_, Vec<i64>>(0))?;
+        assert_eq!(v, vec![1, 2, 3]);
+
+        let v = db.query_row("SELECT []::INTEGER[]", [], |row| row.get::<_, Vec<i32>>(0))?;
+        assert!(v.is_empty());
+
+        let v = db.query_row("SELECT [NULL, NULL]::VARCHAR[]", [], |row| {
+            row.get::<_, Vec<Option<String>>>(0)
+        })?;
+        assert_eq!(v, vec![None, None]);
+        let err = db
+            .query_row("SELECT [1, NULL]", [], |row| row.get::<_, Vec<i32>>(0))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidColumnType(..)), "{err:?}");
+
+        let v = db.query_row("SELECT [['a'], [], ['b', 'c']]", [], |row| {
+            row.get::<_, Vec<Vec<String>>>(0)
+        })?;
+        assert_eq!(v, vec![vec!["a"], vec![], vec!["b", "c"]]);
+
+        let v = db.query_row("SELECT [1.5, 2.5]::DOUBLE[2]", [], |row| row.get::<_, Vec<f64>>(0))?;
+        assert_eq!(v, vec![1.5, 2.5]);
+
+        // BLOBs still read into Vec<u8>, but not into other vectors
+        let v = db.query_row("SELECT '\\x01\\x02'::BLOB", [], |row| row.get::<_, Vec<u8>>(0))?;
+        assert_eq!(v, vec![1, 2]);
+        let err = db
+            .query_row("SELECT '\\x01\\x02'::BLOB", [], |row| row.get::<_, Vec<i32>>(0))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidColumnType(..)), "{err:?}");
+        let err = db
+            .query_row("SELECT 'ab'", [], |row| row.get::<_, Vec<u16>>(0))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidColumnType(..)), "{err:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_size_array() -> crate::Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE embeddings (id INTEGER, v FLOAT[3]);
+             INSERT INTO embeddings VALUES (1, [0.5, 1.5, 2.5]), (2, NULL)",
+        )?;
+        let get = |id: i32| {
+            db.query_row("SELECT v FROM embeddings WHERE id = ?", [id], |row| {
+                row.get::<_, Option<[f32; 3]>>(0)
+            })
+        };
+        assert_eq!(get(1)?, Some([0.5, 1.5, 2.5]));
+        assert_eq!(get(2)?, None);
+
+        let v = db.query_row("SELECT [[1, 2], [3, 4]]::INTEGER[2][2]", [], |row| {
+            row.get::<_, [[i32; 2]; 2]>(0)
+        })?;
+        assert_eq!(v, [[1, 2], [3, 4]]);
+
+        let err = db
+            .query_row("SELECT v FROM embeddings WHERE id = 1", [], |row| {
+                row.get::<_, [f32; 4]>(0)
+            })
+            .unwrap_err();
+        match err {
+            Error::FromSqlConversionFailure(0, _, err) => assert_eq!(err.to_string(), "expected 4 elements, got 3"),
+            err => panic!("Unexpected error {err:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_map() -> crate::Result<()> {
+        use std::collections::HashMap;
+
+        let db = Connection::open_in_memory()?;
+        let map = db.query_row("SELECT MAP {'a': 1, 'b': 2}", [], |row| {
+            row.get::<_, HashMap<String, i64>>(0)
+        })?;
+        assert_eq!(map, HashMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]));
+
+        let map = db.query_row("SELECT MAP {1: NULL, 2: 'x'}", [], |row| {
+            row.get::<_, HashMap<i32, Option<String>>>(0)
+        })?;
+        assert_eq!(map, HashMap::from([(1, None), (2, Some("x".to_owned()))]));
+
+        let map = db.query_row("SELECT NULL::MAP(VARCHAR, INTEGER)", [], |row| {
+            row.get::<_, Option<HashMap<String, i32>>>(0)
+        })?;
+        assert_eq!(map, None);
+
+        let err = db
+            .query_row("SELECT MAP {'a': 'b'}", [], |row| row.get::<_, HashMap<String, i64>>(0))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidColumnType(..)), "{err:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_enum_label() -> Result<()> {
+        use crate::types::{FromSqlError, FromSqlResult, Value, ValueRef};
+
+        #[derive(Debug, PartialEq)]
+        enum Status {
+            Active,
+            Inactive,
+        }
+
+        impl FromSql for Status {
+            fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+                match value.as_str()? {
+                    "ACTIVE" => Ok(Status::Active),
+                    "INACTIVE" => Ok(Status::Inactive),
+                    other => Err(FromSqlError::Other(format!("unknown status {other}").into())),
+                }
+            }
+        }
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TYPE status AS ENUM ('INACTIVE', 'ACTIVE');
+             CREATE TYPE medium AS ENUM (SELECT 'm' || range FROM range(300));
+             CREATE TYPE large AS ENUM (SELECT 'l' || range FROM range(70000));
+             CREATE TABLE t(s status, m medium, l large);
+             INSERT INTO t VALUES ('ACTIVE', 'm299', 'l69999'), ('INACTIVE', 'm0', 'l1'), (NULL, 'm7', 'l0');",
+        )?;
+        let mut stmt = db.prepare("SELECT s, m, l FROM t")?;
+        let rows = stmt
+            .query_map([], |r| {
+                Ok((
+                    r.get::<_, Option<String>>(0)?,
+                    r.get::<_, Option<Status>>(0)?,
+                    r.get::<_, String>(1)?,
+                    r.get::<_, String>(2)?,
+                    r.get::<_, Value>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        let expected = [
+            (Some("ACTIVE"), Some(Status::Active), "m299", "l69999"),
+            (Some("INACTIVE"), Some(Status::Inactive), "m0", "l1"),
+            (None, None, "m7", "l0"),
+        ];
+        for (row, (s, status, m, l)) in rows.into_iter().zip(expected) {
+            assert_eq!(row.0.as_deref(), s);
+            assert_eq!(row.1, status);
+            assert_eq!(row.2, m);
+            assert_eq!(row.3, l);
+            assert_eq!(row.4, Value::Enum(l.to_owned()));
+        }
+
+        let mut stmt = db.prepare("SELECT s, m, l FROM t")?;
+        let indexes = stmt
+            .query_map([], |r| {
+                let index = |i| r.get_ref(i).map(|v| v.enum_index());
+                Ok((index(0)?, index(1)?, index(2)?))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(
+            indexes,
+            [
+                (Some(1), Some(299), Some(69999)),
+                (Some(0), Some(0), Some(1)),
+                (None, Some(7), Some(0))
+            ]
+        );
+        assert_eq!(
+            db.query_row("SELECT 'm1'", [], |r| Ok(r.get_ref(0)?.enum_index()))?,
+            None
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,88 @@
+//! [`ToSql`] and [`FromSql`] implementation for [`IpAddr`], [`Ipv4Addr`] and
+//! [`Ipv6Addr`].
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::{
+    types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef},
+    Result,
+};
+
+macro_rules! ip_addr {
+    ($t:ty) => {
+        /// Serialize the address to text, which DuckDB casts to `INET` when
+        /// the `inet` extension is loaded.
+        impl ToSql for $t {
+            #[inline]
+            fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+                Ok(ToSqlOutput::from(self.to_string()))
+            }
+        }
+
+        /// Deserialize text to an address.
+        impl FromSql for $t {
+            #[inline]
+            fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+                match value {
+                    ValueRef::Text(s) => {
+                        let s = std::str::from_utf8(s).map_err(|e| FromSqlError::Other(Box::new(e)))?;
+                        s.parse().map_err(|e| FromSqlError::Other(Box::new(e)))
+                    }
+                    _ => Err(FromSqlError::InvalidType),
+                }
+            }
+        }
+    };
+}
+
+ip_addr!(IpAddr);
+ip_addr!(Ipv4Addr);
+ip_addr!(Ipv6Addr);
+
+#[cfg(test)]
+mod test {
+    use std::net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use crate::{params, Connection, Error, Result};
+
+    fn checked_memory_handle() -> Result<Connection> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE ips (i INTEGER, v TEXT)")?;
+        Ok(db)
+    }
+
+    #[test]
+    fn test_sql_ip_addr() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let v4 = Ipv4Addr::new(192, 168, 0, 1);
+        let v6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+
+        db.execute(
+            "INSERT INTO ips (i, v) VALUES (0, ?), (1, ?), (2, ?), (3, ?)",
+            params![v4, v6, IpAddr::V6(Ipv6Addr::LOCALHOST), "300.1.2.3"],
+        )?;
+        let get = |id: i64| db.query_row("SELECT v FROM ips WHERE i = ?", [id], |r| r.get::<_, IpAddr>(0));
+
+        assert_eq!(get(0)?, IpAddr::V4(v4));
+        assert_eq!(get(1)?, IpAddr::V6(v6));
+        assert_eq!(get(2)?, IpAddr::V6(Ipv6Addr::LOCALHOST));
+        assert_eq!(
+            db.query_row("SELECT v FROM ips WHERE i = 0", [], |r| r.get::<_, Ipv4Addr>(0))?,
+            v4
+        );
+        assert_eq!(
+            db.query_row("SELECT v FROM ips WHERE i = 1", [], |r| r.get::<_, Ipv6Addr>(0))?,
+            v6
+        );
+
+        // A v6 address is not a valid v4 address.
+        assert!(db
+            .query_row("SELECT v FROM ips WHERE i = 1", [], |r| r.get::<_, Ipv4Addr>(0))
+            .is_err());
+
+        match get(3).unwrap_err() {
+            Error::FromSqlConversionFailure(_, _, e) => assert!(e.downcast::<AddrParseError>().is_ok()),
+            e => panic!("Expected conversion failure, got {e}"),
+        }
+        Ok(())
+    }
+}
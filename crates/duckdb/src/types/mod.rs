@@ -28,41 +28,16 @@
 //! types, so if you store an `i64` in a column with type `REAL` it will be
 //! stored as an `INTEGER`, not a `REAL`.
 //!
-//! If the `time` feature is enabled, implementations are
-//! provided for `time::OffsetDateTime` that use the RFC 3339 date/time format,
-//! `"%Y-%m-%dT%H:%M:%S.%fZ"`, to store time values as strings.  These values
-//! can be parsed by SQLite's builtin
-//! [datetime](https://www.sqlite.org/lang_datefunc.html) functions.  If you
-//! want different storage for datetimes, you can use a newtype.
-#![cfg_attr(
-    feature = "time",
-    doc = r##"
-For example, to store datetimes as `i64`s counting the number of seconds since
-the Unix epoch:
-
-```
-use duckdb::types::{FromSql, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
-use duckdb::Result;
-
-pub struct DateTimeSql(pub time::OffsetDateTime);
-
-impl FromSql for DateTimeSql {
-    fn column_result(value: ValueRef) -> FromSqlResult<Self> {
-        i64::column_result(value).map(|as_i64| {
-            DateTimeSql(time::OffsetDateTime::from_unix_timestamp(as_i64))
-        })
-    }
-}
-
-impl ToSql for DateTimeSql {
-    fn to_sql(&self) -> Result<ToSqlOutput> {
-        Ok(self.0.timestamp().into())
-    }
-}
-```
-
-"##
-)]
+//! If the `chrono` feature is enabled, `NaiveDate`, `NaiveTime` and
+//! `NaiveDateTime` are bound as native `DATE`, `TIME` and `TIMESTAMP` values,
+//! and `DateTime<Tz>` is bound as text normalized to UTC, which DuckDB casts
+//! on insert into a `TIMESTAMP` or `TIMESTAMPTZ` column. If you want different
+//! storage for datetimes, you can use a newtype.
+//!
+//! If the `inet` feature is enabled, `IpAddr`, `Ipv4Addr` and `Ipv6Addr` are
+//! stored as text, which DuckDB casts to `INET` when the `inet` extension is
+//! loaded.
+//!
 //! [`ToSql`] and [`FromSql`] are also implemented for `Option<T>` where `T`
 //! implements [`ToSql`] or [`FromSql`] for the cases where you want to know if
 //! a value was NULL (which gets translated to `None`).
@@ -70,19 +45,33 @@ impl ToSql for DateTimeSql {
 pub use self::{
     from_sql::{FromSql, FromSqlError, FromSqlResult},
     ordered_map::OrderedMap,
-    to_sql::{ToSql, ToSqlOutput},
+    time_tz::TimeWithOffset,
+    to_sql::{List, ToSql, ToSqlOutput},
     value::Value,
     value_ref::{EnumType, ListType, TimeUnit, ValueRef},
 };
 
+#[cfg(feature = "serde_json")]
+pub(crate) use self::serde_json::write_object;
+pub(crate) use self::{from_sql::decimal_to_string, value::list_element_type};
+
+use crate::Error;
 use arrow::datatypes::DataType;
 use std::fmt;
 
+mod arrow_value;
 #[cfg(feature = "chrono")]
 mod chrono;
 mod from_sql;
+#[cfg(feature = "inet")]
+mod inet;
+#[cfg(feature = "ordered-float")]
+mod ordered_float;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
 #[cfg(feature = "serde_json")]
 mod serde_json;
+mod time_tz;
 mod to_sql;
 #[cfg(feature = "url")]
 mod url;
@@ -148,6 +137,8 @@ pub enum Type {
     Date32,
     /// TIME64
     Time64,
+    /// TIMETZ, see [`TimeWithOffset`]
+    TimeTz,
     /// INTERVAL
     Interval,
     /// LIST
@@ -166,9 +157,11 @@ pub enum Type {
     Any,
 }
 
-impl From<&DataType> for Type {
-    fn from(value: &DataType) -> Self {
-        match value {
+impl Type {
+    /// The DuckDB type of values of the Arrow type `value`, or
+    /// [`Error::UnsupportedArrowType`] if it has no DuckDB counterpart.
+    pub fn try_from_arrow(value: &DataType) -> Result<Self, Error> {
+        Ok(match value {
             DataType::Null => Self::Null,
             DataType::Boolean => Self::Boolean,
             DataType::Int8 => Self::TinyInt,
@@ -180,45 +173,57 @@ impl From<&DataType> for Type {
             DataType::UInt32 => Self::UInt,
             DataType::UInt64 => Self::UBigInt,
             // DataType::Float16 => Self::Float16,
-            // DataType::Float32 => Self::Float32,
-            DataType::Float64 => Self::Float,
+            DataType::Float32 => Self::Float,
+            DataType::Float64 => Self::Double,
             DataType::Timestamp(_, _) => Self::Timestamp,
-            DataType::Date32 => Self::Date32,
-            // DataType::Date64 => Self::Date64,
-            // DataType::Time32(_) => Self::Time32,
-            DataType::Time64(_) => Self::Time64,
-            // DataType::Duration(_) => Self::Duration,
-            // DataType::Interval(_) => Self::Interval,
-            DataType::Binary => Self::Blob,
-            // DataType::FixedSizeBinary(_) => Self::FixedSizeBinary,
-            // DataType::LargeBinary => Self::LargeBinary,
+            // DuckDB has a single DATE and TIME type, and reads Arrow durations
+            // as intervals.
+            DataType::Date32 | DataType::Date64 => Self::Date32,
+            DataType::Time32(_) | DataType::Time64(_) => Self::Time64,
+            DataType::Duration(_) | DataType::Interval(_) => Self::Interval,
+            DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => Self::Blob,
             DataType::LargeUtf8 | DataType::Utf8 => Self::Text,
-            DataType::List(inner) => Self::List(Box::new(Type::from(inner.data_type()))),
-            DataType::FixedSizeList(field, size) => {
-                Self::Array(Box::new(Type::from(field.data_type())), (*size).try_into().unwrap())
-            }
+            DataType::List(inner) => Self::List(Box::new(Type::try_from_arrow(inner.data_type())?)),
+            DataType::FixedSizeList(field, size) => Self::Array(
+                Box::new(Type::try_from_arrow(field.data_type())?),
+                (*size).try_into().unwrap(),
+            ),
             // DataType::LargeList(_) => Self::LargeList,
             DataType::Struct(inner) => Self::Struct(
                 inner
                     .iter()
-                    .map(|f| (f.name().to_owned(), Type::from(f.data_type())))
-                    .collect(),
+                    .map(|f| Ok((f.name().to_owned(), Type::try_from_arrow(f.data_type())?)))
+                    .collect::<Result<_, Error>>()?,
             ),
-            DataType::LargeList(inner) => Self::List(Box::new(Type::from(inner.data_type()))),
-            // DataType::Union(_, _) => Self::Union,
+            DataType::LargeList(inner) => Self::List(Box::new(Type::try_from_arrow(inner.data_type())?)),
+            DataType::Union(..) => Self::Union,
+            DataType::Dictionary(..) => Self::Enum,
             DataType::Decimal128(..) => Self::Decimal,
             DataType::Decimal256(..) => Self::Decimal,
             DataType::Map(field, ..) => {
                 let data_type = field.data_type();
                 match data_type {
                     DataType::Struct(fields) => Self::Map(
-                        Box::new(Type::from(fields[0].data_type())),
-                        Box::new(Type::from(fields[1].data_type())),
+                        Box::new(Type::try_from_arrow(fields[0].data_type())?),
+                        Box::new(Type::try_from_arrow(fields[1].data_type())?),
                     ),
-                    _ => unreachable!(),
+                    _ => return Err(Error::UnsupportedArrowType(value.clone())),
                 }
             }
-            res => unimplemented!("{}", res),
+            res => return Err(Error::UnsupportedArrowType(res.clone())),
+        })
+    }
+}
+
+/// # Panics
+///
+/// Panics if the Arrow type has no DuckDB counterpart, see
+/// [`Type::try_from_arrow`].
+impl From<&DataType> for Type {
+    fn from(value: &DataType) -> Self {
+        match Type::try_from_arrow(value) {
+            Ok(t) => t,
+            Err(err) => panic!("{err}"),
         }
     }
 }
@@ -245,6 +250,7 @@ impl fmt::Display for Type {
             Type::Blob => f.pad("Blob"),
             Type::Date32 => f.pad("Date32"),
             Type::Time64 => f.pad("Time64"),
+            Type::TimeTz => f.pad("TimeTz"),
             Type::Interval => f.pad("Interval"),
             Type::Struct(..) => f.pad("Struct"),
             Type::List(..) => f.pad("List"),
@@ -460,6 +466,112 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_nested_value() -> Result<()> {
+        use super::{OrderedMap, Type, Value};
+        let db = Connection::open_in_memory()?;
+
+        let value = db.query_row("SELECT {'x': [1, 2], 'y': 'hi'}", [], |row| row.get::<_, Value>(0))?;
+        assert_eq!(
+            value,
+            Value::Struct(OrderedMap::from(vec![
+                ("x".to_owned(), Value::List(vec![Value::Int(1), Value::Int(2)])),
+                ("y".to_owned(), Value::Text("hi".to_owned())),
+            ]))
+        );
+        assert_eq!(
+            value.data_type(),
+            Type::Struct(vec![
+                ("x".to_owned(), Type::List(Box::new(Type::Int))),
+                ("y".to_owned(), Type::Text),
+            ])
+        );
+
+        let value = db.query_row("SELECT MAP {'k': [{'a': 1}]}", [], |row| row.get::<_, Value>(0))?;
+        let entry = Value::Struct(OrderedMap::from(vec![("a".to_owned(), Value::Int(1))]));
+        assert_eq!(
+            value,
+            Value::Map(OrderedMap::from(vec![(
+                Value::Text("k".to_owned()),
+                Value::List(vec![entry])
+            )]))
+        );
+        assert_eq!(
+            value.data_type(),
+            Type::Map(
+                Box::new(Type::Text),
+                Box::new(Type::List(Box::new(Type::Struct(vec![("a".to_owned(), Type::Int)]))))
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_value() -> Result<()> {
+        use super::{ToSqlOutput, Value};
+        let db = Connection::open_in_memory()?;
+
+        let value = db.query_row("SELECT 12.340::DECIMAL(10, 3)", [], |row| row.get::<_, Value>(0))?;
+        assert_eq!(
+            value,
+            Value::Decimal {
+                value: 12340,
+                width: 10,
+                scale: 3
+            }
+        );
+        assert_eq!(
+            12.34,
+            db.query_row("SELECT 12.340::DECIMAL(10, 3)", [], |row| row.get::<_, f64>(0))?
+        );
+
+        // Too wide for a double to hold exactly
+        let sql = "SELECT -1234567890123456789012345678.9012345678::DECIMAL(38, 10)";
+        let value = db.query_row(sql, [], |row| row.get::<_, Value>(0))?;
+        assert_eq!(
+            value,
+            Value::Decimal {
+                value: -12345678901234567890123456789012345678,
+                width: 38,
+                scale: 10
+            }
+        );
+
+        // Binding a decimal round-trips it unchanged
+        let bound = db.query_row("SELECT ?::VARCHAR", [ToSqlOutput::Owned(value.clone())], |row| {
+            row.get::<_, String>(0)
+        })?;
+        assert_eq!(bound, "-1234567890123456789012345678.9012345678");
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_value() -> Result<()> {
+        use super::{Type, Value};
+        let db = Connection::open_in_memory()?;
+
+        let mut stmt = db.prepare("SELECT INTERVAL 3 MONTH + INTERVAL 2 DAY, INTERVAL '1.5' SECOND")?;
+        let (a, b) = stmt.query_row([], |row| Ok((row.get::<_, Value>(0)?, row.get::<_, Value>(1)?)))?;
+        assert_eq!(
+            a,
+            Value::Interval {
+                months: 3,
+                days: 2,
+                nanos: 0
+            }
+        );
+        assert_eq!(
+            b,
+            Value::Interval {
+                months: 0,
+                days: 0,
+                nanos: 1_500_000_000
+            }
+        );
+        assert_eq!(stmt.column_type(0)?, Type::Interval);
+        Ok(())
+    }
+
     macro_rules! test_conversion {
         ($db_etc:ident, $insert_value:expr, $get_type:ty,expect $expected_value:expr) => {
             $db_etc.insert_statement.execute(params![$insert_value])?;
@@ -546,4 +658,91 @@ mod test {
         test_conversion!(db_etc, 0f64, i64, expect_from_sql_error);
         Ok(())
     }
+
+    #[test]
+    fn test_float_double() -> Result<()> {
+        use super::Type;
+        let db = Connection::open_in_memory()?;
+
+        let mut stmt = db.prepare("SELECT 1.25::FLOAT, 0.1::DOUBLE")?;
+        let (f, d, f_wide) = stmt.query_row([], |row| {
+            assert_eq!(row.get_ref(0)?.try_data_type()?, Type::Float);
+            assert_eq!(row.get_ref(1)?.try_data_type()?, Type::Double);
+            Ok((row.get::<_, f32>(0)?, row.get::<_, f64>(1)?, row.get::<_, f64>(0)?))
+        })?;
+        assert_eq!(f, 1.25);
+        assert_eq!(d, 0.1);
+        assert_eq!(f_wide, 1.25);
+        assert_eq!(stmt.column_type(0)?, Type::Float);
+        assert_eq!(stmt.column_type(1)?, Type::Double);
+
+        let f: f32 = db.query_row("SELECT ?::FLOAT", [0.1f32], |row| row.get(0))?;
+        assert_eq!(f, 0.1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_arrow_type() {
+        use super::Type;
+        use arrow::datatypes::{DataType, TimeUnit};
+
+        assert_eq!(Type::try_from_arrow(&DataType::Int32), Ok(Type::Int));
+        assert_eq!(Type::from(&DataType::Int32), Type::Int);
+        assert_eq!(Type::try_from_arrow(&DataType::Date64), Ok(Type::Date32));
+        for unit in [TimeUnit::Second, TimeUnit::Millisecond] {
+            assert_eq!(Type::try_from_arrow(&DataType::Time32(unit)), Ok(Type::Time64));
+        }
+        for unit in [
+            TimeUnit::Second,
+            TimeUnit::Millisecond,
+            TimeUnit::Microsecond,
+            TimeUnit::Nanosecond,
+        ] {
+            assert_eq!(Type::try_from_arrow(&DataType::Duration(unit)), Ok(Type::Interval));
+        }
+        assert_eq!(
+            Type::try_from_arrow(&DataType::Float16),
+            Err(Error::UnsupportedArrowType(DataType::Float16))
+        );
+    }
+
+    #[test]
+    fn test_struct_type() -> Result<()> {
+        use super::Type;
+
+        let db = Connection::open_in_memory()?;
+        let mut stmt = db.prepare("SELECT {'z': 1, 'a': 'duck', 'm': [1.5]} AS s")?;
+        let rows = stmt.query([])?;
+        let ty = rows.as_ref().unwrap().column_type(0)?;
+        assert_eq!(
+            ty,
+            Type::Struct(vec![
+                ("z".to_owned(), Type::Int),
+                ("a".to_owned(), Type::Text),
+                ("m".to_owned(), Type::List(Box::new(Type::Decimal))),
+            ])
+        );
+        assert_eq!(ty.to_string(), "Struct");
+        Ok(())
+    }
+
+    #[test]
+    fn test_union_column_type_error() -> Result<()> {
+        use super::Type;
+
+        let db = Connection::open_in_memory()?;
+        let err = db
+            .query_row("SELECT union_value(num := 2)::UNION(num INT, str VARCHAR)", [], |row| {
+                row.get::<_, i32>(0)
+            })
+            .unwrap_err();
+        match err {
+            Error::InvalidColumnType(idx, _, ty) => {
+                assert_eq!(idx, 0);
+                assert_eq!(ty, Type::Union);
+            }
+            e => panic!("Unexpected error type: {e:?}"),
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,61 @@
+//! [`ToSql`] and [`FromSql`] implementation for [`ordered_float::OrderedFloat`].
+use crate::{
+    types::{FromSql, FromSqlResult, ToSql, ToSqlOutput, ValueRef},
+    Result,
+};
+use ordered_float::OrderedFloat;
+
+/// Bind `OrderedFloat<T>` as `T` is bound, e.g. an `OrderedFloat<f64>` as a
+/// `DOUBLE`.
+impl<T: ToSql> ToSql for OrderedFloat<T> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        self.0.to_sql()
+    }
+}
+
+/// Read a value as `T` is read. The value is wrapped as is, so a `NaN` keeps
+/// its sign and payload bits.
+impl<T: FromSql> FromSql for OrderedFloat<T> {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        T::column_result(value).map(OrderedFloat)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Connection, Result};
+    use ordered_float::OrderedFloat;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_ordered_float_round_trip() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo (f DOUBLE)")?;
+        let nan = f64::from_bits(0x7ff8_0000_dead_beef);
+        for f in [1.5, f64::NEG_INFINITY, nan, -0.0] {
+            db.execute("INSERT INTO foo VALUES (?)", [OrderedFloat(f)])?;
+        }
+
+        let mut stmt = db.prepare("SELECT f FROM foo")?;
+        let read = stmt
+            .query_map([], |r| r.get::<_, OrderedFloat<f64>>(0))?
+            .collect::<Result<BTreeSet<_>>>()?;
+        assert_eq!(
+            read.into_iter().collect::<Vec<_>>(),
+            [
+                OrderedFloat(f64::NEG_INFINITY),
+                OrderedFloat(-0.0),
+                OrderedFloat(1.5),
+                OrderedFloat(nan)
+            ]
+        );
+
+        let read: OrderedFloat<f64> = db.query_row("SELECT f FROM foo WHERE isnan(f)", [], |r| r.get(0))?;
+        assert_eq!(read.to_bits(), nan.to_bits());
+        let read: OrderedFloat<f32> = db.query_row("SELECT 2.5::FLOAT", [], |r| r.get(0))?;
+        assert_eq!(read, OrderedFloat(2.5));
+        Ok(())
+    }
+}
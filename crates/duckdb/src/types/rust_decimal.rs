@@ -0,0 +1,85 @@
+//! [`ToSql`] and [`FromSql`] implementation for [`rust_decimal::Decimal`].
+use crate::{
+    types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, Value, ValueRef},
+    Result,
+};
+use rust_decimal::Decimal;
+
+/// Bind `Decimal` as a `DECIMAL(38, scale)`, keeping its scale.
+impl ToSql for Decimal {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Decimal {
+            value: self.mantissa(),
+            width: 38,
+            scale: self.scale() as u8,
+        }))
+    }
+}
+
+/// Read a `DECIMAL` (or integral `HUGEINT`) without going through `f64`.
+///
+/// Fails with [`FromSqlError::OutOfRange`] for values that don't fit in the
+/// 96-bit mantissa or 28 digit scale of `Decimal`.
+impl FromSql for Decimal {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let (value, scale) = match value {
+            ValueRef::Decimal { value, scale, .. } => (value, scale),
+            ValueRef::HugeInt(value) => (value, 0),
+            _ => return Err(FromSqlError::InvalidType),
+        };
+        Decimal::try_from_i128_with_scale(value, scale as u32).map_err(|_| FromSqlError::OutOfRange(value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Connection, Error, Result};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_decimal_scale() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let d: Decimal = db.query_row("SELECT 12.340::DECIMAL(10, 3)", [], |r| r.get(0))?;
+        assert_eq!(d.to_string(), "12.340");
+
+        let d: Decimal = db.query_row("SELECT -123456789012345678901234.5678::DECIMAL(38, 4)", [], |r| {
+            r.get(0)
+        })?;
+        assert_eq!(d, Decimal::from_str("-123456789012345678901234.5678").unwrap());
+
+        let d: Decimal = db.query_row("SELECT 42::HUGEINT", [], |r| r.get(0))?;
+        assert_eq!(d, Decimal::from(42));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_round_trip() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE prices (p DECIMAL(38, 10))")?;
+        let price = Decimal::from_str("79228162514.2643383279").unwrap();
+        db.execute("INSERT INTO prices VALUES (?)", [price])?;
+
+        let (d, s): (Decimal, String) =
+            db.query_row("SELECT p, p::VARCHAR FROM prices", [], |r| Ok((r.get(0)?, r.get(1)?)))?;
+        assert_eq!(d, price);
+        assert_eq!(s, "79228162514.2643383279");
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_out_of_range() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let err = db
+            .query_row(
+                "SELECT 9999999999999999999999999999.9999999999::DECIMAL(38, 10)",
+                [],
+                |r| r.get::<_, Decimal>(0),
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::IntegralValueOutOfRange(..)), "{err:?}");
+        Ok(())
+    }
+}
@@ -1,13 +1,23 @@
 //! [`ToSql`] and [`FromSql`] implementation for JSON `Value`.
 
-use serde_json::Value;
+use std::io::{self, Write};
+
+use serde_json::{Map, Number, Value};
 
 use crate::{
-    types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef},
+    types::{
+        self, decimal_to_string,
+        value::{format_date, format_time, micros, MICROS_PER_DAY},
+        FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef,
+    },
     Result,
 };
 
 /// Serialize JSON `Value` to text.
+///
+/// The C API can't create `JSON` typed values, so the text is bound as a
+/// `VARCHAR`, which DuckDB casts implicitly when inserting into a `JSON`
+/// column (this needs the `json` extension, as does the column type itself).
 impl ToSql for Value {
     #[inline]
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
@@ -15,7 +25,8 @@ impl ToSql for Value {
     }
 }
 
-/// Deserialize text/blob to JSON `Value`.
+/// Deserialize text/blob to JSON `Value`. `JSON` columns are read as text, so
+/// they decode the same way.
 impl FromSql for Value {
     #[inline]
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
@@ -28,6 +39,129 @@ impl FromSql for Value {
     }
 }
 
+/// Convert a DuckDB [`Value`](types::Value) to JSON, following DuckDB's own
+/// `to_json`.
+///
+/// Lists and arrays become arrays, and structs and maps become objects. Map
+/// keys that aren't text are rendered as JSON text. Temporal values and blobs
+/// are rendered as strings in DuckDB's text format, and intervals as objects
+/// with `months`, `days` and `nanos`. Decimals become numbers, and integers
+/// that don't fit in 64 bits become strings. Non-finite floats become `null`.
+impl From<types::Value> for Value {
+    fn from(value: types::Value) -> Value {
+        use types::Value as V;
+        match value {
+            V::Null => Value::Null,
+            V::Boolean(b) => b.into(),
+            V::TinyInt(i) => i.into(),
+            V::SmallInt(i) => i.into(),
+            V::Int(i) => i.into(),
+            V::BigInt(i) => i.into(),
+            V::HugeInt(i) => i64::try_from(i)
+                .map(Value::from)
+                .or_else(|_| u64::try_from(i).map(Value::from))
+                .unwrap_or_else(|_| i.to_string().into()),
+            V::UTinyInt(i) => i.into(),
+            V::USmallInt(i) => i.into(),
+            V::UInt(i) => i.into(),
+            V::UBigInt(i) => i.into(),
+            V::Float(f) => float(f.into()),
+            V::Double(f) => float(f),
+            V::Decimal { value, scale, .. } => float(decimal_to_string(value, scale).parse().unwrap()),
+            V::Timestamp(unit, t) => {
+                let micros = micros(unit, t);
+                let (days, micros) = (micros.div_euclid(MICROS_PER_DAY), micros.rem_euclid(MICROS_PER_DAY));
+                format!("{} {}", format_date(days), format_time(micros)).into()
+            }
+            V::Text(s) | V::Enum(s) => s.into(),
+            V::Blob(b) => b
+                .iter()
+                .map(|&b| match b {
+                    b'\\' => "\\\\".to_owned(),
+                    0x20..=0x7e => (b as char).to_string(),
+                    _ => format!("\\x{b:02X}"),
+                })
+                .collect::<String>()
+                .into(),
+            V::Date32(d) => format_date(d.into()).into(),
+            V::Time64(unit, t) => format_time(micros(unit, t)).into(),
+            V::Interval { months, days, nanos } => {
+                let mut map = Map::new();
+                map.insert("months".to_owned(), months.into());
+                map.insert("days".to_owned(), days.into());
+                map.insert("nanos".to_owned(), nanos.into());
+                Value::Object(map)
+            }
+            V::List(items) | V::Array(items) => items.into_iter().map(Value::from).collect(),
+            V::Struct(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(k, v)| (k.clone(), Value::from(v.clone())))
+                    .collect(),
+            ),
+            V::Map(entries) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(k, v)| (json_key(k.clone()), Value::from(v.clone())))
+                    .collect(),
+            ),
+            V::Union(value) => (*value).into(),
+        }
+    }
+}
+
+/// Write `value` as JSON, like converting it to a JSON `Value` would, but
+/// keeping struct fields and map entries in order.
+pub(crate) fn write_json<W: Write>(writer: &mut W, value: types::Value) -> io::Result<()> {
+    match value {
+        types::Value::Struct(fields) => write_object(writer, fields.iter().map(|(k, v)| (k.clone(), v.clone()))),
+        types::Value::Map(entries) => {
+            write_object(writer, entries.iter().map(|(k, v)| (json_key(k.clone()), v.clone())))
+        }
+        types::Value::List(items) | types::Value::Array(items) => {
+            writer.write_all(b"[")?;
+            for (i, item) in items.into_iter().enumerate() {
+                if i > 0 {
+                    writer.write_all(b",")?;
+                }
+                write_json(writer, item)?;
+            }
+            writer.write_all(b"]")
+        }
+        types::Value::Union(value) => write_json(writer, *value),
+        value => Ok(serde_json::to_writer(writer, &Value::from(value))?),
+    }
+}
+
+/// Write `entries` as a JSON object, in order.
+pub(crate) fn write_object<W: Write>(
+    writer: &mut W,
+    entries: impl IntoIterator<Item = (String, types::Value)>,
+) -> io::Result<()> {
+    writer.write_all(b"{")?;
+    for (i, (key, value)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut *writer, &key)?;
+        writer.write_all(b":")?;
+        write_json(writer, value)?;
+    }
+    writer.write_all(b"}")
+}
+
+/// JSON object keys are strings, so other keys are rendered as JSON text.
+fn json_key(key: types::Value) -> String {
+    match Value::from(key) {
+        Value::String(s) => s,
+        key => key.to_string(),
+    }
+}
+
+fn float(f: f64) -> Value {
+    Number::from_f64(f).map_or(Value::Null, Value::Number)
+}
+
 #[cfg(test)]
 mod test {
     use crate::{types::ToSql, Connection, Result};
@@ -55,4 +189,52 @@ mod test {
         assert_eq!(data, b);
         Ok(())
     }
+
+    #[test]
+    fn test_value_to_json() -> Result<()> {
+        use crate::types::Value;
+        use serde_json::json;
+
+        let db = Connection::open_in_memory()?;
+        let sql = "SELECT 170141183460469231731687303715884105727::HUGEINT, 12.340::DECIMAL(6, 3), \
+                   'nan'::DOUBLE, DATE '1969-07-20', TIME '20:17:40.5', TIMESTAMP '2000-02-29 00:00:01.000123', \
+                   INTERVAL 1 MONTH, '\\xFFa\\x5C'::BLOB, MAP {1: 'a'}, MAP {'k': [true]}";
+        let values: Vec<serde_json::Value> =
+            db.query_row(sql, [], |r| (0..10).map(|i| Ok(r.get::<_, Value>(i)?.into())).collect())?;
+        assert_eq!(
+            values,
+            [
+                json!("170141183460469231731687303715884105727"),
+                json!(12.34),
+                json!(null),
+                json!("1969-07-20"),
+                json!("20:17:40.5"),
+                json!("2000-02-29 00:00:01.000123"),
+                json!({"months": 1, "days": 0, "nanos": 0}),
+                json!("\\xFFa\\\\"),
+                json!({"1": "a"}),
+                json!({"k": [true]}),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_json_column() -> Result<()> {
+        use serde_json::json;
+
+        let db = Connection::open_in_memory()?;
+        let v: serde_json::Value = db.query_row(r#"SELECT '{"a":1}'::JSON"#, [], |r| r.get(0))?;
+        assert_eq!(v, json!({"a": 1}));
+
+        db.execute_batch("CREATE TABLE j (v JSON)")?;
+        let data = json!({"list": [1, 2.5, null], "nested": {"s": "x"}});
+        db.execute("INSERT INTO j VALUES (?)", [&data])?;
+        let (v, a): (serde_json::Value, i64) =
+            db.query_row("SELECT v, v->'list'->>0 FROM j", [], |r| Ok((r.get(0)?, r.get(1)?)))?;
+        assert_eq!(v, data);
+        assert_eq!(a, 1);
+        Ok(())
+    }
 }
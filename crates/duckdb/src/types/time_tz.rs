@@ -0,0 +1,208 @@
+//! [`ToSql`] and [`FromSql`] implementation for [`TimeWithOffset`], DuckDB's
+//! `TIMETZ`.
+use std::{fmt, str::FromStr};
+
+use super::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use crate::Result;
+
+/// A `TIMETZ` value: a time of day together with its offset from UTC.
+///
+/// DuckDB can't return `TIMETZ` columns as Arrow, which is how results are
+/// read, so select them as text, e.g. `SELECT t::VARCHAR`. Values are bound as
+/// text, which DuckDB casts to `TIMETZ`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimeWithOffset {
+    /// Microseconds since midnight, in local time. `24:00:00` is allowed.
+    pub micros: i64,
+    /// Offset from UTC in seconds, positive east of Greenwich, within
+    /// ±15:59:59.
+    pub offset_secs: i32,
+}
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+const OFFSET_BITS: u32 = 24;
+const MAX_OFFSET: i32 = 16 * 60 * 60 - 1;
+
+impl TimeWithOffset {
+    /// Unpacks DuckDB's in-memory representation of a `TIMETZ`, as found in
+    /// the vectors of a table function.
+    ///
+    /// The upper 40 bits hold the microseconds, and the lower 24 bits hold
+    /// `15:59:59 - offset` in seconds, so that the packed values sort by time
+    /// and then by offset, with offsets east of UTC last.
+    pub fn from_bits(bits: u64) -> TimeWithOffset {
+        TimeWithOffset {
+            micros: (bits >> OFFSET_BITS) as i64,
+            offset_secs: MAX_OFFSET - (bits & ((1 << OFFSET_BITS) - 1)) as i32,
+        }
+    }
+
+    /// Packs the value in DuckDB's in-memory representation, see
+    /// [`from_bits`](TimeWithOffset::from_bits).
+    pub fn to_bits(&self) -> u64 {
+        ((self.micros as u64) << OFFSET_BITS) | (MAX_OFFSET - self.offset_secs) as u64
+    }
+}
+
+/// Formats the value like DuckDB does, e.g. `12:30:00.5+05:30`.
+impl fmt::Display for TimeWithOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.micros / 1_000_000;
+        write!(f, "{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)?;
+        let frac = self.micros % 1_000_000;
+        if frac != 0 {
+            write!(f, ".{}", format!("{frac:06}").trim_end_matches('0'))?;
+        }
+        let offset = self.offset_secs.unsigned_abs();
+        let sign = if self.offset_secs < 0 { '-' } else { '+' };
+        write!(f, "{sign}{:02}", offset / 3600)?;
+        if !offset.is_multiple_of(3600) {
+            write!(f, ":{:02}", offset / 60 % 60)?;
+        }
+        if !offset.is_multiple_of(60) {
+            write!(f, ":{:02}", offset % 60)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses DuckDB's text format, `HH:MM:SS[.ffffff]±HH[:MM[:SS]]`.
+impl FromStr for TimeWithOffset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<TimeWithOffset, String> {
+        let invalid = || format!("invalid TIMETZ: {s:?}");
+        let split = s.rfind(['+', '-']).ok_or_else(invalid)?;
+        let (time, offset) = s.split_at(split);
+
+        let (hms, frac) = time.split_once('.').unwrap_or((time, ""));
+        let [h, m, sec] = parse_fields(hms, 3).ok_or_else(invalid)?;
+        if frac.len() > 6 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let frac = format!("{frac:0<6}").parse::<i64>().map_err(|_| invalid())?;
+        let micros = ((h * 60 + m) * 60 + sec) * 1_000_000 + frac;
+
+        let [oh, om, os] = parse_fields(&offset[1..], 1).ok_or_else(invalid)?;
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let offset_secs = sign * ((oh * 60 + om) * 60 + os) as i32;
+
+        if !(0..=MICROS_PER_DAY).contains(&micros) || [m, sec, om, os].iter().any(|&v| v > 59) {
+            return Err(invalid());
+        }
+        if !(-MAX_OFFSET..=MAX_OFFSET).contains(&offset_secs) {
+            return Err(invalid());
+        }
+        Ok(TimeWithOffset { micros, offset_secs })
+    }
+}
+
+/// Parses `HH[:MM[:SS]]` with at least `min` fields, defaulting the missing
+/// ones to zero.
+fn parse_fields(s: &str, min: usize) -> Option<[i64; 3]> {
+    let mut fields = [0; 3];
+    let mut count = 0;
+    for part in s.split(':') {
+        if count == 3 || part.len() != 2 || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        fields[count] = part.parse().ok()?;
+        count += 1;
+    }
+    (count >= min).then_some(fields)
+}
+
+impl ToSql for TimeWithOffset {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+/// Parses text, as returned by casting a `TIMETZ` to `VARCHAR`.
+impl FromSql for TimeWithOffset {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Text(_) => value
+                .as_str()?
+                .parse()
+                .map_err(|e: String| FromSqlError::Other(e.into())),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeWithOffset;
+    use crate::{params, Connection, Result};
+
+    #[test]
+    fn test_bits() {
+        let t = TimeWithOffset {
+            micros: 45_000_000_001,
+            offset_secs: -(7 * 3600 + 30 * 60),
+        };
+        assert_eq!(TimeWithOffset::from_bits(t.to_bits()), t);
+        // 12:00:00+00
+        assert_eq!(
+            TimeWithOffset::from_bits((43_200_000_000 << 24) | 57_599),
+            TimeWithOffset {
+                micros: 43_200_000_000,
+                offset_secs: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let parse = |s: &str| s.parse::<TimeWithOffset>();
+        assert_eq!(
+            parse("23:59:59.123456-07:30:15"),
+            Ok(TimeWithOffset {
+                micros: 86_399_123_456,
+                offset_secs: -(7 * 3600 + 30 * 60 + 15),
+            })
+        );
+        assert_eq!(parse("12:00:00.5+05").unwrap().offset_secs, 5 * 3600);
+        assert_eq!(parse("24:00:00-15:59:59").unwrap().micros, 86_400_000_000);
+        for s in [
+            "12:00:00",
+            "12:00+05",
+            "12:60:00+05",
+            "24:00:01+00",
+            "12:00:00+16",
+            "12:00:00.1234567+00",
+        ] {
+            assert!(parse(s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_time_tz_round_trip() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE t (i INTEGER, v TIMETZ)")?;
+        let times = [
+            "00:00:00+00",
+            "12:30:00.5+05:30",
+            "23:59:59.123456-07:30:15",
+            "24:00:00-15:59:59",
+            "01:02:03.000004+15:59:59",
+        ];
+        for (i, s) in times.iter().enumerate() {
+            let t: TimeWithOffset = s.parse().unwrap();
+            assert_eq!(t.to_string(), *s);
+            db.execute("INSERT INTO t VALUES (?, ?)", params![i as i32, t])?;
+        }
+        let mut stmt = db.prepare("SELECT v::VARCHAR FROM t ORDER BY i")?;
+        let read = stmt.iter::<TimeWithOffset>([])?.collect::<Result<Vec<_>>>()?;
+        let expected = times.iter().map(|s| s.parse().unwrap()).collect::<Vec<_>>();
+        assert_eq!(read, expected);
+
+        // Selecting the column itself fails, rather than crashing.
+        let err = db.query_row("SELECT v FROM t", [], |_| Ok(())).unwrap_err();
+        assert!(err.to_string().contains("TIMETZ"), "{err}");
+        Ok(())
+    }
+}
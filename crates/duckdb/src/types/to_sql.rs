@@ -1,5 +1,5 @@
-use super::{Null, TimeUnit, Value, ValueRef};
-use crate::Result;
+use super::{value::list_element_type, Null, TimeUnit, Type, Value, ValueRef};
+use crate::{Error, Result};
 use std::borrow::Cow;
 
 /// `ToSqlOutput` represents the possible output types for implementers of the
@@ -12,6 +12,10 @@ pub enum ToSqlOutput<'a> {
 
     /// An owned SQLite-representable value.
     Owned(Value),
+
+    /// A list of values, bound as a DuckDB `LIST` of the given element type.
+    /// The type is needed so that empty lists can be bound too.
+    List(Type, Vec<Value>),
 }
 
 // Generically allow any type that can be converted into a ValueRef
@@ -65,7 +69,8 @@ impl ToSql for ToSqlOutput<'_> {
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
         Ok(match *self {
             ToSqlOutput::Borrowed(v) => ToSqlOutput::Borrowed(v),
-            ToSqlOutput::Owned(ref v) => ToSqlOutput::Borrowed(ValueRef::from(v)),
+            ToSqlOutput::Owned(ref v) => ToSqlOutput::Owned(v.clone()),
+            ToSqlOutput::List(ref ty, ref items) => ToSqlOutput::List(ty.clone(), items.clone()),
         })
     }
 }
@@ -105,6 +110,13 @@ impl<T: ToSql + ?Sized> ToSql for std::sync::Arc<T> {
     }
 }
 
+impl<T: ToSql> ToSql for std::num::Wrapping<T> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        self.0.to_sql()
+    }
+}
+
 // We should be able to use a generic impl like this:
 //
 // impl<T: Copy> ToSql for T where T: Into<Value> {
@@ -185,10 +197,63 @@ impl ToSql for [u8] {
     }
 }
 
+/// Lists, arrays and structs are built recursively, each element or field
+/// typed by its value, and empty lists as `VARCHAR[]`. The C API can't create
+/// `NULL` values, so they can't be nested in these. DuckDB doesn't know the
+/// type of a parameter when binding the query, so cast nested parameters to
+/// use them, e.g. `SELECT (?::STRUCT(a INTEGER)).a`.
 impl ToSql for Value {
     #[inline]
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
-        Ok(ToSqlOutput::from(self))
+        match self {
+            // lists have no borrowed representation
+            Value::List(items) => Ok(ToSqlOutput::List(list_element_type(items), items.clone())),
+            // structs and arrays are built by value when binding
+            Value::Struct(..) | Value::Array(..) => Ok(ToSqlOutput::Owned(self.clone())),
+            // enum labels are cast to the enum type by DuckDB
+            Value::Enum(label) => Ok(ToSqlOutput::from(label.as_str())),
+            Value::Map(..) | Value::Union(..) => Err(Error::ToSqlConversionFailure(
+                format!("{} values cannot be bound as parameters", self.data_type()).into(),
+            )),
+            _ => Ok(ToSqlOutput::from(self)),
+        }
+    }
+}
+
+fn to_value(output: ToSqlOutput<'_>) -> Value {
+    match output {
+        ToSqlOutput::Borrowed(v) => v.to_owned(),
+        ToSqlOutput::Owned(v) => v,
+        ToSqlOutput::List(_, items) => Value::List(items),
+    }
+}
+
+/// Converts each element of a slice, to be bound as a DuckDB `LIST`. The
+/// element type is taken from `witness`, so that empty lists are typed too.
+fn list_to_sql<'a, T: ToSql>(items: &[T], witness: T) -> Result<ToSqlOutput<'a>> {
+    let ty = match witness.to_sql()? {
+        ToSqlOutput::List(ty, _) => Type::List(Box::new(ty)),
+        output => to_value(output).data_type(),
+    };
+    let items = items
+        .iter()
+        .map(|item| item.to_sql().map(to_value))
+        .collect::<Result<_>>()?;
+    Ok(ToSqlOutput::List(ty, items))
+}
+
+/// Binds a slice as a DuckDB `LIST` parameter, e.g. `[List(&ids)]` for
+/// `WHERE id = ANY(?)`. Slices of `u8` are bound as `BLOB`s, hence the
+/// wrapper rather than an impl for each slice type. Empty lists take the type
+/// of `T::default()`, and the C API can't create `NULL` elements, so binding
+/// a list of `Option<T>` fails if any of them is `None`.
+#[derive(Clone, Copy, Debug)]
+pub struct List<'a, T>(pub &'a [T]);
+
+impl<T: ToSql + Default> ToSql for List<'_, T> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        list_to_sql(self.0, T::default())
     }
 }
 
@@ -202,6 +267,16 @@ impl<T: ToSql> ToSql for Option<T> {
     }
 }
 
+impl ToSql for u128 {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        // HUGEINT is signed, so only values up to `i128::MAX` can be bound.
+        i128::try_from(*self)
+            .map(ToSqlOutput::from)
+            .map_err(|err| Error::ToSqlConversionFailure(err.into()))
+    }
+}
+
 impl ToSql for std::time::Duration {
     fn to_sql(&self) -> crate::Result<ToSqlOutput<'_>> {
         Ok(ToSqlOutput::Owned(Value::Timestamp(
@@ -226,6 +301,29 @@ mod test {
         is_to_sql::<u8>();
         is_to_sql::<u16>();
         is_to_sql::<u32>();
+        is_to_sql::<i128>();
+        is_to_sql::<u128>();
+    }
+
+    #[test]
+    fn test_u128_out_of_range() {
+        assert!((i128::MAX as u128).to_sql().is_ok());
+        assert!(u128::MAX.to_sql().is_err());
+    }
+
+    #[test]
+    fn test_wrapping() -> crate::Result<()> {
+        use crate::Connection;
+        use std::num::Wrapping;
+
+        let db = Connection::open_in_memory()?;
+        let v = Wrapping(i64::MAX) + Wrapping(1);
+        let back: Wrapping<i64> = db.query_row("SELECT ?", [v], |r| r.get(0))?;
+        assert_eq!(back, Wrapping(i64::MIN));
+
+        let back: Wrapping<u8> = db.query_row("SELECT ?", [Wrapping(200u8)], |r| r.get(0))?;
+        assert_eq!(back, Wrapping(200));
+        Ok(())
     }
 
     #[test]
@@ -1,5 +1,6 @@
-use super::{Null, OrderedMap, TimeUnit, Type};
-use rust_decimal::prelude::*;
+use super::{decimal_to_string, Null, OrderedMap, TimeUnit, Type};
+use crate::{Error, Result};
+use std::fmt;
 
 /// Owning [dynamic type value](http://sqlite.org/datatype3.html). Value's type is typically
 /// dictated by DuckDB (not by the caller).
@@ -34,8 +35,18 @@ pub enum Value {
     Float(f32),
     /// The value is a f64.
     Double(f64),
-    /// The value is a Decimal.
-    Decimal(Decimal),
+    /// The value is an exact decimal, stored as its unscaled backing integer.
+    ///
+    /// DuckDB decimals have a width of at most 38 digits, so they always fit in an
+    /// `i128`. Arrow `Decimal256` columns are not supported.
+    Decimal {
+        /// unscaled value, e.g. `12340` for `12.340`
+        value: i128,
+        /// total number of digits
+        width: u8,
+        /// number of digits after the decimal point
+        scale: u8,
+    },
     /// The value is a timestamp.
     Timestamp(TimeUnit, i64),
     /// The value is a text string.
@@ -209,6 +220,184 @@ where
     }
 }
 
+/// Format the value the way DuckDB casts it to `VARCHAR`, e.g. `NULL`,
+/// `[1, 2, 3]`, `{'a': 1}` or `{k=v}` for a map.
+///
+/// Top-level text is written as is. Text inside lists, structs and maps is
+/// quoted with `'`, doubling the quotes it contains, so that items holding
+/// commas or brackets stay readable. Blobs are written as text, with bytes
+/// that aren't printable ASCII as `\xAB` escapes.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Text(s) | Value::Enum(s) => f.write_str(s),
+            value => write_value(f, value),
+        }
+    }
+}
+
+/// Write `value`, quoting it if it's text.
+fn write_nested(f: &mut fmt::Formatter<'_>, value: &Value) -> fmt::Result {
+    match value {
+        Value::Text(s) | Value::Enum(s) => write_quoted(f, s),
+        value => write_value(f, value),
+    }
+}
+
+fn write_quoted(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "'{}'", s.replace('\'', "''"))
+}
+
+fn write_value(f: &mut fmt::Formatter<'_>, value: &Value) -> fmt::Result {
+    match value {
+        Value::Null => f.write_str("NULL"),
+        Value::Boolean(b) => write!(f, "{b}"),
+        Value::TinyInt(i) => write!(f, "{i}"),
+        Value::SmallInt(i) => write!(f, "{i}"),
+        Value::Int(i) => write!(f, "{i}"),
+        Value::BigInt(i) => write!(f, "{i}"),
+        Value::HugeInt(i) => write!(f, "{i}"),
+        Value::UTinyInt(i) => write!(f, "{i}"),
+        Value::USmallInt(i) => write!(f, "{i}"),
+        Value::UInt(i) => write!(f, "{i}"),
+        Value::UBigInt(i) => write!(f, "{i}"),
+        Value::Float(x) => write_float(f, (*x).into()),
+        Value::Double(x) => write_float(f, *x),
+        Value::Decimal { value, scale, .. } => f.write_str(&decimal_to_string(*value, *scale)),
+        Value::Timestamp(unit, t) => {
+            let micros = micros(*unit, *t);
+            let (days, micros) = (micros.div_euclid(MICROS_PER_DAY), micros.rem_euclid(MICROS_PER_DAY));
+            write!(f, "{} {}", format_date(days), format_time(micros))
+        }
+        Value::Text(s) | Value::Enum(s) => f.write_str(s),
+        Value::Blob(b) => f.write_str(&format_blob(b)),
+        Value::Date32(d) => f.write_str(&format_date((*d).into())),
+        Value::Time64(unit, t) => f.write_str(&format_time(micros(*unit, *t))),
+        Value::Interval { months, days, nanos } => write_interval(f, *months, *days, *nanos),
+        Value::List(items) | Value::Array(items) => {
+            f.write_str("[")?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write_nested(f, item)?;
+            }
+            f.write_str("]")
+        }
+        Value::Struct(fields) => {
+            f.write_str("{")?;
+            for (i, (name, value)) in fields.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write_quoted(f, name)?;
+                f.write_str(": ")?;
+                write_nested(f, value)?;
+            }
+            f.write_str("}")
+        }
+        Value::Map(entries) => {
+            f.write_str("{")?;
+            for (i, (key, value)) in entries.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write_nested(f, key)?;
+                f.write_str("=")?;
+                write_nested(f, value)?;
+            }
+            f.write_str("}")
+        }
+        Value::Union(value) => write_nested(f, value),
+    }
+}
+
+fn write_float(f: &mut fmt::Formatter<'_>, x: f64) -> fmt::Result {
+    if x.is_nan() {
+        f.write_str("nan")
+    } else {
+        // `Debug` keeps the `.0` of whole numbers, as DuckDB does
+        write!(f, "{x:?}")
+    }
+}
+
+/// Write an interval like `1 year 2 months 3 days 04:05:06.5`, leaving out
+/// the zero parts.
+fn write_interval(f: &mut fmt::Formatter<'_>, months: i32, days: i32, nanos: i64) -> fmt::Result {
+    let mut parts = Vec::new();
+    let plural = |n: i32, unit: &str| format!("{n} {unit}{}", if n.abs() == 1 { "" } else { "s" });
+    if months / 12 != 0 {
+        parts.push(plural(months / 12, "year"));
+    }
+    if months % 12 != 0 {
+        parts.push(plural(months % 12, "month"));
+    }
+    if days != 0 {
+        parts.push(plural(days, "day"));
+    }
+    let micros = nanos / 1000;
+    if micros != 0 || parts.is_empty() {
+        let sign = if micros < 0 { "-" } else { "" };
+        parts.push(format!("{sign}{}", format_time(micros.abs())));
+    }
+    f.write_str(&parts.join(" "))
+}
+
+pub(crate) const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// Convert a number of `unit` to microseconds, rounding nanoseconds down.
+pub(crate) fn micros(unit: TimeUnit, value: i64) -> i64 {
+    match unit {
+        TimeUnit::Nanosecond => value.div_euclid(1000),
+        unit => unit.to_micros(value),
+    }
+}
+
+/// Format days since the epoch as `YYYY-MM-DD`.
+pub(crate) fn format_date(days: i64) -> String {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Format microseconds since midnight as `HH:MM:SS[.ffffff]`.
+pub(crate) fn format_time(micros: i64) -> String {
+    let secs = micros / 1_000_000;
+    let hms = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    match micros % 1_000_000 {
+        0 => hms,
+        frac => format!("{hms}.{}", format!("{frac:06}").trim_end_matches('0')),
+    }
+}
+
+/// Format a blob the way DuckDB casts it to text: printable ASCII as is, and
+/// other bytes and backslashes as `\xAB` escapes.
+fn format_blob(blob: &[u8]) -> String {
+    blob.iter()
+        .map(|&b| match b {
+            0x20..=0x7e if b != b'\\' => (b as char).to_string(),
+            _ => format!("\\x{b:02X}"),
+        })
+        .collect()
+}
+
+/// The type of the elements of a list or array: that of its first non-`NULL`
+/// element, or `Type::Null` if there is none.
+pub(crate) fn list_element_type(items: &[Value]) -> Type {
+    items
+        .iter()
+        .find(|item| !matches!(item, Value::Null))
+        .map_or(Type::Null, Value::data_type)
+}
+
 impl Value {
     /// Returns DuckDB fundamental datatype.
     #[inline]
@@ -227,15 +416,290 @@ impl Value {
             Value::UBigInt(_) => Type::UBigInt,
             Value::Float(_) => Type::Float,
             Value::Double(_) => Type::Double,
-            Value::Decimal(_) => Type::Decimal,
+            Value::Decimal { .. } => Type::Decimal,
             Value::Timestamp(_, _) => Type::Timestamp,
             Value::Text(_) => Type::Text,
             Value::Blob(_) => Type::Blob,
             Value::Date32(_) => Type::Date32,
             Value::Time64(..) => Type::Time64,
             Value::Interval { .. } => Type::Interval,
-            Value::Union(..) | Value::Struct(..) | Value::List(..) | Value::Array(..) | Value::Map(..) => todo!(),
+            Value::List(ref items) => Type::List(Box::new(list_element_type(items))),
+            Value::Array(ref items) => Type::Array(Box::new(list_element_type(items)), items.len() as u32),
+            Value::Struct(ref fields) => Type::Struct(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.data_type()))
+                    .collect(),
+            ),
+            Value::Map(ref entries) => {
+                let (key, value) = entries
+                    .iter()
+                    .next()
+                    .map_or((Type::Null, Type::Null), |(k, v)| (k.data_type(), v.data_type()));
+                Type::Map(Box::new(key), Box::new(value))
+            }
+            Value::Union(..) => Type::Union,
             Value::Enum(..) => Type::Enum,
         }
     }
+
+    /// Converts the value to `ty`, e.g. to fit a value from a loosely typed
+    /// source into a column.
+    ///
+    /// `NULL` converts to any type, a value converts to its own type as is,
+    /// and the other supported conversions are:
+    ///
+    /// - between integer types, if the value is in the range of `ty`,
+    /// - from integers to `FLOAT` and `DOUBLE`, rounding integers too large to
+    ///   be represented exactly,
+    /// - from `FLOAT` to `DOUBLE`, and from `DOUBLE` to `FLOAT` if the value is
+    ///   represented exactly,
+    /// - from text to the numeric types, by parsing it.
+    ///
+    /// Anything else, including `FLOAT` or `DOUBLE` to an integer, which loses
+    /// the fraction, is an [`Error::InvalidCoercion`]. Use
+    /// [`coerce_to_lossy`](Value::coerce_to_lossy) to allow lossy conversions.
+    ///
+    /// ```rust
+    /// # use duckdb::types::{Type, Value};
+    /// assert_eq!(Value::Int(1).coerce_to(&Type::BigInt).unwrap(), Value::BigInt(1));
+    /// assert_eq!(Value::from("2.5".to_owned()).coerce_to(&Type::Double).unwrap(), Value::Double(2.5));
+    /// assert!(Value::Int(300).coerce_to(&Type::TinyInt).is_err());
+    /// assert!(Value::Double(2.5).coerce_to(&Type::Int).is_err());
+    /// ```
+    pub fn coerce_to(&self, ty: &Type) -> Result<Value> {
+        self.coerce(ty, false)
+    }
+
+    /// Like [`coerce_to`](Value::coerce_to), but also converts `FLOAT` and
+    /// `DOUBLE` to integers, rounding to the nearest integer like DuckDB's
+    /// casts do, and `DOUBLE` to `FLOAT`, rounding to the nearest `FLOAT`.
+    /// Values out of the range of `ty` are still an error.
+    pub fn coerce_to_lossy(&self, ty: &Type) -> Result<Value> {
+        self.coerce(ty, true)
+    }
+
+    fn coerce(&self, ty: &Type, lossy: bool) -> Result<Value> {
+        let fail = |reason: &str| Error::InvalidCoercion(self.data_type(), ty.clone(), reason.to_owned());
+        if *self == Value::Null || self.data_type() == *ty {
+            return Ok(self.clone());
+        }
+        let to_integer = is_integer(ty);
+        if let Some(i) = self.as_i128() {
+            return match *ty {
+                Type::Float => Ok(Value::Float(i as f32)),
+                Type::Double => Ok(Value::Double(i as f64)),
+                _ if to_integer => integer_value(i, ty).ok_or_else(|| fail("out of range")),
+                _ => Err(fail("unsupported conversion")),
+            };
+        }
+        match *self {
+            Value::Float(f) => self.coerce_float(f as f64, ty, lossy),
+            Value::Double(f) => self.coerce_float(f, ty, lossy),
+            Value::Text(ref s) => {
+                let s = s.trim();
+                let value = match *ty {
+                    Type::Float => s.parse().ok().map(Value::Float),
+                    Type::Double => s.parse().ok().map(Value::Double),
+                    _ if to_integer => match s.parse() {
+                        Ok(i) => Some(integer_value(i, ty).ok_or_else(|| fail("out of range"))?),
+                        Err(_) => None,
+                    },
+                    _ => return Err(fail("unsupported conversion")),
+                };
+                value.ok_or_else(|| fail("not a number"))
+            }
+            _ => Err(fail("unsupported conversion")),
+        }
+    }
+
+    fn coerce_float(&self, f: f64, ty: &Type, lossy: bool) -> Result<Value> {
+        let fail = |reason: &str| Error::InvalidCoercion(self.data_type(), ty.clone(), reason.to_owned());
+        match *ty {
+            Type::Double => Ok(Value::Double(f)),
+            Type::Float if f.is_finite() && (f as f32).is_infinite() => Err(fail("out of range")),
+            Type::Float if lossy || f.is_nan() || f as f32 as f64 == f => Ok(Value::Float(f as f32)),
+            Type::Float => Err(fail("not exactly representable")),
+            _ if is_integer(ty) && !lossy => Err(fail("lossy conversion")),
+            _ if is_integer(ty) => {
+                let f = f.round();
+                // `i128::MAX as f64` rounds up to 2^127
+                let i = (f >= i128::MIN as f64 && f < i128::MAX as f64).then_some(f as i128);
+                i.and_then(|i| integer_value(i, ty)).ok_or_else(|| fail("out of range"))
+            }
+            _ => Err(fail("unsupported conversion")),
+        }
+    }
+
+    fn as_i128(&self) -> Option<i128> {
+        match *self {
+            Value::TinyInt(i) => Some(i.into()),
+            Value::SmallInt(i) => Some(i.into()),
+            Value::Int(i) => Some(i.into()),
+            Value::BigInt(i) => Some(i.into()),
+            Value::HugeInt(i) => Some(i),
+            Value::UTinyInt(i) => Some(i.into()),
+            Value::USmallInt(i) => Some(i.into()),
+            Value::UInt(i) => Some(i.into()),
+            Value::UBigInt(i) => Some(i.into()),
+            _ => None,
+        }
+    }
+}
+
+fn is_integer(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::TinyInt
+            | Type::SmallInt
+            | Type::Int
+            | Type::BigInt
+            | Type::HugeInt
+            | Type::UTinyInt
+            | Type::USmallInt
+            | Type::UInt
+            | Type::UBigInt
+    )
+}
+
+/// Returns `i` as an integer value of type `ty`, or `None` if it's out of
+/// range.
+fn integer_value(i: i128, ty: &Type) -> Option<Value> {
+    match *ty {
+        Type::TinyInt => i.try_into().ok().map(Value::TinyInt),
+        Type::SmallInt => i.try_into().ok().map(Value::SmallInt),
+        Type::Int => i.try_into().ok().map(Value::Int),
+        Type::BigInt => i.try_into().ok().map(Value::BigInt),
+        Type::HugeInt => Some(Value::HugeInt(i)),
+        Type::UTinyInt => i.try_into().ok().map(Value::UTinyInt),
+        Type::USmallInt => i.try_into().ok().map(Value::USmallInt),
+        Type::UInt => i.try_into().ok().map(Value::UInt),
+        Type::UBigInt => i.try_into().ok().map(Value::UBigInt),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Value;
+    use crate::{types::Type, Error};
+
+    #[test]
+    fn test_list_data_type() {
+        let list = Value::List(vec![Value::Null, Value::Int(1)]);
+        assert_eq!(list.data_type(), Type::List(Box::new(Type::Int)));
+        let array = Value::Array(vec![Value::Null, Value::Text("a".into())]);
+        assert_eq!(array.data_type(), Type::Array(Box::new(Type::Text), 2));
+        assert_eq!(Value::List(vec![]).data_type(), Type::List(Box::new(Type::Null)));
+        assert_eq!(
+            Value::List(vec![Value::Null]).data_type(),
+            Type::List(Box::new(Type::Null))
+        );
+    }
+
+    #[test]
+    fn test_coerce_to() {
+        assert_eq!(Value::TinyInt(-1).coerce_to(&Type::HugeInt), Ok(Value::HugeInt(-1)));
+        assert_eq!(Value::UBigInt(7).coerce_to(&Type::UTinyInt), Ok(Value::UTinyInt(7)));
+        assert_eq!(Value::Int(3).coerce_to(&Type::Double), Ok(Value::Double(3.0)));
+        assert_eq!(Value::Float(0.5).coerce_to(&Type::Double), Ok(Value::Double(0.5)));
+        assert_eq!(Value::Double(0.5).coerce_to(&Type::Float), Ok(Value::Float(0.5)));
+        assert_eq!(
+            Value::Text(" 42 ".into()).coerce_to(&Type::SmallInt),
+            Ok(Value::SmallInt(42))
+        );
+        assert_eq!(
+            Value::Text("1e3".into()).coerce_to(&Type::Float),
+            Ok(Value::Float(1000.0))
+        );
+        assert_eq!(Value::Null.coerce_to(&Type::Int), Ok(Value::Null));
+        assert_eq!(
+            Value::Text("x".into()).coerce_to(&Type::Text),
+            Ok(Value::Text("x".into()))
+        );
+
+        let err = Value::Int(-1).coerce_to(&Type::UInt).unwrap_err();
+        assert_eq!(
+            err,
+            Error::InvalidCoercion(Type::Int, Type::UInt, "out of range".to_owned())
+        );
+        assert_eq!(err.to_string(), "Cannot coerce Int value to UInt: out of range");
+        for (value, ty) in [
+            (Value::Text("1.5".into()), Type::Int),
+            (Value::Text("300".into()), Type::TinyInt),
+            (Value::Text("1".into()), Type::Boolean),
+            (Value::Boolean(true), Type::Int),
+            (Value::Int(1), Type::Text),
+            (Value::Double(0.1), Type::Float),
+            (Value::Double(1.0), Type::BigInt),
+        ] {
+            assert!(value.coerce_to(&ty).is_err(), "{value:?} to {ty}");
+        }
+    }
+
+    #[test]
+    fn test_coerce_to_lossy() {
+        assert_eq!(Value::Double(2.5).coerce_to_lossy(&Type::Int), Ok(Value::Int(3)));
+        assert_eq!(Value::Float(-1.4).coerce_to_lossy(&Type::BigInt), Ok(Value::BigInt(-1)));
+        assert_eq!(Value::Double(0.1).coerce_to_lossy(&Type::Float), Ok(Value::Float(0.1)));
+        assert!(Value::Double(256.0).coerce_to_lossy(&Type::UTinyInt).is_err());
+        assert!(Value::Double(f64::NAN).coerce_to_lossy(&Type::Int).is_err());
+        assert!(Value::Double(1e300).coerce_to_lossy(&Type::Float).is_err());
+        assert!(Value::Double(1e300).coerce_to_lossy(&Type::HugeInt).is_err());
+    }
+
+    #[test]
+    fn test_display_matches_duckdb() -> crate::Result<()> {
+        let db = crate::Connection::open_in_memory()?;
+        // Values without nested text print exactly as DuckDB casts them
+        let exprs = [
+            "NULL::INTEGER",
+            "true",
+            "-42::TINYINT",
+            "170141183460469231731687303715884105727::HUGEINT",
+            "12.340::DECIMAL(6, 3)",
+            "-0.05::DECIMAL(4, 2)",
+            "1.5::DOUBLE",
+            "2.0::DOUBLE",
+            "DATE '1969-07-20'",
+            "TIME '20:17:40.5'",
+            "TIMESTAMP '2000-02-29 00:00:01.000123'",
+            "TIMESTAMP '1960-01-01 12:00:00'",
+            "INTERVAL 14 MONTH + INTERVAL 3 DAY + INTERVAL 4 HOUR + INTERVAL 500 MILLISECOND",
+            "INTERVAL 1 DAY",
+            "-INTERVAL 90 SECOND",
+            "INTERVAL 0 SECOND",
+            "'\\xFFa\\x5C'::BLOB",
+            "[1, 2, NULL]",
+            "[[1], []]",
+            "{'a': 1, 'b': [true]}",
+            "MAP {1: 2.5, 3: NULL}",
+        ];
+        for expr in exprs {
+            let (value, text): (Value, String) =
+                db.query_row(&format!("SELECT {expr}, ({expr})::VARCHAR"), [], |r| {
+                    Ok((
+                        r.get(0)?,
+                        r.get::<_, Option<String>>(1)?.unwrap_or_else(|| "NULL".into()),
+                    ))
+                })?;
+            assert_eq!(value.to_string(), text, "{expr}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_quotes_nested_text() {
+        let text = |s: &str| Value::Text(s.into());
+        assert_eq!(text("it's, [x]").to_string(), "it's, [x]");
+        assert_eq!(
+            Value::List(vec![text("a, b"), text("it's"), Value::Null]).to_string(),
+            "['a, b', 'it''s', NULL]"
+        );
+        let fields = vec![("k'ey".to_owned(), Value::Enum("on".into()))];
+        assert_eq!(Value::Struct(fields.into()).to_string(), "{'k''ey': 'on'}");
+        let entries = vec![(text("x"), Value::Union(Box::new(text("y"))))];
+        assert_eq!(Value::Map(entries.into()).to_string(), "{'x'='y'}");
+        assert_eq!(Value::Double(f64::NAN).to_string(), "nan");
+    }
 }
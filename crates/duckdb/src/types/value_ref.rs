@@ -2,7 +2,6 @@ use super::{Type, Value};
 use crate::types::{FromSqlError, FromSqlResult, OrderedMap};
 
 use crate::Row;
-use rust_decimal::prelude::*;
 
 use arrow::{
     array::{
@@ -70,8 +69,18 @@ pub enum ValueRef<'a> {
     Float(f32),
     /// The value is a f64.
     Double(f64),
-    /// The value is a decimal
-    Decimal(Decimal),
+    /// The value is an exact decimal, stored as its unscaled backing integer.
+    ///
+    /// DuckDB decimals have a width of at most 38 digits, so they always fit in an
+    /// `i128`. Arrow `Decimal256` columns are not supported.
+    Decimal {
+        /// unscaled value, e.g. `12340` for `12.340`
+        value: i128,
+        /// total number of digits
+        width: u8,
+        /// number of digits after the decimal point
+        scale: u8,
+    },
     /// The value is a timestamp.
     Timestamp(TimeUnit, i64),
     /// The value is a text string.
@@ -125,11 +134,49 @@ pub enum EnumType<'a> {
     UInt32(&'a DictionaryArray<UInt32Type>),
 }
 
+impl<'a> EnumType<'a> {
+    /// Returns the dictionary index of the enum value at `row`, i.e. the
+    /// position of its label in the enum's definition, whatever the key width.
+    pub fn index(&self, row: usize) -> u32 {
+        match *self {
+            EnumType::UInt8(array) => array.keys().value(row).into(),
+            EnumType::UInt16(array) => array.keys().value(row).into(),
+            EnumType::UInt32(array) => array.keys().value(row),
+        }
+    }
+
+    /// Returns the label of the enum value at `row`.
+    pub fn label(&self, row: usize) -> FromSqlResult<&'a str> {
+        let values = match *self {
+            EnumType::UInt8(array) => array.values(),
+            EnumType::UInt16(array) => array.values(),
+            EnumType::UInt32(array) => array.values(),
+        };
+        Row::value_ref_internal(self.index(row) as usize, 0, values).as_str()
+    }
+}
+
 impl ValueRef<'_> {
     /// Returns DuckDB fundamental datatype.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a nested value has an Arrow type with no DuckDB counterpart,
+    /// see [`try_data_type`](ValueRef::try_data_type).
     #[inline]
     pub fn data_type(&self) -> Type {
-        match *self {
+        match self.try_data_type() {
+            Ok(t) => t,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Returns DuckDB fundamental datatype, or
+    /// [`Error::UnsupportedArrowType`](crate::Error::UnsupportedArrowType) if
+    /// the underlying Arrow type cannot be represented.
+    #[inline]
+    pub fn try_data_type(&self) -> crate::Result<Type> {
+        Ok(match *self {
             ValueRef::Null => Type::Null,
             ValueRef::Boolean(_) => Type::Boolean,
             ValueRef::TinyInt(_) => Type::TinyInt,
@@ -143,23 +190,23 @@ impl ValueRef<'_> {
             ValueRef::UBigInt(_) => Type::UBigInt,
             ValueRef::Float(_) => Type::Float,
             ValueRef::Double(_) => Type::Double,
-            ValueRef::Decimal(_) => Type::Decimal,
+            ValueRef::Decimal { .. } => Type::Decimal,
             ValueRef::Timestamp(..) => Type::Timestamp,
             ValueRef::Text(_) => Type::Text,
             ValueRef::Blob(_) => Type::Blob,
             ValueRef::Date32(_) => Type::Date32,
             ValueRef::Time64(..) => Type::Time64,
             ValueRef::Interval { .. } => Type::Interval,
-            ValueRef::Struct(arr, _) => arr.data_type().into(),
-            ValueRef::Map(arr, _) => arr.data_type().into(),
-            ValueRef::Array(arr, _) => arr.data_type().into(),
+            ValueRef::Struct(arr, _) => Type::try_from_arrow(arr.data_type())?,
+            ValueRef::Map(arr, _) => Type::try_from_arrow(arr.data_type())?,
+            ValueRef::Array(arr, _) => Type::try_from_arrow(arr.data_type())?,
             ValueRef::List(arr, _) => match arr {
-                ListType::Large(arr) => arr.data_type().into(),
-                ListType::Regular(arr) => arr.data_type().into(),
+                ListType::Large(arr) => Type::try_from_arrow(arr.data_type())?,
+                ListType::Regular(arr) => Type::try_from_arrow(arr.data_type())?,
             },
             ValueRef::Enum(..) => Type::Enum,
-            ValueRef::Union(arr, _) => arr.data_type().into(),
-        }
+            ValueRef::Union(arr, _) => Type::try_from_arrow(arr.data_type())?,
+        })
     }
 
     /// Returns an owned version of this ValueRef
@@ -169,12 +216,42 @@ impl ValueRef<'_> {
 }
 
 impl<'a> ValueRef<'a> {
-    /// If `self` is case `Text`, returns the string value. Otherwise, returns
+    /// If `self` is case `Text`, returns the string value, or if it is case
+    /// `Enum`, its label. Otherwise, returns
     /// [`Err(Error::InvalidColumnType)`](crate::Error::InvalidColumnType).
+    ///
+    /// The string borrows from the result chunk rather than being copied, so
+    /// it stays valid for as long as the row it was read from, and scanning
+    /// text columns with it doesn't allocate. `NULL` is an error too, so match
+    /// [`ValueRef::Null`] first for nullable columns.
     #[inline]
     pub fn as_str(&self) -> FromSqlResult<&'a str> {
         match *self {
             ValueRef::Text(t) => std::str::from_utf8(t).map_err(|e| FromSqlError::Other(Box::new(e))),
+            ValueRef::Enum(items, idx) => items.label(idx),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+
+    /// If `self` is case `Enum`, returns the dictionary index of its label,
+    /// i.e. its position in the enum's definition. Otherwise, returns `None`.
+    #[inline]
+    pub fn enum_index(&self) -> Option<u32> {
+        match *self {
+            ValueRef::Enum(items, idx) => Some(items.index(idx)),
+            _ => None,
+        }
+    }
+
+    /// If `self` is case `Text` or `Blob`, returns the bytes, without
+    /// checking that text is valid UTF-8. Like [`as_str`](ValueRef::as_str),
+    /// this borrows from the result chunk. Otherwise, returns
+    /// [`Err(Error::InvalidColumnType)`](crate::Error::InvalidColumnType).
+    #[inline]
+    pub fn as_bytes(&self) -> FromSqlResult<&'a [u8]> {
+        match *self {
+            ValueRef::Text(t) => Ok(t),
+            ValueRef::Blob(b) => Ok(b),
             _ => Err(FromSqlError::InvalidType),
         }
     }
@@ -208,7 +285,7 @@ impl From<ValueRef<'_>> for Value {
             ValueRef::UBigInt(i) => Value::UBigInt(i),
             ValueRef::Float(i) => Value::Float(i),
             ValueRef::Double(i) => Value::Double(i),
-            ValueRef::Decimal(i) => Value::Decimal(i),
+            ValueRef::Decimal { value, width, scale } => Value::Decimal { value, width, scale },
             ValueRef::Timestamp(tu, t) => Value::Timestamp(tu, t),
             ValueRef::Text(s) => {
                 let s = std::str::from_utf8(s).expect("invalid UTF-8");
@@ -238,24 +315,7 @@ impl From<ValueRef<'_>> for Value {
                     )
                 }
             },
-            ValueRef::Enum(items, idx) => {
-                let value = Row::value_ref_internal(
-                    idx,
-                    0,
-                    match items {
-                        EnumType::UInt8(res) => res.values(),
-                        EnumType::UInt16(res) => res.values(),
-                        EnumType::UInt32(res) => res.values(),
-                    },
-                )
-                .to_owned();
-
-                if let Value::Text(s) = value {
-                    Value::Enum(s)
-                } else {
-                    panic!("Enum value is not a string")
-                }
-            }
+            ValueRef::Enum(items, idx) => Value::Enum(items.label(idx).expect("Enum value is not a string").to_owned()),
             ValueRef::Struct(items, idx) => {
                 let value: Vec<(String, Value)> = items
                     .columns()
@@ -343,7 +403,7 @@ impl<'a> From<&'a Value> for ValueRef<'a> {
             Value::UBigInt(i) => ValueRef::UBigInt(i),
             Value::Float(i) => ValueRef::Float(i),
             Value::Double(i) => ValueRef::Double(i),
-            Value::Decimal(i) => ValueRef::Decimal(i),
+            Value::Decimal { value, width, scale } => ValueRef::Decimal { value, width, scale },
             Value::Timestamp(tu, t) => ValueRef::Timestamp(tu, t),
             Value::Text(ref s) => ValueRef::Text(s.as_bytes()),
             Value::Blob(ref b) => ValueRef::Blob(b),
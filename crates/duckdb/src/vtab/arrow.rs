@@ -1,20 +1,33 @@
 use super::{
+    bind, func, init,
     vector::{ArrayVector, FlatVector, ListVector, Vector},
-    BindInfo, DataChunk, Free, FunctionInfo, InitInfo, LogicalType, LogicalTypeId, StructVector, VTab,
+    BindInfo, DataChunk, Free, FunctionInfo, InitInfo, LogicalType, LogicalTypeId, StructVector, TableFunction,
+    TableFunctionCall, VTab,
 };
-use std::ptr::null_mut;
+use std::{
+    collections::HashMap,
+    ffi::c_void,
+    ptr::null_mut,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::{ffi::duckdb_vector_size, Connection, Error};
 
 use crate::vtab::vector::Inserter;
 use arrow::array::{
     as_boolean_array, as_generic_binary_array, as_large_list_array, as_list_array, as_primitive_array, as_string_array,
-    as_struct_array, Array, ArrayData, AsArray, BinaryArray, BooleanArray, Decimal128Array, FixedSizeListArray,
-    GenericListArray, OffsetSizeTrait, PrimitiveArray, StringArray, StructArray,
+    as_struct_array, Array, ArrayData, ArrayRef, AsArray, BinaryArray, BooleanArray, Decimal128Array,
+    FixedSizeListArray, GenericListArray, OffsetSizeTrait, PrimitiveArray, StringArray, StructArray,
 };
+use arrow::buffer::{BooleanBuffer, NullBuffer, ScalarBuffer};
 
 use arrow::{
     datatypes::*,
     ffi::{from_ffi, FFI_ArrowArray, FFI_ArrowSchema},
-    record_batch::RecordBatch,
+    record_batch::{RecordBatch, RecordBatchReader},
 };
 
 use num::{cast::AsPrimitive, ToPrimitive};
@@ -127,6 +140,346 @@ impl VTab for ArrowVTab {
     }
 }
 
+/// A dataset registered with [`Connection::attach_arrow_dataset`].
+struct ArrowDataset {
+    schema: SchemaRef,
+    batches: Box<dyn Fn() -> Box<dyn Iterator<Item = RecordBatch> + Send> + Send + Sync>,
+}
+
+unsafe extern "C" fn drop_arrow_dataset(dataset: *mut c_void) {
+    drop(Box::from_raw(dataset.cast::<ArrowDataset>()));
+}
+
+/// A scan of an [`ArrowDataset`]: the batches left, and the rows of the
+/// current batch that haven't been emitted yet.
+struct ArrowDatasetScan {
+    schema: SchemaRef,
+    batches: Box<dyn Iterator<Item = RecordBatch> + Send>,
+    current: Option<RecordBatch>,
+    offset: usize,
+}
+
+impl ArrowDatasetScan {
+    fn new(dataset: &ArrowDataset) -> Self {
+        ArrowDatasetScan {
+            schema: dataset.schema.clone(),
+            batches: (dataset.batches)(),
+            current: None,
+            offset: 0,
+        }
+    }
+}
+
+/// Bind data of [`ArrowDatasetVTab`]; the dataset itself is the extra info
+/// of the function.
+#[repr(C)]
+struct ArrowDatasetBindData;
+
+impl Free for ArrowDatasetBindData {}
+
+/// Init data of [`ArrowDatasetVTab`].
+#[repr(C)]
+struct ArrowDatasetInitData {
+    scan: *mut ArrowDatasetScan,
+}
+
+impl Free for ArrowDatasetInitData {
+    fn free(&mut self) {
+        unsafe {
+            if self.scan.is_null() {
+                return;
+            }
+            drop(Box::from_raw(self.scan));
+        }
+    }
+}
+
+/// The table function scanning an [`ArrowDataset`].
+struct ArrowDatasetVTab;
+
+impl VTab for ArrowDatasetVTab {
+    type BindData = ArrowDatasetBindData;
+    type InitData = ArrowDatasetInitData;
+
+    unsafe fn bind(bind: &BindInfo, _: *mut ArrowDatasetBindData) -> Result<(), Box<dyn std::error::Error>> {
+        let dataset = &*bind.get_extra_info::<ArrowDataset>();
+        for field in dataset.schema.fields() {
+            bind.add_result_column(field.name(), to_duckdb_logical_type(field.data_type())?);
+        }
+        Ok(())
+    }
+
+    unsafe fn init(init: &InitInfo, data: *mut ArrowDatasetInitData) -> Result<(), Box<dyn std::error::Error>> {
+        (*data).scan = null_mut();
+        let dataset = &*init.get_extra_info::<ArrowDataset>();
+        (*data).scan = Box::into_raw(Box::new(ArrowDatasetScan::new(dataset)));
+        Ok(())
+    }
+
+    unsafe fn func(func: &FunctionInfo, output: &mut DataChunk) -> Result<(), Box<dyn std::error::Error>> {
+        let scan = &mut *(*func.get_init_data::<ArrowDatasetInitData>()).scan;
+        loop {
+            if let Some(batch) = &scan.current {
+                if scan.offset < batch.num_rows() {
+                    let len = (batch.num_rows() - scan.offset).min(duckdb_vector_size() as usize);
+                    record_batch_to_duckdb_data_chunk(&batch.slice(scan.offset, len), output)?;
+                    scan.offset += len;
+                    return Ok(());
+                }
+            }
+            let Some(batch) = scan.batches.next() else {
+                output.set_len(0);
+                return Ok(());
+            };
+            let matches = batch.num_columns() == scan.schema.fields().len()
+                && (batch.schema().fields().iter())
+                    .zip(scan.schema.fields())
+                    .all(|(a, b)| a.data_type() == b.data_type());
+            if !matches {
+                return Err(format!(
+                    "record batch schema {} does not match the dataset schema {}",
+                    batch.schema(),
+                    scan.schema
+                )
+                .into());
+            }
+            scan.current = Some(batch);
+            scan.offset = 0;
+        }
+    }
+}
+
+impl Connection {
+    /// Attaches a dataset of Arrow record batches as a table named `name`.
+    ///
+    /// `batches` is called at the start of every scan of the table, and the
+    /// batches it returns are pulled one at a time as DuckDB needs them, so
+    /// they can be produced lazily. Batches may be of any size, and must all
+    /// have the column types of `schema`; scanning a batch that doesn't is an
+    /// error.
+    ///
+    /// The table can be queried as `name` or `name()`. DuckDB filters the rows
+    /// after they have been scanned, as the C API doesn't hand filters to
+    /// table functions, so every batch is read.
+    ///
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use duckdb::{arrow::{array::Int32Array, datatypes::{DataType, Field, Schema}, record_batch::RecordBatch}, Connection, Result};
+    /// fn attach(conn: &Connection) -> Result<()> {
+    ///     let schema = Arc::new(Schema::new(vec![Field::new("n", DataType::Int32, false)]));
+    ///     let batch_schema = schema.clone();
+    ///     conn.attach_arrow_dataset("numbers", schema, move || {
+    ///         let schema = batch_schema.clone();
+    ///         (0..10).map(move |i| {
+    ///             let array = Int32Array::from_iter_values(i * 1000..(i + 1) * 1000);
+    ///             RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap()
+    ///         })
+    ///     })?;
+    ///     let sum: i64 = conn.query_row("SELECT sum(n) FROM numbers", [], |row| row.get(0))?;
+    ///     assert_eq!(sum, 49_995_000);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn attach_arrow_dataset<F, I>(&self, name: &str, schema: SchemaRef, batches: F) -> crate::Result<()>
+    where
+        F: Fn() -> I + Send + Sync + 'static,
+        I: IntoIterator<Item = RecordBatch>,
+        I::IntoIter: Send + 'static,
+    {
+        let dataset = ArrowDataset {
+            schema,
+            batches: Box::new(move || Box::new(batches().into_iter())),
+        };
+        let table_function = TableFunction::default();
+        table_function
+            .set_name(name)
+            .set_bind(Some(bind::<ArrowDatasetVTab>))
+            .set_init(Some(init::<ArrowDatasetVTab>))
+            .set_function(Some(func::<ArrowDatasetVTab>));
+        unsafe {
+            table_function.set_extra_info(Box::into_raw(Box::new(dataset)).cast(), Some(drop_arrow_dataset));
+        }
+        self.db.borrow_mut().register_table_function(table_function)?;
+        self.register_replacement_scan(name, name, &[])
+    }
+
+    /// Registers `batch` as a table named `name`, which can be queried until
+    /// it's [`unregister`](Connection::unregister)ed. The batch isn't
+    /// copied, its rows are read by every scan of the table.
+    ///
+    /// Registering a name again replaces its table. A table or view named
+    /// `name` in the database takes precedence. The tables are shared by the
+    /// connections cloned with [`try_clone`](Connection::try_clone), which
+    /// can all query and unregister them, and the batches are kept until
+    /// they're unregistered or the database is closed, even once this
+    /// connection is dropped.
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{arrow::record_batch::RecordBatch, Connection, Result};
+    /// fn count_orders(conn: &Connection, orders: &RecordBatch) -> Result<i64> {
+    ///     conn.register_arrow("orders", orders)?;
+    ///     let count = conn.query_row("SELECT count(*) FROM orders WHERE amount > 100", [], |row| row.get(0));
+    ///     conn.unregister("orders");
+    ///     count
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if a column has a type that isn't supported.
+    pub fn register_arrow(&self, name: &str, batch: &RecordBatch) -> crate::Result<()> {
+        self.register_arrow_batches(name, batch.schema(), vec![batch.clone()])
+    }
+
+    /// Like [`register_arrow`](Connection::register_arrow), but registers the
+    /// batches read from `reader` as a table.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `reader` fails, or if a column has a type that
+    /// isn't supported.
+    pub fn register_arrow_reader<R: RecordBatchReader>(&self, name: &str, reader: R) -> crate::Result<()> {
+        let schema = reader.schema();
+        let batches = reader.collect::<Result<Vec<_>, _>>()?;
+        self.register_arrow_batches(name, schema, batches)
+    }
+
+    /// Unregisters the table `name` registered with
+    /// [`register_arrow`](Connection::register_arrow) on this connection or
+    /// one it shares its tables with, returning whether there was one.
+    /// Querying it afterwards fails as for any missing table, but statements
+    /// prepared before keep reading it.
+    pub fn unregister(&self, name: &str) -> bool {
+        let tables = self.db.borrow().arrow_tables.clone();
+        let removed = tables.tables.lock().unwrap().remove(&name.to_ascii_lowercase());
+        removed.is_some()
+    }
+
+    fn register_arrow_batches(&self, name: &str, schema: SchemaRef, batches: Vec<RecordBatch>) -> crate::Result<()> {
+        for field in schema.fields() {
+            to_duckdb_logical_type(field.data_type())
+                .map_err(|_| Error::ArrowTypeToDuckdbType(field.to_string(), field.data_type().clone()))?;
+        }
+        let batches = Arc::new(batches);
+        let dataset = ArrowDataset {
+            schema,
+            batches: Box::new(move || Box::new(Vec::clone(&batches).into_iter())),
+        };
+        let tables = self.arrow_tables()?;
+        let mut tables = tables.tables.lock().unwrap();
+        tables.insert(name.to_ascii_lowercase(), Arc::new(dataset));
+        Ok(())
+    }
+
+    /// The Arrow tables of the connection, registering the table function and
+    /// the replacement scan reading them the first time.
+    fn arrow_tables(&self) -> crate::Result<Arc<ArrowTables>> {
+        let tables = self.db.borrow().arrow_tables.clone();
+        let mut function_name = tables.function_name.lock().unwrap();
+        if function_name.is_some() {
+            return Ok(tables.clone());
+        }
+        // DuckDB fails to register a function whose name is taken, e.g. by
+        // the tables of a connection opened from the same raw database.
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let name = format!("arrow_table_{}", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+        let table_function = TableFunction::default();
+        table_function
+            .set_name(&name)
+            .add_parameter(&LogicalType::new(LogicalTypeId::Varchar))
+            .set_bind(Some(bind::<ArrowTableVTab>))
+            .set_init(Some(init::<ArrowTableVTab>))
+            .set_function(Some(func::<ArrowTableVTab>));
+        unsafe {
+            table_function.set_extra_info(Arc::into_raw(tables.clone()) as *mut c_void, Some(drop_arrow_tables));
+        }
+        self.db.borrow_mut().register_table_function(table_function)?;
+
+        let scan_tables = tables.clone();
+        let scan_function_name = name.clone();
+        self.register_replacement_scan_with(move |name| {
+            scan_tables.get(name).map(|_| TableFunctionCall {
+                function_name: scan_function_name.clone(),
+                parameters: vec![crate::types::Value::Text(name.to_owned())],
+            })
+        });
+        *function_name = Some(name);
+        drop(function_name);
+        Ok(tables)
+    }
+}
+
+/// The tables registered with [`Connection::register_arrow`] by lowercase
+/// name, shared by a connection and its clones, and the name of the table
+/// function reading them once it's registered.
+#[derive(Default)]
+pub(crate) struct ArrowTables {
+    function_name: Mutex<Option<String>>,
+    tables: Mutex<HashMap<String, Arc<ArrowDataset>>>,
+}
+
+impl ArrowTables {
+    fn get(&self, name: &str) -> Option<Arc<ArrowDataset>> {
+        self.tables.lock().unwrap().get(&name.to_ascii_lowercase()).cloned()
+    }
+}
+
+unsafe extern "C" fn drop_arrow_tables(tables: *mut c_void) {
+    drop(Arc::from_raw(tables.cast::<ArrowTables>()));
+}
+
+/// Bind data of [`ArrowTableVTab`]: the table that's read, which statements
+/// keep once bound even if it's unregistered.
+#[repr(C)]
+struct ArrowTableBindData {
+    table: *mut Arc<ArrowDataset>,
+}
+
+impl Free for ArrowTableBindData {
+    fn free(&mut self) {
+        unsafe {
+            if self.table.is_null() {
+                return;
+            }
+            drop(Box::from_raw(self.table));
+        }
+    }
+}
+
+/// The table function reading the [`ArrowTables`] of a connection, called
+/// with the name of a table.
+struct ArrowTableVTab;
+
+impl VTab for ArrowTableVTab {
+    type BindData = ArrowTableBindData;
+    type InitData = ArrowDatasetInitData;
+
+    unsafe fn bind(bind: &BindInfo, data: *mut ArrowTableBindData) -> Result<(), Box<dyn std::error::Error>> {
+        (*data).table = null_mut();
+        let tables = &*bind.get_extra_info::<ArrowTables>();
+        let name = bind.get_parameter(0).to_string();
+        let table = tables
+            .get(&name)
+            .ok_or_else(|| format!("Arrow table {name} is not registered"))?;
+        for field in table.schema.fields() {
+            bind.add_result_column(field.name(), to_duckdb_logical_type(field.data_type())?);
+        }
+        (*data).table = Box::into_raw(Box::new(table));
+        Ok(())
+    }
+
+    unsafe fn init(init: &InitInfo, data: *mut ArrowDatasetInitData) -> Result<(), Box<dyn std::error::Error>> {
+        (*data).scan = null_mut();
+        let table = &*(*init.get_bind_data::<ArrowTableBindData>()).table;
+        (*data).scan = Box::into_raw(Box::new(ArrowDatasetScan::new(table)));
+        Ok(())
+    }
+
+    unsafe fn func(func: &FunctionInfo, output: &mut DataChunk) -> Result<(), Box<dyn std::error::Error>> {
+        ArrowDatasetVTab::func(func, output)
+    }
+}
+
 /// Convert arrow DataType to duckdb type id
 pub fn to_duckdb_type_id(data_type: &DataType) -> Result<LogicalTypeId, Box<dyn std::error::Error>> {
     use LogicalTypeId::*;
@@ -197,9 +550,12 @@ pub fn to_duckdb_logical_type(data_type: &DataType) -> Result<LogicalType, Box<d
             // DuckDB does not support negative decimal scales
             Ok(LogicalType::decimal(*width, (*scale).try_into().unwrap()))
         }
-        DataType::Boolean | DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary | DataType::LargeBinary => {
-            Ok(LogicalType::new(to_duckdb_type_id(data_type)?))
-        }
+        DataType::Boolean
+        | DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Binary
+        | DataType::LargeBinary
+        | DataType::FixedSizeBinary(_) => Ok(LogicalType::new(to_duckdb_type_id(data_type)?)),
         dtype if dtype.is_primitive() => Ok(LogicalType::new(to_duckdb_type_id(data_type)?)),
         _ => Err(format!(
             "Unsupported data type: {data_type}, please file an issue https://github.com/wangfenjin/duckdb-rs"
@@ -208,8 +564,91 @@ pub fn to_duckdb_logical_type(data_type: &DataType) -> Result<LogicalType, Box<d
     }
 }
 
+impl DataChunk {
+    /// Creates a [DataChunk] holding the rows of `batch`, with the DuckDB
+    /// types corresponding to its columns, see
+    /// [`record_batch_to_duckdb_data_chunk`].
+    ///
+    /// Returns an error if a column has a type that isn't supported, or if
+    /// `batch` has more rows than fit in a chunk (the vector size, usually
+    /// 2048).
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<DataChunk, Box<dyn std::error::Error>> {
+        let logical_types = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| to_duckdb_logical_type(field.data_type()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut chunk = DataChunk::new(&logical_types);
+        record_batch_to_duckdb_data_chunk(batch, &mut chunk)?;
+        Ok(chunk)
+    }
+}
+
+impl FlatVector {
+    /// Copies the first `len` rows of the vector into an Arrow array, with the
+    /// Arrow type DuckDB uses for the column in [`query_arrow`](crate::Statement::query_arrow).
+    /// Null rows are null in the array.
+    ///
+    /// Booleans, integers, floating point numbers, `DATE`, `TIME`, `TIMESTAMP`
+    /// (in any precision), `VARCHAR` and `BLOB` vectors are supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is larger than the capacity of the vector, or if the
+    /// vector has any other type.
+    pub fn to_arrow_array(&self, len: usize) -> ArrayRef {
+        assert!(
+            len <= self.capacity(),
+            "cannot read {len} rows from a vector of capacity {}",
+            self.capacity()
+        );
+        let nulls = self
+            .validity_slice()
+            .map(|_| NullBuffer::from_iter((0..len).map(|row| !self.row_is_null(row))));
+        match self.logical_type().id() {
+            LogicalTypeId::Boolean => {
+                let values = self.as_slice::<u8>()[..len].iter().map(|&b| b != 0);
+                Arc::new(BooleanArray::new(BooleanBuffer::from_iter(values), nulls))
+            }
+            LogicalTypeId::Tinyint => flat_vector_to_primitive_array::<Int8Type>(self, len, nulls),
+            LogicalTypeId::Smallint => flat_vector_to_primitive_array::<Int16Type>(self, len, nulls),
+            LogicalTypeId::Integer => flat_vector_to_primitive_array::<Int32Type>(self, len, nulls),
+            LogicalTypeId::Bigint => flat_vector_to_primitive_array::<Int64Type>(self, len, nulls),
+            LogicalTypeId::UTinyint => flat_vector_to_primitive_array::<UInt8Type>(self, len, nulls),
+            LogicalTypeId::USmallint => flat_vector_to_primitive_array::<UInt16Type>(self, len, nulls),
+            LogicalTypeId::UInteger => flat_vector_to_primitive_array::<UInt32Type>(self, len, nulls),
+            LogicalTypeId::UBigint => flat_vector_to_primitive_array::<UInt64Type>(self, len, nulls),
+            LogicalTypeId::Float => flat_vector_to_primitive_array::<Float32Type>(self, len, nulls),
+            LogicalTypeId::Double => flat_vector_to_primitive_array::<Float64Type>(self, len, nulls),
+            LogicalTypeId::Date => flat_vector_to_primitive_array::<Date32Type>(self, len, nulls),
+            LogicalTypeId::Time => flat_vector_to_primitive_array::<Time64MicrosecondType>(self, len, nulls),
+            LogicalTypeId::TimestampS => flat_vector_to_primitive_array::<TimestampSecondType>(self, len, nulls),
+            LogicalTypeId::TimestampMs => flat_vector_to_primitive_array::<TimestampMillisecondType>(self, len, nulls),
+            LogicalTypeId::Timestamp => flat_vector_to_primitive_array::<TimestampMicrosecondType>(self, len, nulls),
+            LogicalTypeId::TimestampNs => flat_vector_to_primitive_array::<TimestampNanosecondType>(self, len, nulls),
+            LogicalTypeId::Varchar => Arc::new((0..len).map(|row| self.get_str(row)).collect::<StringArray>()),
+            LogicalTypeId::Blob => Arc::new((0..len).map(|row| self.get_bytes(row)).collect::<BinaryArray>()),
+            id => panic!("cannot convert a {id:?} vector to an Arrow array"),
+        }
+    }
+}
+
+fn flat_vector_to_primitive_array<T: ArrowPrimitiveType>(
+    vector: &FlatVector,
+    len: usize,
+    nulls: Option<NullBuffer>,
+) -> ArrayRef {
+    let values = vector.as_slice::<T::Native>()[..len].to_vec();
+    Arc::new(PrimitiveArray::<T>::new(ScalarBuffer::from(values), nulls))
+}
+
 /// Converts a `RecordBatch` to a `DataChunk` in the DuckDB format.
 ///
+/// Returns an error if the batch and the chunk have a different number of
+/// columns, if a column doesn't have the DuckDB type corresponding to its
+/// Arrow type, or if the batch has more rows than fit in the chunk.
+///
 /// # Arguments
 ///
 /// * `batch` - A reference to the `RecordBatch` to be converted to a `DataChunk`.
@@ -218,8 +657,30 @@ pub fn record_batch_to_duckdb_data_chunk(
     batch: &RecordBatch,
     chunk: &mut DataChunk,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    if batch.num_columns() != chunk.num_columns() {
+        return Err(format!(
+            "the record batch has {} columns, but the data chunk has {}",
+            batch.num_columns(),
+            chunk.num_columns()
+        )
+        .into());
+    }
+    let capacity = unsafe { duckdb_vector_size() } as usize;
+    if batch.num_rows() > capacity {
+        return Err(format!(
+            "the record batch has {} rows, but a data chunk holds at most {capacity}",
+            batch.num_rows()
+        )
+        .into());
+    }
+    for (i, field) in batch.schema().fields().iter().enumerate() {
+        let expected = to_duckdb_logical_type(field.data_type())?.id();
+        let actual = chunk.flat_vector(i).logical_type().id();
+        if expected != actual {
+            return Err(format!("column {field} can't be written to a {actual:?} vector").into());
+        }
+    }
     // Fill the row
-    assert_eq!(batch.num_columns(), chunk.num_columns());
     for i in 0..batch.num_columns() {
         let col = batch.column(i);
         match col.data_type() {
@@ -229,8 +690,8 @@ pub fn record_batch_to_duckdb_data_chunk(
             DataType::Utf8 => {
                 string_array_to_vector(as_string_array(col.as_ref()), &mut chunk.flat_vector(i));
             }
-            DataType::Binary => {
-                binary_array_to_vector(as_generic_binary_array(col.as_ref()), &mut chunk.flat_vector(i));
+            DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => {
+                binary_array_to_vector(col.as_ref(), &mut chunk.flat_vector(i));
             }
             DataType::List(_) => {
                 list_array_to_vector(as_list_array(col.as_ref()), &mut chunk.list_vector(i))?;
@@ -259,9 +720,11 @@ pub fn record_batch_to_duckdb_data_chunk(
     Ok(())
 }
 
-fn primitive_array_to_flat_vector<T: ArrowPrimitiveType>(array: &PrimitiveArray<T>, out_vector: &mut FlatVector) {
-    // assert!(array.len() <= out_vector.capacity());
-    out_vector.copy::<T::Native>(array.values());
+fn primitive_array_to_flat_vector<T: ArrowPrimitiveType>(
+    array: &PrimitiveArray<T>,
+    out_vector: &mut FlatVector,
+) -> crate::Result<()> {
+    out_vector.copy::<T::Native>(array.values())?;
     if let Some(nulls) = array.nulls() {
         for (i, null) in nulls.into_iter().enumerate() {
             if !null {
@@ -269,16 +732,17 @@ fn primitive_array_to_flat_vector<T: ArrowPrimitiveType>(array: &PrimitiveArray<
             }
         }
     }
+    Ok(())
 }
 
 fn primitive_array_to_flat_vector_cast<T: ArrowPrimitiveType>(
     data_type: DataType,
     array: &dyn Array,
     out_vector: &mut dyn Vector,
-) {
+) -> crate::Result<()> {
     let array = arrow::compute::kernels::cast::cast(array, &data_type).unwrap();
     let out_vector: &mut FlatVector = out_vector.as_mut_any().downcast_mut().unwrap();
-    out_vector.copy::<T::Native>(array.as_primitive::<T>().values());
+    out_vector.copy::<T::Native>(array.as_primitive::<T>().values())?;
     if let Some(nulls) = array.nulls() {
         for (i, null) in nulls.iter().enumerate() {
             if !null {
@@ -286,6 +750,7 @@ fn primitive_array_to_flat_vector_cast<T: ArrowPrimitiveType>(
             }
         }
     }
+    Ok(())
 }
 
 fn primitive_array_to_vector(array: &dyn Array, out: &mut dyn Vector) -> Result<(), Box<dyn std::error::Error>> {
@@ -297,61 +762,61 @@ fn primitive_array_to_vector(array: &dyn Array, out: &mut dyn Vector) -> Result<
             primitive_array_to_flat_vector::<UInt8Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
         DataType::UInt16 => {
             primitive_array_to_flat_vector::<UInt16Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
         DataType::UInt32 => {
             primitive_array_to_flat_vector::<UInt32Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
         DataType::UInt64 => {
             primitive_array_to_flat_vector::<UInt64Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
         DataType::Int8 => {
             primitive_array_to_flat_vector::<Int8Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
         DataType::Int16 => {
             primitive_array_to_flat_vector::<Int16Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
         DataType::Int32 => {
             primitive_array_to_flat_vector::<Int32Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
         DataType::Int64 => {
             primitive_array_to_flat_vector::<Int64Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
         DataType::Float32 => {
             primitive_array_to_flat_vector::<Float32Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
         DataType::Float64 => {
             primitive_array_to_flat_vector::<Float64Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
         DataType::Decimal128(width, _) => {
             decimal_array_to_vector(
@@ -366,37 +831,37 @@ fn primitive_array_to_vector(array: &dyn Array, out: &mut dyn Vector) -> Result<
             DataType::Timestamp(TimeUnit::Microsecond, Some(tz.clone())),
             array,
             out,
-        ),
+        )?,
         DataType::Timestamp(unit, None) => match unit {
             TimeUnit::Second => primitive_array_to_flat_vector::<TimestampSecondType>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            ),
+            )?,
             TimeUnit::Millisecond => primitive_array_to_flat_vector::<TimestampMillisecondType>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            ),
+            )?,
             TimeUnit::Microsecond => primitive_array_to_flat_vector::<TimestampMicrosecondType>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            ),
+            )?,
             TimeUnit::Nanosecond => primitive_array_to_flat_vector::<TimestampNanosecondType>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            ),
+            )?,
         },
         DataType::Date32 => {
             primitive_array_to_flat_vector::<Date32Type>(
                 as_primitive_array(array),
                 out.as_mut_any().downcast_mut().unwrap(),
-            );
+            )?;
         }
-        DataType::Date64 => primitive_array_to_flat_vector_cast::<Date32Type>(Date32Type::DATA_TYPE, array, out),
+        DataType::Date64 => primitive_array_to_flat_vector_cast::<Date32Type>(Date32Type::DATA_TYPE, array, out)?,
         DataType::Time32(_) => {
-            primitive_array_to_flat_vector_cast::<Time64MicrosecondType>(Time64MicrosecondType::DATA_TYPE, array, out)
+            primitive_array_to_flat_vector_cast::<Time64MicrosecondType>(Time64MicrosecondType::DATA_TYPE, array, out)?
         }
         DataType::Time64(_) => {
-            primitive_array_to_flat_vector_cast::<Time64MicrosecondType>(Time64MicrosecondType::DATA_TYPE, array, out)
+            primitive_array_to_flat_vector_cast::<Time64MicrosecondType>(Time64MicrosecondType::DATA_TYPE, array, out)?
         }
         datatype => return Err(format!("Data type \"{datatype}\" not yet supported by ArrowVTab").into()),
     }
@@ -450,6 +915,9 @@ fn boolean_array_to_vector(array: &BooleanArray, out: &mut FlatVector) {
 
     for i in 0..array.len() {
         out.as_mut_slice()[i] = array.value(i);
+        if array.is_null(i) {
+            out.set_null(i);
+        }
     }
 }
 
@@ -458,17 +926,30 @@ fn string_array_to_vector(array: &StringArray, out: &mut FlatVector) {
 
     // TODO: zero copy assignment
     for i in 0..array.len() {
-        let s = array.value(i);
-        out.insert(i, s);
+        if array.is_null(i) {
+            out.set_null(i);
+        } else {
+            out.insert(i, array.value(i));
+        }
     }
 }
 
-fn binary_array_to_vector(array: &BinaryArray, out: &mut FlatVector) {
-    assert!(array.len() <= out.capacity());
+fn binary_array_to_vector(array: &dyn Array, out: &mut FlatVector) {
+    match array.data_type() {
+        DataType::LargeBinary => binary_values_to_vector(as_generic_binary_array::<i64>(array).iter(), out),
+        DataType::FixedSizeBinary(_) => binary_values_to_vector(array.as_fixed_size_binary().iter(), out),
+        _ => binary_values_to_vector(as_generic_binary_array::<i32>(array).iter(), out),
+    }
+}
 
-    for i in 0..array.len() {
-        let s = array.value(i);
-        out.insert(i, s);
+fn binary_values_to_vector<'a>(values: impl ExactSizeIterator<Item = Option<&'a [u8]>>, out: &mut FlatVector) {
+    assert!(values.len() <= out.capacity());
+
+    for (i, value) in values.enumerate() {
+        match value {
+            Some(value) => out.insert(i, value),
+            None => out.set_null(i),
+        }
     }
 }
 
@@ -485,8 +966,8 @@ fn list_array_to_vector<O: OffsetSizeTrait + AsPrimitive<usize>>(
         DataType::Utf8 => {
             string_array_to_vector(as_string_array(value_array.as_ref()), &mut child);
         }
-        DataType::Binary => {
-            binary_array_to_vector(as_generic_binary_array(value_array.as_ref()), &mut child);
+        DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => {
+            binary_array_to_vector(value_array.as_ref(), &mut child);
         }
         _ => {
             return Err("Nested list is not supported yet.".into());
@@ -497,7 +978,11 @@ fn list_array_to_vector<O: OffsetSizeTrait + AsPrimitive<usize>>(
         let offset = array.value_offsets()[i];
         let length = array.value_length(i);
         out.set_entry(i, offset.as_(), length.as_());
+        if array.is_null(i) {
+            out.set_null(i);
+        }
     }
+    out.set_len(value_array.len());
     Ok(())
 }
 
@@ -514,8 +999,8 @@ fn fixed_size_list_array_to_vector(
         DataType::Utf8 => {
             string_array_to_vector(as_string_array(value_array.as_ref()), &mut child);
         }
-        DataType::Binary => {
-            binary_array_to_vector(as_generic_binary_array(value_array.as_ref()), &mut child);
+        DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => {
+            binary_array_to_vector(value_array.as_ref(), &mut child);
         }
         _ => {
             return Err("Nested array is not supported yet.".into());
@@ -541,8 +1026,8 @@ fn struct_array_to_vector(array: &StructArray, out: &mut StructVector) -> Result
             DataType::Utf8 => {
                 string_array_to_vector(as_string_array(column.as_ref()), &mut out.child(i));
             }
-            DataType::Binary => {
-                binary_array_to_vector(as_generic_binary_array(column.as_ref()), &mut out.child(i));
+            DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => {
+                binary_array_to_vector(column.as_ref(), &mut out.child(i));
             }
             DataType::List(_) => {
                 list_array_to_vector(as_list_array(column.as_ref()), &mut out.list_vector_child(i))?;
@@ -607,14 +1092,17 @@ pub fn arrow_ffi_to_query_params(array: FFI_ArrowArray, schema: FFI_ArrowSchema)
 
 #[cfg(test)]
 mod test {
-    use super::{arrow_recordbatch_to_query_params, ArrowVTab};
-    use crate::{Connection, Result};
+    use super::{arrow_recordbatch_to_query_params, record_batch_to_duckdb_data_chunk, ArrowVTab};
+    use crate::{
+        vtab::{DataChunk, LogicalType, LogicalTypeId},
+        Connection, Result,
+    };
     use arrow::{
         array::{
             Array, ArrayRef, AsArray, BinaryArray, Date32Array, Date64Array, Decimal128Array, Decimal256Array,
-            FixedSizeListArray, GenericListArray, Int32Array, ListArray, OffsetSizeTrait, PrimitiveArray, StringArray,
-            StructArray, Time32SecondArray, Time64MicrosecondArray, TimestampMicrosecondArray,
-            TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
+            FixedSizeBinaryArray, FixedSizeListArray, GenericListArray, Int32Array, LargeBinaryArray, ListArray,
+            OffsetSizeTrait, PrimitiveArray, StringArray, StructArray, Time32SecondArray, Time64MicrosecondArray,
+            TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
         },
         buffer::{OffsetBuffer, ScalarBuffer},
         datatypes::{i256, ArrowPrimitiveType, DataType, Field, Fields, Schema},
@@ -961,6 +1449,182 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_attach_arrow_dataset() -> Result<(), Box<dyn Error>> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let db = Connection::open_in_memory()?;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("n", DataType::Int32, true),
+            Field::new("s", DataType::Utf8, false),
+        ]));
+        let batch = |range: std::ops::Range<i32>| {
+            let n = Int32Array::from_iter(range.clone().map(|i| (i % 3 != 0).then_some(i)));
+            let s = StringArray::from_iter_values(range.map(|i| format!("row {i}")));
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(n), Arc::new(s)]).unwrap()
+        };
+        // The batches are produced lazily, and span several chunks.
+        let batches = [batch(0..5000), batch(5000..5000), batch(5000..5003)];
+        let scans = Arc::new(AtomicUsize::new(0));
+        let counter = scans.clone();
+        db.attach_arrow_dataset("numbers", schema.clone(), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            batches.clone().into_iter()
+        })?;
+
+        let (count, nulls, sum): (i64, i64, i64) = db.query_row(
+            "SELECT count(*), count(*) FILTER (n IS NULL), sum(n) FROM numbers",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )?;
+        assert_eq!((count, nulls), (5003, 1668));
+        assert_eq!(sum, (0..5003).filter(|i| i % 3 != 0).sum::<i64>());
+        let s: String = db.query_row("SELECT s FROM numbers() WHERE n = 5002", [], |r| r.get(0))?;
+        assert_eq!(s, "row 5002");
+        assert_eq!(scans.load(Ordering::SeqCst), 2);
+
+        let other = Arc::new(Schema::new(vec![Field::new("n", DataType::Int64, false)]));
+        db.attach_arrow_dataset("mismatched", schema, move || {
+            let n = arrow::array::Int64Array::from(vec![1]);
+            [RecordBatch::try_new(other.clone(), vec![Arc::new(n)]).unwrap()]
+        })?;
+        let err = db.query_row("SELECT * FROM mismatched", [], |_| Ok(())).unwrap_err();
+        assert!(err.to_string().contains("does not match the dataset schema"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_arrow() -> Result<(), Box<dyn Error>> {
+        use arrow::record_batch::RecordBatchIterator;
+
+        let db = Connection::open_in_memory()?;
+        let n = Int32Array::from_iter_values(0..5000);
+        let s = StringArray::from_iter_values((0..5000).map(|i| format!("row {i}")));
+        let batch = RecordBatch::try_from_iter([("n", Arc::new(n) as ArrayRef), ("s", Arc::new(s) as ArrayRef)])?;
+        db.register_arrow("my_batch", &batch)?;
+
+        let (count, sum): (i64, i64) = db.query_row("SELECT count(*), sum(n) FROM my_batch", [], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })?;
+        assert_eq!((count, sum), (5000, 12_497_500));
+        let s: String = db.query_row("SELECT s FROM My_Batch WHERE n = 4999", [], |r| r.get(0))?;
+        assert_eq!(s, "row 4999");
+
+        // The tables are shared with the clones of the connection. A statement
+        // prepared before unregistering keeps reading the batch.
+        let clone = db.try_clone()?;
+        let mut stmt = clone.prepare("SELECT count(*) FROM my_batch")?;
+        assert!(clone.unregister("my_batch"));
+        assert!(!db.unregister("my_batch"));
+        assert_eq!(stmt.query_row([], |r| r.get::<_, i64>(0))?, 5000);
+        let err = db
+            .query_row("SELECT count(*) FROM my_batch", [], |_| Ok(()))
+            .unwrap_err();
+        assert!(err.to_string().contains("my_batch does not exist"), "{err}");
+
+        let reader = RecordBatchIterator::new([Ok(batch.slice(0, 10)), Ok(batch.slice(10, 5))], batch.schema());
+        db.register_arrow_reader("my_reader", reader)?;
+        let count: i64 = db.query_row("SELECT count(*) FROM my_reader JOIN my_reader USING (n)", [], |r| {
+            r.get(0)
+        })?;
+        assert_eq!(count, 15);
+
+        // Registering a name again replaces its table.
+        db.register_arrow("my_reader", &batch.slice(0, 1))?;
+        let count: i64 = db.query_row("SELECT count(*) FROM my_reader", [], |r| r.get(0))?;
+        assert_eq!(count, 1);
+
+        // Registering on a clone doesn't register the table function again.
+        clone.register_arrow("my_clone_batch", &batch.slice(0, 2))?;
+        let count: i64 = db.query_row("SELECT count(*) FROM my_clone_batch", [], |r| r.get(0))?;
+        assert_eq!(count, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_chunk_from_record_batch() -> Result<(), Box<dyn Error>> {
+        let lists = ListArray::from_iter_primitive::<arrow::datatypes::Int64Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+            Some(vec![]),
+        ]);
+        let batch = RecordBatch::try_from_iter([
+            (
+                "i",
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+            ),
+            (
+                "s",
+                Arc::new(StringArray::from(vec!["a", "a string longer than 12 bytes", ""])),
+            ),
+            ("l", Arc::new(lists)),
+        ])?;
+
+        let chunk = DataChunk::from_record_batch(&batch)?;
+        assert_eq!((chunk.len(), chunk.num_columns()), (3, 3));
+        let ints = chunk.flat_vector(0);
+        assert_eq!([ints.as_slice::<i32>()[0], ints.as_slice::<i32>()[2]], [1, 3]);
+        assert_eq!(ints.nulls().collect::<Vec<_>>(), [1]);
+        assert_eq!(chunk.flat_vector(1).get_str(1), Some("a string longer than 12 bytes"));
+        let list = chunk.list_vector(2);
+        assert_eq!(list.get::<i64>(0).unwrap().collect::<Vec<_>>(), [Some(1), Some(2)]);
+        assert!(list.row_is_null(1));
+        assert_eq!(list.get::<i64>(2).unwrap().count(), 0);
+
+        // mismatched chunks are an error
+        let mut chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Integer)]);
+        let err = record_batch_to_duckdb_data_chunk(&batch, &mut chunk).unwrap_err();
+        assert!(err.to_string().contains("has 3 columns"), "{err}");
+        let mut chunk = DataChunk::new(&[
+            LogicalType::new(LogicalTypeId::Integer),
+            LogicalType::new(LogicalTypeId::Integer),
+            LogicalType::new(LogicalTypeId::Integer),
+        ]);
+        let err = record_batch_to_duckdb_data_chunk(&batch, &mut chunk).unwrap_err();
+        assert!(
+            err.to_string().contains("can't be written to a Integer vector"),
+            "{err}"
+        );
+
+        let batch = RecordBatch::try_from_iter([("i", Arc::new(Int32Array::from(vec![0; 5000])) as ArrayRef)])?;
+        let err = DataChunk::from_record_batch(&batch).err().unwrap();
+        assert!(err.to_string().contains("has 5000 rows"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_flat_vector_to_arrow_array() -> Result<(), Box<dyn Error>> {
+        let db = Connection::open_in_memory()?;
+        let batch = db
+            .prepare(
+                "SELECT * FROM (VALUES
+                    (true, -1::TINYINT, 2::SMALLINT, 3, 4::BIGINT, 5::UTINYINT, 6::USMALLINT, 7::UINTEGER,
+                     8::UBIGINT, 1.5::FLOAT, 2.5::DOUBLE, DATE '2024-02-29', TIME '12:34:56.789',
+                     TIMESTAMP '2024-02-29 12:34:56.789', 'a string longer than 12 bytes', 'blob'::BLOB),
+                    (NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL),
+                    (false, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, DATE '1970-01-01', TIME '00:00:00',
+                     TIMESTAMP '1970-01-01', '', ''::BLOB)
+                )",
+            )?
+            .query_arrow([])?
+            .next()
+            .unwrap();
+        let chunk = DataChunk::from_record_batch(&batch)?;
+        for (i, expected) in batch.columns().iter().enumerate() {
+            let array = chunk.flat_vector(i).to_arrow_array(chunk.len());
+            assert_eq!(&array, expected, "column {i}");
+            assert!(array.is_null(1));
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot convert a List vector to an Arrow array")]
+    fn test_flat_vector_to_arrow_array_unsupported() {
+        let chunk = DataChunk::new(&[LogicalType::list(&LogicalType::new(LogicalTypeId::Integer))]);
+        chunk.flat_vector(0).to_arrow_array(0);
+    }
+
     #[test]
     fn test_arrow_error() {
         let arc: ArrayRef = Arc::new(Decimal256Array::from(vec![i256::from(1), i256::from(2), i256::from(3)]));
@@ -1007,4 +1671,31 @@ mod test {
         assert_eq!(column.len(), 1);
         assert_eq!(column.value(0), b"test");
     }
+
+    #[test]
+    fn test_arrow_large_and_fixed_size_binary() -> Result<(), Box<dyn Error>> {
+        let large = vec![7u8; 1 << 20];
+        let hash = [0xABu8; 32];
+        let large_array: ArrayRef = Arc::new(LargeBinaryArray::from_opt_vec(vec![Some(large.as_slice()), None]));
+        let hash_array: ArrayRef = Arc::new(FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+            vec![None, Some(hash)].into_iter(),
+            32,
+        )?);
+        let batch = RecordBatch::try_from_iter(vec![("large", large_array), ("hash", hash_array)])?;
+
+        let db = Connection::open_in_memory()?;
+        db.register_table_function::<ArrowVTab>("arrow")?;
+        db.execute_batch("CREATE TABLE blobs (large BLOB, hash BLOB)")?;
+        db.execute(
+            "INSERT INTO blobs SELECT * FROM arrow(?, ?)",
+            arrow_recordbatch_to_query_params(batch),
+        )?;
+
+        let mut stmt = db.prepare("SELECT large, hash FROM blobs")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<(Option<Vec<u8>>, Option<Vec<u8>>)>>>()?;
+        assert_eq!(rows, vec![(Some(large), None), (None, Some(hash.to_vec()))]);
+        Ok(())
+    }
 }
@@ -1,10 +1,10 @@
 use super::{
     logical_type::LogicalType,
-    vector::{ArrayVector, FlatVector, ListVector, StructVector},
+    vector::{ArrayVector, EnumVector, FlatVector, ListVector, MapVector, StructVector},
 };
 use crate::ffi::{
     duckdb_create_data_chunk, duckdb_data_chunk, duckdb_data_chunk_get_column_count, duckdb_data_chunk_get_size,
-    duckdb_data_chunk_get_vector, duckdb_data_chunk_set_size, duckdb_destroy_data_chunk,
+    duckdb_data_chunk_get_vector, duckdb_data_chunk_reset, duckdb_data_chunk_set_size, duckdb_destroy_data_chunk,
 };
 
 /// DataChunk in DuckDB.
@@ -27,12 +27,26 @@ impl DataChunk {
 
     /// Get the vector at the specific column index: `idx`.
     pub fn flat_vector(&self, idx: usize) -> FlatVector {
-        FlatVector::from(unsafe { duckdb_data_chunk_get_vector(self.ptr, idx as u64) })
+        FlatVector::in_chunk(unsafe { duckdb_data_chunk_get_vector(self.ptr, idx as u64) }, self.ptr)
     }
 
     /// Get a list vector from the column index.
     pub fn list_vector(&self, idx: usize) -> ListVector {
-        ListVector::from(unsafe { duckdb_data_chunk_get_vector(self.ptr, idx as u64) })
+        ListVector::in_chunk(unsafe { duckdb_data_chunk_get_vector(self.ptr, idx as u64) }, self.ptr)
+    }
+
+    /// Get a map vector from the column index.
+    pub fn map_vector(&self, idx: usize) -> MapVector {
+        MapVector::from(unsafe { duckdb_data_chunk_get_vector(self.ptr, idx as u64) })
+    }
+
+    /// Get an enum vector from the column index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column isn't an `ENUM`.
+    pub fn enum_vector(&self, idx: usize) -> EnumVector {
+        EnumVector::from(unsafe { duckdb_data_chunk_get_vector(self.ptr, idx as u64) })
     }
 
     /// Get a array vector from the column index.
@@ -50,6 +64,18 @@ impl DataChunk {
         unsafe { duckdb_data_chunk_set_size(self.ptr, new_len as u64) };
     }
 
+    /// Reset the [DataChunk] so that it can be filled again, e.g. by an
+    /// appender or a table function producing many chunks, without allocating
+    /// a new one.
+    ///
+    /// This sets the length to 0, marks every row as valid, and releases the
+    /// strings and the list entries of the previous fill. Get the vectors
+    /// again after resetting: the children of nested vectors obtained before
+    /// are no longer valid.
+    pub fn reset(&mut self) {
+        unsafe { duckdb_data_chunk_reset(self.ptr) };
+    }
+
     /// Get the length / the number of rows in this [DataChunk].
     pub fn len(&self) -> usize {
         unsafe { duckdb_data_chunk_get_size(self.ptr) as usize }
@@ -88,7 +114,10 @@ impl Drop for DataChunk {
 
 #[cfg(test)]
 mod test {
-    use super::{super::logical_type::LogicalTypeId, *};
+    use super::{
+        super::{logical_type::LogicalTypeId, vector::Inserter},
+        *,
+    };
 
     #[test]
     fn test_data_chunk_construction() {
@@ -108,6 +137,376 @@ mod test {
         data[0] = 42;
     }
 
+    #[test]
+    fn test_vector_slice_len() {
+        let datachunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Bigint)]);
+        let mut vector = datachunk.flat_vector(0);
+        assert!(vector.as_mut_slice_with_len::<i64>().is_empty());
+
+        datachunk.set_len(3);
+        vector.as_mut_slice_with_len::<i64>().copy_from_slice(&[1, 2, 3]);
+        assert_eq!(&vector.as_slice::<i64>()[..3], &[1, 2, 3]);
+        assert_eq!(vector.as_mut_slice::<i64>().len(), vector.capacity());
+
+        let list = DataChunk::new(&[LogicalType::list(&LogicalType::new(LogicalTypeId::Bigint))]);
+        let mut child = list.list_vector(0).child(5);
+        assert_eq!(child.as_mut_slice_with_len::<i64>().len(), 5);
+    }
+
+    #[test]
+    fn test_vector_copy_small_ints() {
+        let datachunk = DataChunk::new(&[
+            LogicalType::new(LogicalTypeId::Tinyint),
+            LogicalType::new(LogicalTypeId::USmallint),
+        ]);
+        datachunk.flat_vector(0).copy(&[-1i8, 2, 3]).unwrap();
+        datachunk.flat_vector(1).copy(&[u16::MAX, 2]).unwrap();
+        assert_eq!(&datachunk.flat_vector(0).as_slice::<i8>()[..3], &[-1, 2, 3]);
+        assert_eq!(&datachunk.flat_vector(1).as_slice::<u16>()[..2], &[u16::MAX, 2]);
+    }
+
+    #[test]
+    fn test_vector_copy_invalid() {
+        let datachunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Tinyint)]);
+        let err = datachunk.flat_vector(0).copy(&[1i32, 2, 3]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid vector write: cannot copy 4-byte values into a vector of 1-byte elements"
+        );
+        let mut vector = datachunk.flat_vector(0);
+        let too_many = vec![0i8; vector.capacity() + 1];
+        assert!(matches!(
+            vector.copy(&too_many),
+            Err(crate::Error::InvalidVectorWrite(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot insert 4-byte values into a vector of 8-byte elements")]
+    fn test_vector_insert_width_mismatch() {
+        let chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Bigint)]);
+        chunk.flat_vector(0).insert(0, 1i32);
+    }
+
+    #[test]
+    fn test_vector_insert_option() {
+        let chunk = DataChunk::new(&[
+            LogicalType::new(LogicalTypeId::Integer),
+            LogicalType::new(LogicalTypeId::Varchar),
+        ]);
+        let (numbers, strings) = (chunk.flat_vector(0), chunk.flat_vector(1));
+        let values = [Some(1), None, Some(3), None];
+        for (i, value) in values.into_iter().enumerate() {
+            numbers.insert(i, value);
+            strings.insert(i, value.map(|_| "duck"));
+        }
+        // Overwriting a null
+        numbers.insert(3, Some(4));
+        chunk.set_len(4);
+
+        assert_eq!(numbers.nulls().collect::<Vec<_>>(), [1]);
+        let data = numbers.as_slice::<i32>();
+        assert_eq!((data[0], data[2], data[3]), (1, 3, 4));
+        assert_eq!(strings.nulls().collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(strings.get_str(2), Some("duck"));
+    }
+
+    #[test]
+    fn test_vector_nulls() {
+        let chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Integer)]);
+        let mut vector = chunk.flat_vector(0);
+        assert!(vector.validity_slice().is_none());
+        assert_eq!(vector.nulls().count(), 0);
+
+        for row in [1, 63, 64, 130, 200] {
+            vector.set_null(row);
+        }
+        chunk.set_len(131);
+        let validity = vector.validity_slice().unwrap();
+        assert_eq!(validity.len(), vector.capacity().div_ceil(64));
+        assert_eq!(validity[0], !(1 << 1 | 1 << 63));
+        assert_eq!(validity[1], !1);
+        assert_eq!(vector.nulls().collect::<Vec<_>>(), [1, 63, 64, 130]);
+        assert!((0..131).all(|row| vector.row_is_null(row) == [1, 63, 64, 130].contains(&row)));
+    }
+
+    #[test]
+    fn test_enum_vector() {
+        let chunk = DataChunk::new(&[LogicalType::enum_type(&["ACTIVE", "INACTIVE"]).unwrap()]);
+        let mut vector = chunk.enum_vector(0);
+        assert_eq!(vector.labels(), ["ACTIVE", "INACTIVE"]);
+        vector.insert_label(0, "INACTIVE").unwrap();
+        vector.insert_label(1, "ACTIVE").unwrap();
+        vector.set_null(2);
+        assert_eq!(chunk.flat_vector(0).as_slice::<u8>()[..2], [1, 0]);
+        assert_eq!(vector.get_label(0), Some("INACTIVE"));
+        assert_eq!(vector.get_ordinal(1), Some(0));
+        assert_eq!(vector.get_label(2), None);
+
+        let err = vector.insert_label(0, "active").unwrap_err();
+        assert!(err.to_string().contains("\"active\" is not a label"), "{err}");
+        assert_eq!(vector.get_label(0), Some("INACTIVE"));
+
+        // 256 labels don't fit in a UTINYINT
+        let labels = (0..256).map(|i| i.to_string()).collect::<Vec<_>>();
+        let labels = labels.iter().map(String::as_str).collect::<Vec<_>>();
+        let chunk = DataChunk::new(&[LogicalType::enum_type(&labels).unwrap()]);
+        let vector = chunk.enum_vector(0);
+        vector.insert_label(0, "255").unwrap();
+        assert_eq!(chunk.flat_vector(0).as_slice::<u16>()[0], 255);
+        assert_eq!(vector.get_label(0), Some("255"));
+    }
+
+    #[test]
+    fn test_data_chunk_reset() {
+        let mut chunk = DataChunk::new(&[
+            LogicalType::new(LogicalTypeId::Varchar),
+            LogicalType::list(&LogicalType::new(LogicalTypeId::Integer)),
+        ]);
+        for round in 0..3 {
+            let mut strings = chunk.flat_vector(0);
+            let mut lists = chunk.list_vector(1);
+            assert!(strings.validity_slice().is_none());
+            assert!(lists.is_empty());
+
+            strings.insert(0, format!("a string longer than twelve bytes {round}").as_str());
+            strings.set_null(1);
+            lists.child(3).copy(&[round, round, round]).unwrap();
+            lists.set_entry(0, 0, 3);
+            lists.set_len(3);
+            lists.set_null(1);
+            chunk.set_len(2);
+            assert_eq!(
+                strings.get_str(0),
+                Some(format!("a string longer than twelve bytes {round}").as_str())
+            );
+            assert_eq!(strings.nulls().collect::<Vec<_>>(), [1]);
+
+            chunk.reset();
+            assert!(chunk.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_vector_uuid() {
+        let chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Uuid)]);
+        let mut vector = chunk.flat_vector(0);
+        let uuid = 0xa0eebc99_9c0b_4ef8_bb6d_6bb9bd380a11;
+        vector.insert_uuid(0, uuid);
+        vector.set_null(1);
+        assert_eq!(vector.as_slice::<u128>()[0], uuid ^ 1 << 127);
+        assert_eq!(vector.get_uuid(0), Some(uuid));
+        assert_eq!(vector.get_uuid(1), None);
+
+        #[cfg(feature = "uuid")]
+        {
+            let uuid = uuid::Uuid::from_u128(1);
+            vector.insert(2, uuid);
+            assert_eq!(vector.get_uuid(2), Some(1));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not a UUID vector")]
+    fn test_vector_uuid_wrong_type() {
+        let chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Hugeint)]);
+        chunk.flat_vector(0).insert_uuid(0, 1);
+    }
+
+    #[test]
+    fn test_vector_try_as_slice() {
+        let chunk = DataChunk::new(&[
+            LogicalType::new(LogicalTypeId::Bigint),
+            LogicalType::struct_type(&[("a", LogicalType::new(LogicalTypeId::Integer))]),
+        ]);
+        let mut vector = chunk.flat_vector(0);
+        vector.copy(&[1i64, -2]).unwrap();
+        assert_eq!(&vector.try_as_slice::<i64>().unwrap()[..2], &[1, -2]);
+        assert!(vector.try_as_slice::<i32>().is_none());
+        assert!(chunk.flat_vector(1).try_as_slice::<u64>().is_none());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "cannot read 4-byte values from a vector of 8-byte elements")]
+    fn test_vector_as_slice_wrong_width() {
+        let chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Bigint)]);
+        chunk.flat_vector(0).as_slice::<i32>();
+    }
+
+    #[test]
+    fn test_vector_copy_with_validity() {
+        let chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Double)]);
+        let mut vector = chunk.flat_vector(0);
+        vector
+            .copy_with_validity(&[1.0, 2.0, 3.0], &[true, true, true])
+            .unwrap();
+        assert!(vector.validity_slice().is_none());
+
+        vector
+            .copy_with_validity(&[1.5, 0.0, 3.5, 0.0], &[true, false, true, false])
+            .unwrap();
+        chunk.set_len(4);
+        assert_eq!(&vector.as_slice::<f64>()[..4], &[1.5, 0.0, 3.5, 0.0]);
+        assert_eq!(vector.nulls().collect::<Vec<_>>(), [1, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "data and validity have different lengths")]
+    fn test_vector_copy_with_validity_mismatch() {
+        let chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Integer)]);
+        chunk.flat_vector(0).copy_with_validity(&[1i32, 2], &[true]).unwrap();
+    }
+
+    #[test]
+    fn test_list_vector_read() {
+        let chunk = DataChunk::new(&[LogicalType::list(&LogicalType::new(LogicalTypeId::Integer))]);
+        let mut list = chunk.list_vector(0);
+        // [1, 2, 3], NULL, [4, NULL], []
+        let mut child = list.child(6);
+        child.copy(&[1i32, 2, 3, 4, 0]).unwrap();
+        child.set_null(4);
+        list.set_len(5);
+        list.set_entry(0, 0, 3);
+        list.set_null(1);
+        list.set_entry(2, 3, 2);
+        list.set_entry(3, 5, 0);
+        chunk.set_len(4);
+
+        let list_sum = |row| list.get::<i32>(row).map(|items| items.flatten().sum::<i32>());
+        assert_eq!(list_sum(0), Some(6));
+        assert_eq!(list_sum(1), None);
+        assert_eq!(list_sum(2), Some(4));
+        assert_eq!(list_sum(3), Some(0));
+
+        assert!(list.row_is_null(1));
+        assert!(!list.row_is_null(3));
+        assert_eq!(list.get_entry(2), (3, 2));
+        assert_eq!(list.get::<i32>(2).unwrap().collect::<Vec<_>>(), [Some(4), None]);
+    }
+
+    #[test]
+    fn test_list_vector_append_entry() {
+        let chunk = DataChunk::new(&[LogicalType::list(&LogicalType::new(LogicalTypeId::Bigint))]);
+        let mut list = chunk.list_vector(0);
+        list.append_entry::<i64>(&[]);
+        list.append_entry(&[1i64]);
+        list.append_null();
+        list.append_entry(&[2i64, 3, 4]);
+        chunk.set_len(4);
+
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.get_entry(3), (1, 3));
+        let rows = (0..4)
+            .map(|row| list.get::<i64>(row).map(|items| items.flatten().collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        assert_eq!(rows, [Some(vec![]), Some(vec![1]), None, Some(vec![2, 3, 4])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot append 4-byte values into a vector of 8-byte elements")]
+    fn test_list_vector_append_entry_width_mismatch() {
+        let chunk = DataChunk::new(&[LogicalType::list(&LogicalType::new(LogicalTypeId::Bigint))]);
+        chunk.list_vector(0).append_entry(&[1i32]);
+    }
+
+    #[test]
+    fn test_list_vector_slices() {
+        let chunk = DataChunk::new(&[LogicalType::list(&LogicalType::new(LogicalTypeId::Integer))]);
+        let mut list = chunk.list_vector(0);
+        assert!(list.child_slice::<i32>().is_empty());
+        // [30, 40], [], [10], with gaps and out of order
+        list.set_child(&[10i32, 0, 30, 40, 0]).unwrap();
+        list.set_entry(0, 2, 2);
+        list.set_entry(1, 4, 0);
+        list.set_entry(2, 0, 1);
+        chunk.set_len(3);
+
+        let child = list.child_slice::<i32>();
+        assert_eq!(child.len(), 5);
+        let rows = list
+            .entries()
+            .iter()
+            .map(|e| &child[e.offset as usize..(e.offset + e.length) as usize])
+            .collect::<Vec<_>>();
+        assert_eq!(rows, [&[30, 40][..], &[], &[10]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot read 8-byte values from a vector of 4-byte elements")]
+    fn test_list_vector_read_width_mismatch() {
+        let chunk = DataChunk::new(&[LogicalType::list(&LogicalType::new(LogicalTypeId::Integer))]);
+        let _ = chunk.list_vector(0).get::<i64>(0);
+    }
+
+    #[test]
+    fn test_vector_get_str() {
+        use crate::vtab::Inserter;
+
+        let chunk = DataChunk::new(&[
+            LogicalType::new(LogicalTypeId::Varchar),
+            LogicalType::new(LogicalTypeId::Blob),
+        ]);
+        let mut text = chunk.flat_vector(0);
+        // inlined up to 12 bytes, stored behind a pointer beyond that
+        let strings = ["", "hello", "twelve bytes", "thirteen byte", "a much longer string ✓"];
+        for (i, s) in strings.iter().enumerate() {
+            text.insert(i, *s);
+        }
+        text.set_null(strings.len());
+        let blobs = chunk.flat_vector(1);
+        blobs.insert(0, &b"\xFF\x00short"[..]);
+        blobs.insert(1, &[0xFFu8; 40][..]);
+        chunk.set_len(strings.len() + 1);
+
+        for (i, s) in strings.iter().enumerate() {
+            assert_eq!(text.get_str(i), Some(*s));
+        }
+        assert_eq!(text.get_str(strings.len()), None);
+        assert_eq!(blobs.get_bytes(0), Some(&b"\xFF\x00short"[..]));
+        assert_eq!(blobs.get_bytes(1), Some(&[0xFFu8; 40][..]));
+        assert_eq!(blobs.get_str(1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot read bytes from a Integer vector")]
+    fn test_vector_get_str_type_mismatch() {
+        let chunk = DataChunk::new(&[LogicalType::new(LogicalTypeId::Integer)]);
+        chunk.flat_vector(0).get_str(0);
+    }
+
+    #[test]
+    fn test_array_vector_nulls() {
+        let chunk = DataChunk::new(&[LogicalType::array(&LogicalType::new(LogicalTypeId::Double), 4)]);
+        let mut arrays = chunk.array_vector(0);
+        arrays.set_child(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert!(!arrays.child_is_null(2));
+        arrays.set_child_null(2);
+        arrays.set_null(1);
+        assert!(arrays.child_is_null(2));
+        assert!(!arrays.child_is_null(3));
+        assert!(arrays.row_is_null(1));
+        assert!(!arrays.row_is_null(0));
+    }
+
+    #[test]
+    fn test_struct_vector_children() {
+        let fields = [
+            ("id", LogicalType::new(LogicalTypeId::Integer)),
+            ("name", LogicalType::new(LogicalTypeId::Varchar)),
+        ];
+        let chunk = DataChunk::new(&[LogicalType::struct_type(&fields)]);
+        let vector = chunk.struct_vector(0);
+
+        let names = vector.children().map(|(name, _)| name).collect::<Vec<_>>();
+        assert_eq!(names, ["id", "name"]);
+        vector.child_by_name("name").unwrap().insert(0, "duck");
+        vector.child_by_name("id").unwrap().insert(0, 7);
+        assert_eq!(vector.child(1).get_str(0), Some("duck"));
+        assert_eq!(vector.child(0).as_slice::<i32>()[0], 7);
+        assert!(vector.child_by_name("missing").is_none());
+    }
+
     #[test]
     fn test_logi() {
         let key = LogicalType::new(LogicalTypeId::Varchar);
@@ -1,9 +1,9 @@
 use std::{
-    ffi::{c_char, CString},
+    ffi::{c_char, CStr, CString},
     fmt::Debug,
 };
 
-use crate::ffi::*;
+use crate::{ffi::*, Result};
 
 /// Logical Type Id
 /// <https://duckdb.org/docs/api/c/types>
@@ -42,6 +42,8 @@ pub enum LogicalTypeId {
     Interval = DUCKDB_TYPE_DUCKDB_TYPE_INTERVAL,
     /// Hugeint
     Hugeint = DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT,
+    /// Unsigned Hugeint
+    UHugeint = DUCKDB_TYPE_DUCKDB_TYPE_UHUGEINT,
     /// Varchar
     Varchar = DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR,
     /// Blob
@@ -68,6 +70,10 @@ pub enum LogicalTypeId {
     Union = DUCKDB_TYPE_DUCKDB_TYPE_UNION,
     /// Timestamp TZ
     TimestampTZ = DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_TZ,
+    /// Time TZ, see [`TimeWithOffset`](crate::types::TimeWithOffset)
+    TimeTZ = DUCKDB_TYPE_DUCKDB_TYPE_TIME_TZ,
+    /// Array (fixed-size list)
+    Array = DUCKDB_TYPE_DUCKDB_TYPE_ARRAY,
 }
 
 impl From<u32> for LogicalTypeId {
@@ -92,6 +98,7 @@ impl From<u32> for LogicalTypeId {
             DUCKDB_TYPE_DUCKDB_TYPE_TIME => Self::Time,
             DUCKDB_TYPE_DUCKDB_TYPE_INTERVAL => Self::Interval,
             DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT => Self::Hugeint,
+            DUCKDB_TYPE_DUCKDB_TYPE_UHUGEINT => Self::UHugeint,
             DUCKDB_TYPE_DUCKDB_TYPE_DECIMAL => Self::Decimal,
             DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_S => Self::TimestampS,
             DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_MS => Self::TimestampMs,
@@ -103,6 +110,8 @@ impl From<u32> for LogicalTypeId {
             DUCKDB_TYPE_DUCKDB_TYPE_UUID => Self::Uuid,
             DUCKDB_TYPE_DUCKDB_TYPE_UNION => Self::Union,
             DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_TZ => Self::TimestampTZ,
+            DUCKDB_TYPE_DUCKDB_TYPE_TIME_TZ => Self::TimeTZ,
+            DUCKDB_TYPE_DUCKDB_TYPE_ARRAY => Self::Array,
             _ => panic!(),
         }
     }
@@ -200,6 +209,36 @@ impl LogicalType {
         }
     }
 
+    /// Creates an enum type with the given labels, in order.
+    ///
+    /// # Failure
+    ///
+    /// Will return [`Error::NulError`](crate::Error::NulError) if a label
+    /// contains a nul byte.
+    pub fn enum_type(labels: &[&str]) -> Result<Self> {
+        let labels = labels.iter().map(|l| CString::new(*l)).collect::<Result<Vec<_>, _>>()?;
+        let mut label_ptrs = labels.iter().map(|l| l.as_ptr()).collect::<Vec<*const c_char>>();
+        unsafe {
+            Ok(Self {
+                ptr: duckdb_create_enum_type(label_ptrs.as_mut_ptr(), label_ptrs.len() as idx_t),
+            })
+        }
+    }
+
+    /// Retrieves the labels of an enum, in order.
+    /// Returns an empty list if the LogicalType is not an enum
+    pub fn enum_labels(&self) -> Vec<String> {
+        let size = unsafe { duckdb_enum_dictionary_size(self.ptr) };
+        (0..size)
+            .map(|idx| unsafe {
+                let label = duckdb_enum_dictionary_value(self.ptr, idx as idx_t);
+                let owned = CStr::from_ptr(label).to_string_lossy().into_owned();
+                duckdb_free(label.cast());
+                owned
+            })
+            .collect()
+    }
+
     /// Retrieves the decimal width
     /// Returns 0 if the LogicalType is not a decimal
     pub fn decimal_width(&self) -> u8 {
@@ -212,6 +251,51 @@ impl LogicalType {
         unsafe { duckdb_decimal_scale(self.ptr) }
     }
 
+    /// Size in bytes of a single element in a flat vector of this type, or
+    /// `None` for types without a fixed-width data buffer (e.g. `STRUCT`).
+    pub(crate) fn physical_width(&self) -> Option<usize> {
+        let type_id = unsafe { duckdb_get_type_id(self.ptr) };
+        Some(match type_id {
+            DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN | DUCKDB_TYPE_DUCKDB_TYPE_TINYINT | DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => 1,
+            DUCKDB_TYPE_DUCKDB_TYPE_SMALLINT | DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => 2,
+            DUCKDB_TYPE_DUCKDB_TYPE_INTEGER
+            | DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER
+            | DUCKDB_TYPE_DUCKDB_TYPE_FLOAT
+            | DUCKDB_TYPE_DUCKDB_TYPE_DATE => 4,
+            DUCKDB_TYPE_DUCKDB_TYPE_BIGINT
+            | DUCKDB_TYPE_DUCKDB_TYPE_UBIGINT
+            | DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE
+            | DUCKDB_TYPE_DUCKDB_TYPE_TIME
+            | DUCKDB_TYPE_DUCKDB_TYPE_TIME_TZ
+            | DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP
+            | DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_S
+            | DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_MS
+            | DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_NS
+            | DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_TZ => 8,
+            DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT
+            | DUCKDB_TYPE_DUCKDB_TYPE_UHUGEINT
+            | DUCKDB_TYPE_DUCKDB_TYPE_UUID
+            | DUCKDB_TYPE_DUCKDB_TYPE_INTERVAL
+            | DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR
+            | DUCKDB_TYPE_DUCKDB_TYPE_BLOB
+            | DUCKDB_TYPE_DUCKDB_TYPE_BIT
+            | DUCKDB_TYPE_DUCKDB_TYPE_LIST
+            | DUCKDB_TYPE_DUCKDB_TYPE_MAP => 16,
+            DUCKDB_TYPE_DUCKDB_TYPE_DECIMAL => match self.decimal_width() {
+                1..=4 => 2,
+                5..=9 => 4,
+                10..=18 => 8,
+                _ => 16,
+            },
+            DUCKDB_TYPE_DUCKDB_TYPE_ENUM => match unsafe { duckdb_enum_internal_type(self.ptr) } {
+                DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => 1,
+                DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => 2,
+                _ => 4,
+            },
+            _ => return None,
+        })
+    }
+
     /// Make a `LogicalType` for `struct`
     pub fn struct_type(fields: &[(&str, LogicalType)]) -> Self {
         let keys: Vec<CString> = fields.iter().map(|f| CString::new(f.0).unwrap()).collect();
@@ -305,6 +389,19 @@ mod test {
         assert_eq!(typ.child(0).id(), crate::vtab::LogicalTypeId::Boolean);
     }
 
+    #[test]
+    fn test_enum() {
+        let typ = LogicalType::enum_type(&["a", "b", "c"]).unwrap();
+
+        assert_eq!(typ.id(), LogicalTypeId::Enum);
+        assert_eq!(typ.enum_labels(), ["a", "b", "c"]);
+        assert!(LogicalType::new(LogicalTypeId::Varchar).enum_labels().is_empty());
+        assert!(matches!(
+            LogicalType::enum_type(&["a", "b\0c"]),
+            Err(crate::Error::NulError(_))
+        ));
+    }
+
     #[test]
     fn test_decimal() {
         let typ = LogicalType::decimal(10, 2);
@@ -322,6 +419,21 @@ mod test {
         assert_eq!(typ.decimal_scale(), 0);
     }
 
+    #[test]
+    fn test_physical_width() {
+        assert_eq!(LogicalType::new(LogicalTypeId::Tinyint).physical_width(), Some(1));
+        assert_eq!(LogicalType::new(LogicalTypeId::USmallint).physical_width(), Some(2));
+        assert_eq!(LogicalType::new(LogicalTypeId::Date).physical_width(), Some(4));
+        assert_eq!(LogicalType::new(LogicalTypeId::TimestampNs).physical_width(), Some(8));
+        assert_eq!(LogicalType::new(LogicalTypeId::Varchar).physical_width(), Some(16));
+        assert_eq!(LogicalType::decimal(4, 1).physical_width(), Some(2));
+        assert_eq!(LogicalType::decimal(18, 3).physical_width(), Some(8));
+        assert_eq!(LogicalType::decimal(38, 10).physical_width(), Some(16));
+
+        let fields = &[("a", LogicalType::new(LogicalTypeId::Integer))];
+        assert_eq!(LogicalType::struct_type(fields).physical_width(), None);
+    }
+
     #[test]
     fn test_union_type() {
         let fields = &[
@@ -1,7 +1,10 @@
 use crate::{error::Error, inner_connection::InnerConnection, Connection, Result};
 
 use super::{ffi, ffi::duckdb_free};
-use std::ffi::c_void;
+use std::{
+    ffi::{c_char, c_void, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+};
 
 mod data_chunk;
 mod function;
@@ -24,7 +27,7 @@ pub use data_chunk::DataChunk;
 pub use function::{BindInfo, FunctionInfo, InitInfo, TableFunction};
 pub use logical_type::{LogicalType, LogicalTypeId};
 pub use value::Value;
-pub use vector::{FlatVector, Inserter, ListVector, StructVector, Vector};
+pub use vector::{EnumVector, FlatVector, Inserter, ListVector, MapVector, StructVector, Vector};
 
 use ffi::{duckdb_bind_info, duckdb_data_chunk, duckdb_function_info, duckdb_init_info};
 
@@ -58,7 +61,8 @@ pub trait Free {
 
 /// Duckdb table function trait
 ///
-/// See to the HelloVTab example for more details
+/// See the `table_function` example, which serves rows from a `Vec`, and the
+/// HelloVTab example for more details
 /// <https://duckdb.org/docs/api/c/table_functions>
 pub trait VTab: Sized {
     /// The data type of the bind data
@@ -111,6 +115,13 @@ pub trait VTab: Sized {
     fn supports_pushdown() -> bool {
         false
     }
+    /// The number of rows the table function returns for the given bind
+    /// data, which helps the optimizer, e.g. to plan joins. Called once
+    /// [`bind`](VTab::bind) succeeded.
+    /// default is None, in which case DuckDB uses its own default estimate
+    fn cardinality(_data: &Self::BindData) -> Option<Cardinality> {
+        None
+    }
     /// The parameters of the table function
     /// default is None
     fn parameters() -> Option<Vec<LogicalType>> {
@@ -123,13 +134,35 @@ pub trait VTab: Sized {
     }
 }
 
+/// The number of rows a table function returns, see [`VTab::cardinality`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cardinality {
+    /// Exactly this many rows
+    Exact(u64),
+    /// About this many rows
+    Estimate(u64),
+}
+
+/// Runs a table function callback, turning a panic into an error, as unwinding
+/// into DuckDB would abort the process.
+fn catch_panic(f: impl FnOnce() -> Result<(), Box<dyn std::error::Error>>) -> Result<(), Box<dyn std::error::Error>> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+        Err(format!("table function panicked: {message}").into())
+    })
+}
+
 unsafe extern "C" fn func<T>(info: duckdb_function_info, output: duckdb_data_chunk)
 where
     T: VTab,
 {
     let info = FunctionInfo::from(info);
     let mut output = DataChunk::from(output);
-    let result = T::func(&info, &mut output);
+    let result = catch_panic(|| T::func(&info, &mut output));
     if result.is_err() {
         info.set_error(&result.err().unwrap().to_string());
     }
@@ -141,7 +174,7 @@ where
 {
     let info = InitInfo::from(info);
     let data = malloc_data_c::<T::InitData>();
-    let result = T::init(&info, data);
+    let result = catch_panic(|| T::init(&info, data));
     info.set_init_data(data.cast(), Some(drop_data_c::<T::InitData>));
     if result.is_err() {
         info.set_error(&result.err().unwrap().to_string());
@@ -154,7 +187,15 @@ where
 {
     let info = BindInfo::from(info);
     let data = malloc_data_c::<T::BindData>();
-    let result = T::bind(&info, data);
+    let result = catch_panic(|| {
+        T::bind(&info, data)?;
+        match T::cardinality(&*data) {
+            Some(Cardinality::Exact(rows)) => info.set_cardinality(rows, true),
+            Some(Cardinality::Estimate(rows)) => info.set_cardinality(rows, false),
+            None => {}
+        }
+        Ok(())
+    });
     info.set_bind_data(data.cast(), Some(drop_data_c::<T::BindData>));
     if result.is_err() {
         info.set_error(&result.err().unwrap().to_string());
@@ -182,6 +223,141 @@ impl Connection {
     }
 }
 
+/// A call of a table function, which a replacement scan resolves a table name
+/// to, see [`Connection::register_replacement_scan_with`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableFunctionCall {
+    /// The name of the table function
+    pub function_name: String,
+    /// The arguments of the call. Only text and integers that fit in an `i64`
+    /// are supported; they are passed as `VARCHAR` and `BIGINT`.
+    pub parameters: Vec<crate::types::Value>,
+}
+
+type ReplacementScan = Box<dyn Fn(&str) -> Option<TableFunctionCall> + Send + Sync>;
+
+unsafe extern "C" fn replacement_scan(
+    info: ffi::duckdb_replacement_scan_info,
+    table_name: *const c_char,
+    data: *mut c_void,
+) {
+    let scan = &*(data as *const ReplacementScan);
+    let table_name = CStr::from_ptr(table_name).to_string_lossy();
+    let call = match panic::catch_unwind(AssertUnwindSafe(|| scan(&table_name))) {
+        Ok(Some(call)) => call,
+        Ok(None) => return,
+        Err(_) => return set_replacement_scan_error(info, "replacement scan panicked"),
+    };
+    let Ok(function_name) = CString::new(call.function_name) else {
+        return set_replacement_scan_error(info, "the function name contains a nul byte");
+    };
+    ffi::duckdb_replacement_scan_set_function_name(info, function_name.as_ptr());
+    for parameter in &call.parameters {
+        let Some(mut value) = replacement_scan_parameter(parameter) else {
+            let message = format!("unsupported replacement scan parameter: {parameter:?}");
+            return set_replacement_scan_error(info, &message);
+        };
+        ffi::duckdb_replacement_scan_add_parameter(info, value);
+        ffi::duckdb_destroy_value(&mut value);
+    }
+}
+
+unsafe fn replacement_scan_parameter(parameter: &crate::types::Value) -> Option<ffi::duckdb_value> {
+    use crate::types::Value::*;
+    let int = match *parameter {
+        Text(ref s) => return Some(ffi::duckdb_create_varchar_length(s.as_ptr().cast(), s.len() as u64)),
+        TinyInt(i) => i64::from(i),
+        SmallInt(i) => i64::from(i),
+        Int(i) => i64::from(i),
+        BigInt(i) => i,
+        UTinyInt(i) => i64::from(i),
+        USmallInt(i) => i64::from(i),
+        UInt(i) => i64::from(i),
+        UBigInt(i) => i64::try_from(i).ok()?,
+        _ => return None,
+    };
+    Some(ffi::duckdb_create_int64(int))
+}
+
+unsafe fn set_replacement_scan_error(info: ffi::duckdb_replacement_scan_info, message: &str) {
+    let message = CString::new(message.replace('\0', "")).unwrap();
+    ffi::duckdb_replacement_scan_set_error(info, message.as_ptr());
+}
+
+unsafe extern "C" fn drop_replacement_scan(data: *mut c_void) {
+    drop(Box::from_raw(data as *mut ReplacementScan));
+}
+
+impl Connection {
+    /// Resolve `SELECT * FROM table_name` to the table function `function_name`, called with
+    /// `parameters` as its `VARCHAR` arguments.
+    ///
+    /// This is the usual way to expose a Rust-side dataset under a plain table name: register
+    /// a [`VTab`] with [`Connection::register_table_function`] and map the name to it here.
+    ///
+    /// Replacement scans are only consulted for names that are not found in the catalog, so a
+    /// real table or view called `table_name` always takes precedence. The scan is registered
+    /// on the database, which means it is visible to every connection to it and lasts until
+    /// the database is closed.
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn query_dataset(conn: &Connection) -> Result<usize> {
+    ///     // `read_dataset` is a table function taking the dataset name
+    ///     conn.register_replacement_scan("my_dataset", "read_dataset", &["my_dataset"])?;
+    ///     conn.query_row("SELECT count(*) FROM my_dataset", [], |row| row.get(0))
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return [`Error::NulError`](crate::Error::NulError) if a name or
+    /// parameter contains a nul byte, without registering the scan.
+    pub fn register_replacement_scan(&self, table_name: &str, function_name: &str, parameters: &[&str]) -> Result<()> {
+        for s in [table_name, function_name].iter().chain(parameters) {
+            CString::new(*s)?;
+        }
+        let table_name = table_name.to_owned();
+        let call = TableFunctionCall {
+            function_name: function_name.to_owned(),
+            parameters: parameters
+                .iter()
+                .map(|p| crate::types::Value::Text(p.to_string()))
+                .collect(),
+        };
+        // Identifiers are case insensitive, so `FROM My_Dataset` resolves as well
+        self.register_replacement_scan_with(move |name| name.eq_ignore_ascii_case(&table_name).then(|| call.clone()));
+        Ok(())
+    }
+
+    /// Resolve the names of tables that are not found in the catalog with `scan`, which returns
+    /// the table function call to read instead, or `None` to leave the name unresolved, e.g. to
+    /// let another replacement scan resolve it.
+    ///
+    /// As with [`register_replacement_scan`](Connection::register_replacement_scan), the scan is
+    /// visible to every connection to the database, and may be called from any of their threads.
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{types::Value, vtab::TableFunctionCall, Connection, Result};
+    /// fn query_datasets(conn: &Connection) -> Result<usize> {
+    ///     // `read_dataset` is a table function taking the dataset name
+    ///     conn.register_replacement_scan_with(|name| {
+    ///         name.starts_with("dataset_").then(|| TableFunctionCall {
+    ///             function_name: "read_dataset".to_owned(),
+    ///             parameters: vec![Value::Text(name.to_owned())],
+    ///         })
+    ///     });
+    ///     conn.query_row("SELECT count(*) FROM dataset_1", [], |row| row.get(0))
+    /// }
+    /// ```
+    pub fn register_replacement_scan_with<F>(&self, scan: F)
+    where
+        F: Fn(&str) -> Option<TableFunctionCall> + Send + Sync + 'static,
+    {
+        self.db.borrow_mut().add_replacement_scan(Box::new(scan));
+    }
+}
+
 impl InnerConnection {
     /// Register the given TableFunction with the current db
     pub fn register_table_function(&mut self, table_function: TableFunction) -> Result<()> {
@@ -193,11 +369,23 @@ impl InnerConnection {
         }
         Ok(())
     }
+
+    fn add_replacement_scan(&mut self, scan: ReplacementScan) {
+        unsafe {
+            ffi::duckdb_add_replacement_scan(
+                self.db,
+                Some(replacement_scan),
+                Box::into_raw(Box::new(scan)) as *mut c_void,
+                Some(drop_replacement_scan),
+            );
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::types::TimeWithOffset;
     use std::{
         error::Error,
         ffi::{c_char, CString},
@@ -302,6 +490,272 @@ mod test {
         }
     }
 
+    struct PanickingVTab;
+
+    impl VTab for PanickingVTab {
+        type InitData = HelloInitData;
+        type BindData = HelloInitData;
+
+        unsafe fn bind(bind: &BindInfo, _: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            // only one parameter is declared
+            bind.get_parameter(1);
+            Ok(())
+        }
+
+        unsafe fn init(_: &InitInfo, _: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            Ok(())
+        }
+
+        unsafe fn func(_: &FunctionInfo, _: &mut DataChunk) -> Result<(), Box<dyn std::error::Error>> {
+            Ok(())
+        }
+
+        fn parameters() -> Option<Vec<LogicalType>> {
+            Some(vec![LogicalType::new(LogicalTypeId::Varchar)])
+        }
+    }
+
+    const UUIDS: [&str; 4] = [
+        "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11",
+        "00000000-0000-0000-0000-000000000000",
+        "ffffffff-ffff-ffff-ffff-ffffffffffff",
+        "7fffffff-ffff-ffff-ffff-ffffffffffff",
+    ];
+
+    struct PrimitiveVTab;
+
+    impl VTab for PrimitiveVTab {
+        type InitData = HelloInitData;
+        type BindData = HelloInitData;
+
+        unsafe fn bind(bind: &BindInfo, _: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            bind.add_result_column("flag", LogicalType::new(LogicalTypeId::Boolean));
+            bind.add_result_column("n", LogicalType::new(LogicalTypeId::Bigint));
+            bind.add_result_column("x", LogicalType::new(LogicalTypeId::Float));
+            bind.add_result_column("t", LogicalType::new(LogicalTypeId::TimeTZ));
+            bind.add_result_column("u", LogicalType::new(LogicalTypeId::Uuid));
+            Ok(())
+        }
+
+        unsafe fn init(info: &InitInfo, data: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            HelloVTab::init(info, data)
+        }
+
+        unsafe fn func(func: &FunctionInfo, output: &mut DataChunk) -> Result<(), Box<dyn std::error::Error>> {
+            let init_info = func.get_init_data::<HelloInitData>();
+            if (*init_info).done {
+                output.set_len(0);
+                return Ok(());
+            }
+            (*init_info).done = true;
+            let (flags, numbers, floats) = (output.flat_vector(0), output.flat_vector(1), output.flat_vector(2));
+            let (times, uuids) = (output.flat_vector(3), output.flat_vector(4));
+            for (i, uuid) in UUIDS.iter().enumerate() {
+                flags.insert(i, i % 2 == 0);
+                numbers.insert(i, i as i64 * 10);
+                floats.insert(i, i as f32 / 2.0);
+                let time = TimeWithOffset {
+                    micros: i as i64 * 3_600_000_000,
+                    offset_secs: (i as i32 - 2) * 1800,
+                };
+                times.insert(i, time);
+                uuids.insert_uuid(i, u128::from_str_radix(&uuid.replace('-', ""), 16)?);
+            }
+            output.set_len(4);
+            Ok(())
+        }
+    }
+
+    struct MapVTab;
+
+    impl VTab for MapVTab {
+        type InitData = HelloInitData;
+        type BindData = HelloInitData;
+
+        unsafe fn bind(bind: &BindInfo, _: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            let map = LogicalType::map(
+                &LogicalType::new(LogicalTypeId::Varchar),
+                &LogicalType::new(LogicalTypeId::Integer),
+            );
+            bind.add_result_column("m", map);
+            Ok(())
+        }
+
+        unsafe fn init(info: &InitInfo, data: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            HelloVTab::init(info, data)
+        }
+
+        unsafe fn func(func: &FunctionInfo, output: &mut DataChunk) -> Result<(), Box<dyn std::error::Error>> {
+            let init_info = func.get_init_data::<HelloInitData>();
+            if (*init_info).done {
+                output.set_len(0);
+                return Ok(());
+            }
+            (*init_info).done = true;
+            let mut maps = output.map_vector(0);
+            let (keys, values) = (maps.keys(2), maps.values(2));
+            keys.insert(0, "a");
+            keys.insert(1, "b");
+            values.insert(0, 1);
+            values.insert(1, 2);
+            maps.set_entry(0, 0, 2);
+            maps.set_null(1);
+            maps.set_len(2);
+            output.set_len(2);
+            Ok(())
+        }
+    }
+
+    struct EnumVTab;
+
+    impl VTab for EnumVTab {
+        type InitData = HelloInitData;
+        type BindData = HelloInitData;
+
+        unsafe fn bind(bind: &BindInfo, _: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            bind.add_result_column("status", LogicalType::enum_type(&["ACTIVE", "INACTIVE"])?);
+            Ok(())
+        }
+
+        unsafe fn init(info: &InitInfo, data: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            HelloVTab::init(info, data)
+        }
+
+        unsafe fn func(func: &FunctionInfo, output: &mut DataChunk) -> Result<(), Box<dyn std::error::Error>> {
+            let init_info = func.get_init_data::<HelloInitData>();
+            if (*init_info).done {
+                output.set_len(0);
+                return Ok(());
+            }
+            (*init_info).done = true;
+            let statuses = output.enum_vector(0);
+            for (i, status) in ["INACTIVE", "ACTIVE", "INACTIVE"].into_iter().enumerate() {
+                statuses.insert_label(i, status)?;
+            }
+            output.set_len(3);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_table_function_enum() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<EnumVTab>("statuses")?;
+
+        let mut stmt = conn.prepare("SELECT status::VARCHAR FROM statuses() WHERE status = 'INACTIVE'")?;
+        let rows = stmt.iter::<String>([])?.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, ["INACTIVE", "INACTIVE"]);
+        Ok(())
+    }
+
+    struct ArrayVTab;
+
+    impl VTab for ArrayVTab {
+        type InitData = HelloInitData;
+        type BindData = HelloInitData;
+
+        unsafe fn bind(bind: &BindInfo, _: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            let array = LogicalType::array(&LogicalType::new(LogicalTypeId::Double), 4);
+            bind.add_result_column("a", array);
+            Ok(())
+        }
+
+        unsafe fn init(info: &InitInfo, data: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            HelloVTab::init(info, data)
+        }
+
+        unsafe fn func(func: &FunctionInfo, output: &mut DataChunk) -> Result<(), Box<dyn std::error::Error>> {
+            let init_info = func.get_init_data::<HelloInitData>();
+            if (*init_info).done {
+                output.set_len(0);
+                return Ok(());
+            }
+            (*init_info).done = true;
+            let mut arrays = output.array_vector(0);
+            arrays.set_child(&[1.0, f64::NAN, 3.0, 4.0, 0.0, 0.0, 0.0, 0.0, 5.0, 6.0, 7.0, 8.0])?;
+            arrays.set_child_null(1);
+            arrays.set_null(1);
+            arrays.set_child_null(11);
+            output.set_len(3);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_table_function_array_nulls() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<ArrayVTab>("arrays")?;
+
+        let mut stmt = conn.prepare("SELECT a::VARCHAR FROM arrays()")?;
+        let rows = stmt.iter::<Option<String>>([])?.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            rows,
+            [
+                Some("[1.0, NULL, 3.0, 4.0]".to_owned()),
+                None,
+                Some("[5.0, 6.0, 7.0, NULL]".to_owned())
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_function_map() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<MapVTab>("maps")?;
+
+        let mut stmt = conn.prepare("SELECT m::VARCHAR FROM maps()")?;
+        let rows = stmt.iter::<Option<String>>([])?.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, [Some("{a=1, b=2}".to_owned()), None]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_function_primitive_insert() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<PrimitiveVTab>("primitives")?;
+
+        let mut stmt = conn.prepare("SELECT flag, n, x, t::VARCHAR FROM primitives()")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+            .collect::<Result<Vec<(bool, i64, f32, String)>, _>>()?;
+        assert_eq!(
+            rows,
+            [
+                (true, 0, 0.0, "00:00:00-01".to_owned()),
+                (false, 10, 0.5, "01:00:00-00:30".to_owned()),
+                (true, 20, 1.0, "02:00:00+00".to_owned()),
+                (false, 30, 1.5, "03:00:00+00:30".to_owned()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_function_uuid() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<PrimitiveVTab>("primitives")?;
+
+        let mut stmt = conn.prepare("SELECT u::VARCHAR FROM primitives() ORDER BY u")?;
+        let rows = stmt.iter::<String>([])?.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, [UUIDS[1], UUIDS[3], UUIDS[0], UUIDS[2]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_function_panic() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<PanickingVTab>("panicking")?;
+
+        let err = conn
+            .query_row("SELECT * FROM panicking('x')", [], |_| Ok(()))
+            .unwrap_err();
+        assert!(err.to_string().contains("1 is out of range"), "{err}");
+        // the connection is still usable
+        assert_eq!(conn.query_row("SELECT 1", [], |r| r.get::<_, i32>(0))?, 1);
+        Ok(())
+    }
+
     #[test]
     fn test_table_function() -> Result<(), Box<dyn Error>> {
         let conn = Connection::open_in_memory()?;
@@ -313,6 +767,55 @@ mod test {
         Ok(())
     }
 
+    struct HelloWithCardinalityVTab;
+
+    impl VTab for HelloWithCardinalityVTab {
+        type InitData = HelloInitData;
+        type BindData = HelloBindData;
+
+        unsafe fn bind(bind: &BindInfo, data: *mut HelloBindData) -> Result<(), Box<dyn std::error::Error>> {
+            HelloVTab::bind(bind, data)
+        }
+
+        unsafe fn init(init: &InitInfo, data: *mut HelloInitData) -> Result<(), Box<dyn std::error::Error>> {
+            HelloVTab::init(init, data)
+        }
+
+        unsafe fn func(func: &FunctionInfo, output: &mut DataChunk) -> Result<(), Box<dyn std::error::Error>> {
+            HelloVTab::func(func, output)
+        }
+
+        fn cardinality(_data: &HelloBindData) -> Option<Cardinality> {
+            Some(Cardinality::Estimate(12345))
+        }
+
+        fn parameters() -> Option<Vec<LogicalType>> {
+            HelloVTab::parameters()
+        }
+    }
+
+    #[test]
+    fn test_table_function_cardinality() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<HelloVTab>("hello")?;
+        conn.register_table_function::<HelloWithCardinalityVTab>("hello_cardinality")?;
+
+        let explain = |sql: &str| -> Result<String, crate::Error> {
+            conn.prepare(&format!("EXPLAIN {sql}"))?
+                .query_map([], |row| row.get::<_, String>(1))?
+                .collect()
+        };
+        let plan = explain("SELECT * FROM hello_cardinality('duckdb')")?;
+        assert!(plan.contains("EC: 12345"), "{plan}");
+        assert!(!explain("SELECT * FROM hello('duckdb')")?.contains("EC: 12345"));
+
+        let val = conn.query_row("SELECT * FROM hello_cardinality('duckdb')", [], |row| {
+            row.get::<_, String>(0)
+        })?;
+        assert_eq!(val, "Hello duckdb");
+        Ok(())
+    }
+
     #[test]
     fn test_named_table_function() -> Result<(), Box<dyn Error>> {
         let conn = Connection::open_in_memory()?;
@@ -326,6 +829,73 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_replacement_scan() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<HelloVTab>("hello")?;
+        conn.register_replacement_scan("greeting", "hello", &["duckdb"])?;
+        conn.register_replacement_scan("shadowed", "hello", &["replacement"])?;
+        conn.execute_batch("CREATE TABLE shadowed AS SELECT 'Hello table' AS column0")?;
+
+        let val: String = conn.query_row("SELECT * FROM greeting", [], |row| row.get(0))?;
+        assert_eq!(val, "Hello duckdb");
+        let val: String = conn.query_row("SELECT * FROM GREETING", [], |row| row.get(0))?;
+        assert_eq!(val, "Hello duckdb");
+
+        // Tables in the catalog take precedence over replacement scans
+        let val: String = conn.query_row("SELECT * FROM shadowed", [], |row| row.get(0))?;
+        assert_eq!(val, "Hello table");
+
+        assert!(conn.prepare("SELECT * FROM unknown_dataset").is_err());
+
+        let err = conn.register_replacement_scan("nul", "hel\0lo", &[]).unwrap_err();
+        assert!(matches!(err, crate::Error::NulError(_)), "{err:?}");
+        let err = conn.register_replacement_scan("nul", "hello", &["a\0b"]).unwrap_err();
+        assert!(matches!(err, crate::Error::NulError(_)), "{err:?}");
+        assert!(conn.prepare("SELECT * FROM nul").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_replacement_scan_with() -> Result<(), Box<dyn Error>> {
+        use crate::types::Value;
+
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<HelloVTab>("hello")?;
+        conn.register_replacement_scan_with(|name| {
+            let name = name.strip_prefix("hello_")?;
+            Some(TableFunctionCall {
+                function_name: "hello".to_owned(),
+                parameters: vec![Value::Text(name.to_owned())],
+            })
+        });
+        conn.register_replacement_scan_with(|name| {
+            (name == "numbered").then(|| TableFunctionCall {
+                function_name: "range".to_owned(),
+                parameters: vec![Value::Int(42)],
+            })
+        });
+        conn.register_replacement_scan_with(|name| {
+            (name == "blob").then(|| TableFunctionCall {
+                function_name: "hello".to_owned(),
+                parameters: vec![Value::Blob(vec![1])],
+            })
+        });
+
+        let val: String = conn.query_row("SELECT * FROM hello_world", [], |row| row.get(0))?;
+        assert_eq!(val, "Hello world");
+        // the first scan returns `None`, so the second one is consulted
+        let val: i64 = conn.query_row("SELECT count(*) FROM numbered", [], |row| row.get(0))?;
+        assert_eq!(val, 42);
+        let err = conn.prepare("SELECT * FROM blob").unwrap_err();
+        assert!(
+            err.to_string().contains("unsupported replacement scan parameter"),
+            "{err}"
+        );
+        assert!(conn.prepare("SELECT * FROM unknown_dataset").is_err());
+        Ok(())
+    }
+
     #[cfg(feature = "vtab-loadable")]
     use duckdb_loadable_macros::duckdb_entrypoint;
 
@@ -1,16 +1,21 @@
-use std::{any::Any, ffi::CString, slice};
+use std::{any::Any, collections::HashMap, ffi::CString, iter, mem, slice};
 
 use libduckdb_sys::{duckdb_array_type_array_size, duckdb_array_vector_get_child};
 
-use super::LogicalType;
+use super::{LogicalType, LogicalTypeId};
 use crate::ffi::{
-    duckdb_list_entry, duckdb_list_vector_get_child, duckdb_list_vector_get_size, duckdb_list_vector_reserve,
-    duckdb_list_vector_set_size, duckdb_struct_type_child_count, duckdb_struct_type_child_name,
-    duckdb_struct_vector_get_child, duckdb_validity_set_row_invalid, duckdb_vector,
+    duckdb_data_chunk, duckdb_data_chunk_get_size, duckdb_list_entry, duckdb_list_vector_get_child,
+    duckdb_list_vector_get_size, duckdb_list_vector_reserve, duckdb_list_vector_set_size, duckdb_string_is_inlined,
+    duckdb_string_t, duckdb_struct_type_child_count, duckdb_struct_type_child_name, duckdb_struct_vector_get_child,
+    duckdb_validity_row_is_valid, duckdb_validity_set_row_invalid, duckdb_validity_set_row_validity, duckdb_vector,
     duckdb_vector_assign_string_element, duckdb_vector_assign_string_element_len,
     duckdb_vector_ensure_validity_writable, duckdb_vector_get_column_type, duckdb_vector_get_data,
     duckdb_vector_get_validity, duckdb_vector_size,
 };
+use crate::{
+    types::{TimeWithOffset, Type},
+    Error, Result,
+};
 
 /// Vector trait.
 pub trait Vector {
@@ -24,6 +29,8 @@ pub trait Vector {
 pub struct FlatVector {
     ptr: duckdb_vector,
     capacity: usize,
+    /// The chunk this vector is a column of, if known.
+    chunk: Option<duckdb_data_chunk>,
 }
 
 impl From<duckdb_vector> for FlatVector {
@@ -31,6 +38,7 @@ impl From<duckdb_vector> for FlatVector {
         Self {
             ptr,
             capacity: unsafe { duckdb_vector_size() as usize },
+            chunk: None,
         }
     }
 }
@@ -47,7 +55,18 @@ impl Vector for FlatVector {
 
 impl FlatVector {
     fn with_capacity(ptr: duckdb_vector, capacity: usize) -> Self {
-        Self { ptr, capacity }
+        Self {
+            ptr,
+            capacity,
+            chunk: None,
+        }
+    }
+
+    pub(crate) fn in_chunk(ptr: duckdb_vector, chunk: duckdb_data_chunk) -> Self {
+        Self {
+            chunk: Some(chunk),
+            ..Self::from(ptr)
+        }
     }
 
     /// Returns the capacity of the vector
@@ -61,20 +80,189 @@ impl FlatVector {
     }
 
     /// Returns a slice of the vector
+    ///
+    /// `T` isn't checked against the type of the vector in release builds, so
+    /// the wrong `T` reads garbage; prefer [`try_as_slice`](FlatVector::try_as_slice).
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the vector has fixed-width elements of
+    /// another size than `T`.
     pub fn as_slice<T>(&self) -> &[T] {
+        self.debug_assert_width::<T>();
         unsafe { slice::from_raw_parts(self.as_mut_ptr(), self.capacity()) }
     }
 
+    /// Returns a slice of the vector, or `None` if the elements of the vector
+    /// don't have the size of `T`, e.g. for an `i32` slice of a `BIGINT`
+    /// vector, or have no fixed width, as in a `STRUCT` vector.
+    pub fn try_as_slice<T: Copy>(&self) -> Option<&[T]> {
+        if self.logical_type().physical_width() != Some(mem::size_of::<T>()) {
+            return None;
+        }
+        Some(unsafe { slice::from_raw_parts(self.as_mut_ptr(), self.capacity()) })
+    }
+
     /// Returns a mutable slice of the vector
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds as [`as_slice`](FlatVector::as_slice) does.
     pub fn as_mut_slice<T>(&mut self) -> &mut [T] {
+        self.debug_assert_width::<T>();
         unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.capacity()) }
     }
 
+    fn debug_assert_width<T>(&self) {
+        if cfg!(debug_assertions) && self.logical_type().physical_width().is_some() {
+            self.assert_width::<T>("read", "from");
+        }
+    }
+
+    /// Returns a mutable slice of the vector, as long as the current length of
+    /// the [`DataChunk`](super::DataChunk) it belongs to. A new chunk has a
+    /// length of 0, so the slice is empty until
+    /// [`DataChunk::set_len`](super::DataChunk::set_len) is called: set the
+    /// length of the chunk before writing to it.
+    ///
+    /// # Warning
+    ///
+    /// The length of a vector that wasn't obtained from a chunk, such as the
+    /// child of a [`ListVector`], is not known. This then falls back to
+    /// [`as_mut_slice`](FlatVector::as_mut_slice), covering the whole capacity.
+    pub fn as_mut_slice_with_len<T>(&mut self) -> &mut [T] {
+        self.debug_assert_width::<T>();
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len()) }
+    }
+
+    /// The length of the chunk the vector belongs to, or its capacity if that
+    /// isn't known.
+    fn len(&self) -> usize {
+        match self.chunk {
+            Some(chunk) => unsafe { duckdb_data_chunk_get_size(chunk) as usize }.min(self.capacity()),
+            None => self.capacity(),
+        }
+    }
+
     /// Returns the logical type of the vector
     pub fn logical_type(&self) -> LogicalType {
         LogicalType::from(unsafe { duckdb_vector_get_column_type(self.ptr) })
     }
 
+    /// Returns true if the row is null
+    pub fn row_is_null(&self, row: usize) -> bool {
+        unsafe { !duckdb_validity_row_is_valid(duckdb_vector_get_validity(self.ptr), row as u64) }
+    }
+
+    /// Returns the validity mask of the vector, or `None` if every row is
+    /// valid.
+    ///
+    /// The mask holds one bit per row, set if the row is valid and clear if it
+    /// is null: row `i` is at bit `i % 64` of entry `i / 64`. It covers the
+    /// whole capacity of the vector, so bits past the length of the chunk are
+    /// meaningless.
+    pub fn validity_slice(&self) -> Option<&[u64]> {
+        let validity = unsafe { duckdb_vector_get_validity(self.ptr) };
+        if validity.is_null() {
+            return None;
+        }
+        Some(unsafe { slice::from_raw_parts(validity, self.capacity().div_ceil(64)) })
+    }
+
+    /// Returns an iterator over the indices of the null rows, in order.
+    ///
+    /// Rows past the length of the [`DataChunk`](super::DataChunk) the vector
+    /// belongs to are skipped; see [`as_mut_slice_with_len`](FlatVector::as_mut_slice_with_len).
+    pub fn nulls(&self) -> impl Iterator<Item = usize> + '_ {
+        let len = self.len();
+        self.validity_slice()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &entry)| {
+                let mut nulls = !entry;
+                iter::from_fn(move || {
+                    if nulls == 0 {
+                        return None;
+                    }
+                    let bit = nulls.trailing_zeros() as usize;
+                    nulls &= nulls - 1;
+                    Some(i * 64 + bit)
+                })
+            })
+            .take_while(move |&row| row < len)
+    }
+
+    /// Returns the `VARCHAR` or `BLOB` at `row`, or `None` if it is null.
+    ///
+    /// The bytes are borrowed from the vector: strings of up to 12 bytes are
+    /// stored inline in the vector, and longer ones in a buffer it owns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds, or the vector isn't a `VARCHAR` or
+    /// `BLOB` vector.
+    pub fn get_bytes(&self, row: usize) -> Option<&[u8]> {
+        assert!(row < self.capacity());
+        let id = self.logical_type().id();
+        assert!(
+            matches!(id, LogicalTypeId::Varchar | LogicalTypeId::Blob),
+            "cannot read bytes from a {id:?} vector"
+        );
+        if self.row_is_null(row) {
+            return None;
+        }
+        unsafe {
+            let string = &*self.as_mut_ptr::<duckdb_string_t>().add(row);
+            let len = string.value.inlined.length as usize;
+            let data = if duckdb_string_is_inlined(*string) {
+                string.value.inlined.inlined.as_ptr()
+            } else {
+                string.value.pointer.ptr
+            };
+            Some(slice::from_raw_parts(data.cast(), len))
+        }
+    }
+
+    /// Returns the `VARCHAR` at `row`, or `None` if it is null or not valid
+    /// UTF-8. See [`get_bytes`](FlatVector::get_bytes).
+    pub fn get_str(&self, row: usize) -> Option<&str> {
+        self.get_bytes(row).and_then(|bytes| std::str::from_utf8(bytes).ok())
+    }
+
+    /// Returns the `UUID` at `row`, or `None` if it is null. The value is the
+    /// UUID's bytes as a big-endian integer, as by `uuid::Uuid::as_u128`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds, or the vector isn't a `UUID` vector.
+    pub fn get_uuid(&self, row: usize) -> Option<u128> {
+        assert!(row < self.capacity());
+        self.assert_uuid();
+        if self.row_is_null(row) {
+            return None;
+        }
+        let stored = unsafe { self.as_mut_ptr::<u128>().add(row).read() };
+        Some(stored ^ UUID_SIGN_BIT)
+    }
+
+    /// Writes the `UUID` at `row`, given as a big-endian integer as by
+    /// `uuid::Uuid::as_u128`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds, or the vector isn't a `UUID` vector.
+    pub fn insert_uuid(&self, row: usize, uuid: u128) {
+        assert!(row < self.capacity());
+        self.assert_uuid();
+        unsafe { self.as_mut_ptr::<u128>().add(row).write(uuid ^ UUID_SIGN_BIT) };
+    }
+
+    fn assert_uuid(&self) {
+        let id = self.logical_type().id();
+        assert_eq!(id, LogicalTypeId::Uuid, "not a UUID vector: {id:?}");
+    }
+
     /// Set row as null
     pub fn set_null(&mut self, row: usize) {
         unsafe {
@@ -85,12 +273,77 @@ impl FlatVector {
     }
 
     /// Copy data to the vector.
-    pub fn copy<T: Copy>(&mut self, data: &[T]) {
-        assert!(data.len() <= self.capacity());
-        self.as_mut_slice::<T>()[0..data.len()].copy_from_slice(data);
+    ///
+    /// # Failure
+    ///
+    /// Will return [`Error::InvalidVectorWrite`] if `data` is longer than the
+    /// capacity, or if the size of `T` does not match the width of the
+    /// vector's elements, e.g. copying `i32`s into a `TINYINT` vector.
+    pub fn copy<T: Copy>(&mut self, data: &[T]) -> Result<()> {
+        if data.len() > self.capacity() {
+            return Err(Error::InvalidVectorWrite(format!(
+                "cannot copy {} values into a vector of capacity {}",
+                data.len(),
+                self.capacity()
+            )));
+        }
+        if let Some(msg) = self.width_mismatch::<T>("copy", "into") {
+            return Err(Error::InvalidVectorWrite(msg));
+        }
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), data.len()) }.copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Describes the mismatch if the size of `T` isn't the width of the
+    /// elements, looking up the logical type once.
+    fn width_mismatch<T>(&self, action: &str, preposition: &str) -> Option<String> {
+        let width = self.logical_type().physical_width();
+        (width != Some(mem::size_of::<T>())).then(|| {
+            format!(
+                "cannot {action} {}-byte values {preposition} a vector of {}-byte elements",
+                mem::size_of::<T>(),
+                width.unwrap_or(0),
+            )
+        })
+    }
+
+    fn assert_width<T>(&self, action: &str, preposition: &str) {
+        if let Some(msg) = self.width_mismatch::<T>(action, preposition) {
+            panic!("{msg}");
+        }
+    }
+
+    /// Copy data to the vector, setting the rows where `valid` is `false` as
+    /// null. The values at those rows are copied too, but are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` and `valid` have different lengths.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` as [`copy`](FlatVector::copy) does.
+    pub fn copy_with_validity<T: Copy>(&mut self, data: &[T], valid: &[bool]) -> Result<()> {
+        assert_eq!(data.len(), valid.len(), "data and validity have different lengths");
+        self.copy(data)?;
+        if valid.iter().all(|&v| v) {
+            return Ok(());
+        }
+        unsafe {
+            duckdb_vector_ensure_validity_writable(self.ptr);
+            let validity = duckdb_vector_get_validity(self.ptr);
+            for (row, _) in valid.iter().enumerate().filter(|(_, &v)| !v) {
+                duckdb_validity_set_row_invalid(validity, row as u64);
+            }
+        }
+        Ok(())
     }
 }
 
+/// DuckDB stores a `UUID` as a `HUGEINT` with the top bit flipped, so that
+/// UUIDs sort like their string representations.
+const UUID_SIGN_BIT: u128 = 1 << 127;
+
 /// A trait for inserting data into a vector.
 pub trait Inserter<T> {
     /// Insert a value into the vector.
@@ -129,21 +382,90 @@ impl Inserter<&[u8]> for FlatVector {
     }
 }
 
+macro_rules! primitive_inserter {
+    ($($t:ty),*) => {$(
+        /// Writes the value at `index`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `index` is out of bounds, or if the size of the value does
+        /// not match the width of the vector's elements.
+        impl Inserter<$t> for FlatVector {
+            fn insert(&self, index: usize, value: $t) {
+                assert!(index < self.capacity());
+                self.assert_width::<$t>("insert", "into");
+                unsafe { self.as_mut_ptr::<$t>().add(index).write(value) };
+            }
+        }
+    )*};
+}
+
+primitive_inserter!(bool, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+
+/// Writes the value for `Some`, and sets the row as null for `None`.
+impl<T> Inserter<Option<T>> for FlatVector
+where
+    FlatVector: Inserter<T>,
+{
+    fn insert(&self, index: usize, value: Option<T>) {
+        let validity = unsafe { duckdb_vector_get_validity(self.ptr) };
+        match value {
+            Some(value) => {
+                // the row may have been set as null before
+                if !validity.is_null() {
+                    unsafe { duckdb_validity_set_row_validity(validity, index as u64, true) };
+                }
+                self.insert(index, value);
+            }
+            None => unsafe {
+                duckdb_vector_ensure_validity_writable(self.ptr);
+                duckdb_validity_set_row_invalid(duckdb_vector_get_validity(self.ptr), index as u64);
+            },
+        }
+    }
+}
+
+/// Writes a `UUID`, see [`FlatVector::insert_uuid`].
+#[cfg(feature = "uuid")]
+impl Inserter<uuid::Uuid> for FlatVector {
+    fn insert(&self, index: usize, value: uuid::Uuid) {
+        self.insert_uuid(index, value.as_u128());
+    }
+}
+
+/// Writes a `TIMETZ` in DuckDB's packed representation, see
+/// [`TimeWithOffset::to_bits`].
+impl Inserter<TimeWithOffset> for FlatVector {
+    fn insert(&self, index: usize, value: TimeWithOffset) {
+        self.insert(index, value.to_bits());
+    }
+}
+
 /// A list vector.
 pub struct ListVector {
     /// ListVector does not own the vector pointer.
     entries: FlatVector,
+    /// The row the next [`append_entry`](ListVector::append_entry) writes.
+    next_row: usize,
 }
 
 impl From<duckdb_vector> for ListVector {
     fn from(ptr: duckdb_vector) -> Self {
         Self {
             entries: FlatVector::from(ptr),
+            next_row: 0,
         }
     }
 }
 
 impl ListVector {
+    pub(crate) fn in_chunk(ptr: duckdb_vector, chunk: duckdb_data_chunk) -> Self {
+        Self {
+            entries: FlatVector::in_chunk(ptr, chunk),
+            next_row: 0,
+        }
+    }
+
     /// Returns the number of entries in the list vector.
     pub fn len(&self) -> usize {
         unsafe { duckdb_list_vector_get_size(self.entries.ptr) as usize }
@@ -162,9 +484,106 @@ impl ListVector {
     }
 
     /// Set primitive data to the child node.
-    pub fn set_child<T: Copy>(&self, data: &[T]) {
-        self.child(data.len()).copy(data);
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` as [`FlatVector::copy`] does.
+    pub fn set_child<T: Copy>(&self, data: &[T]) -> Result<()> {
+        self.child(data.len()).copy(data)?;
         self.set_len(data.len());
+        Ok(())
+    }
+
+    /// Returns the offset and length of the list of each row, as long as the
+    /// current length of the [`DataChunk`](super::DataChunk) it belongs to.
+    ///
+    /// The lists don't have to be contiguous or in order in the child vector:
+    /// there may be gaps between them, and the entries of null rows are
+    /// meaningless.
+    pub fn entries(&self) -> &[duckdb_list_entry] {
+        unsafe { slice::from_raw_parts(self.entries.as_mut_ptr(), self.entries.len()) }
+    }
+
+    /// Returns the elements of all the lists, without reserving any capacity.
+    /// Index it with the [`entries`](ListVector::entries) of the rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `T` does not match the width of the elements.
+    pub fn child_slice<T: Copy>(&self) -> &[T] {
+        let len = self.len();
+        let child = FlatVector::with_capacity(unsafe { duckdb_list_vector_get_child(self.entries.ptr) }, len);
+        child.assert_width::<T>("read", "from");
+        if len == 0 {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(child.as_mut_ptr(), len) }
+    }
+
+    /// Writes `values` as the list of the next row, starting at row 0, by
+    /// appending them to the child vector.
+    ///
+    /// This keeps track of the offsets, so a list column can be built row by
+    /// row, e.g. with rows of lengths 0, 1 and 3, without calling
+    /// [`set_entry`](ListVector::set_entry) or [`set_len`](ListVector::set_len).
+    /// Use [`append_null`](ListVector::append_null) for null rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `T` does not match the width of the elements.
+    pub fn append_entry<T: Copy>(&mut self, values: &[T]) {
+        let offset = self.len();
+        let mut child = self.child(offset + values.len());
+        child.assert_width::<T>("append", "into");
+        if !values.is_empty() {
+            child.as_mut_slice::<T>()[offset..].copy_from_slice(values);
+            self.set_len(offset + values.len());
+        }
+        self.set_entry(self.next_row, offset, values.len());
+        self.next_row += 1;
+    }
+
+    /// Sets the next row as null, see [`append_entry`](ListVector::append_entry).
+    pub fn append_null(&mut self) {
+        self.set_entry(self.next_row, self.len(), 0);
+        self.set_null(self.next_row);
+        self.next_row += 1;
+    }
+
+    /// Returns true if the list at `row` is null. This is distinct from an
+    /// empty list, or a list of null elements.
+    pub fn row_is_null(&self, row: usize) -> bool {
+        self.entries.row_is_null(row)
+    }
+
+    /// Set the list at `row` as null.
+    pub fn set_null(&mut self, row: usize) {
+        self.entries.set_null(row);
+    }
+
+    /// Returns the offset and length of the list at `row` in the child vector.
+    pub fn get_entry(&self, row: usize) -> (usize, usize) {
+        let entry = self.entries.as_slice::<duckdb_list_entry>()[row];
+        (entry.offset as usize, entry.length as usize)
+    }
+
+    /// Returns the elements of the list at `row`, or `None` if the list is
+    /// null. Null elements are returned as `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `T` does not match the width of the elements, e.g.
+    /// reading `i64`s from an `INTEGER[]` vector.
+    pub fn get<'a, T: Copy + 'a>(&'a self, row: usize) -> Option<impl Iterator<Item = Option<T>> + 'a> {
+        if self.row_is_null(row) {
+            return None;
+        }
+        let child = FlatVector::with_capacity(unsafe { duckdb_list_vector_get_child(self.entries.ptr) }, self.len());
+        child.assert_width::<T>("read", "from");
+        let (offset, length) = self.get_entry(row);
+        assert!(offset + length <= child.capacity());
+        let data = child.as_mut_ptr::<T>();
+        Some((offset..offset + length).map(move |i| (!child.row_is_null(i)).then(|| unsafe { *data.add(i) })))
     }
 
     /// Set offset and length to the entry.
@@ -188,6 +607,183 @@ impl ListVector {
     }
 }
 
+/// A map vector. A `MAP` is stored as a list of `STRUCT(key, value)`, so
+/// the keys and values of all the maps in the vector are stored in two child
+/// vectors, and each row is an offset and length into them.
+pub struct MapVector {
+    /// MapVector does not own the vector pointer.
+    list: ListVector,
+}
+
+impl From<duckdb_vector> for MapVector {
+    fn from(ptr: duckdb_vector) -> Self {
+        Self {
+            list: ListVector::from(ptr),
+        }
+    }
+}
+
+impl MapVector {
+    /// Returns the total number of entries of the maps in the vector.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns true if the maps in the vector have no entries.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns the key vector, reserving room for `capacity` entries.
+    ///
+    /// Keys must not be null, and must be unique within each map.
+    pub fn keys(&self, capacity: usize) -> FlatVector {
+        self.entry_child(0, capacity)
+    }
+
+    /// Returns the value vector, reserving room for `capacity` entries.
+    pub fn values(&self, capacity: usize) -> FlatVector {
+        self.entry_child(1, capacity)
+    }
+
+    fn entry_child(&self, idx: u64, capacity: usize) -> FlatVector {
+        self.list.reserve(capacity);
+        unsafe {
+            let entries = duckdb_list_vector_get_child(self.list.entries.ptr);
+            FlatVector::with_capacity(duckdb_struct_vector_get_child(entries, idx), capacity)
+        }
+    }
+
+    /// Returns true if the map at `row` is null.
+    pub fn row_is_null(&self, row: usize) -> bool {
+        self.list.row_is_null(row)
+    }
+
+    /// Set the map at `row` as null.
+    pub fn set_null(&mut self, row: usize) {
+        self.list.set_null(row);
+    }
+
+    /// Returns the offset and number of entries of the map at `row` in the key
+    /// and value vectors.
+    pub fn get_entry(&self, row: usize) -> (usize, usize) {
+        self.list.get_entry(row)
+    }
+
+    /// Set the offset and number of entries of the map at `row`.
+    pub fn set_entry(&mut self, row: usize, offset: usize, length: usize) {
+        self.list.set_entry(row, offset, length);
+    }
+
+    /// Set the total number of entries of the maps in the vector.
+    pub fn set_len(&self, new_len: usize) {
+        self.list.set_len(new_len);
+    }
+}
+
+/// An `ENUM` vector. Each row is stored as the ordinal of its label, as a
+/// `UTINYINT`, `USMALLINT` or `UINTEGER` depending on the number of labels of
+/// the enum.
+pub struct EnumVector {
+    vector: FlatVector,
+    labels: Vec<String>,
+    ordinals: HashMap<String, u32>,
+    /// Size in bytes of an ordinal.
+    width: usize,
+}
+
+impl From<duckdb_vector> for EnumVector {
+    /// # Panics
+    ///
+    /// Panics if the vector isn't an `ENUM` vector.
+    fn from(ptr: duckdb_vector) -> Self {
+        let vector = FlatVector::from(ptr);
+        let logical_type = vector.logical_type();
+        let id = logical_type.id();
+        assert_eq!(id, LogicalTypeId::Enum, "not an ENUM vector: {id:?}");
+        let labels = logical_type.enum_labels();
+        let ordinals = labels.iter().enumerate().map(|(i, l)| (l.clone(), i as u32)).collect();
+        Self {
+            width: logical_type.physical_width().unwrap(),
+            vector,
+            labels,
+            ordinals,
+        }
+    }
+}
+
+impl EnumVector {
+    /// Returns the labels of the enum, in order.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Returns the ordinal of the label at `row`, or `None` if it is null.
+    pub fn get_ordinal(&self, row: usize) -> Option<u32> {
+        assert!(row < self.vector.capacity());
+        if self.vector.row_is_null(row) {
+            return None;
+        }
+        let ptr = self.vector.as_mut_ptr::<u8>();
+        Some(unsafe {
+            match self.width {
+                1 => *ptr.add(row) as u32,
+                2 => *ptr.cast::<u16>().add(row) as u32,
+                _ => *ptr.cast::<u32>().add(row),
+            }
+        })
+    }
+
+    /// Returns the label at `row`, or `None` if it is null.
+    pub fn get_label(&self, row: usize) -> Option<&str> {
+        self.get_ordinal(row)
+            .map(|ordinal| self.labels[ordinal as usize].as_str())
+    }
+
+    /// Writes the label with the given ordinal at `row`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds, or `ordinal` isn't the ordinal of a
+    /// label.
+    pub fn insert_ordinal(&self, row: usize, ordinal: u32) {
+        assert!(row < self.vector.capacity());
+        assert!(
+            (ordinal as usize) < self.labels.len(),
+            "the enum has no label with ordinal {ordinal}"
+        );
+        let ptr = self.vector.as_mut_ptr::<u8>();
+        unsafe {
+            match self.width {
+                1 => *ptr.add(row) = ordinal as u8,
+                2 => *ptr.cast::<u16>().add(row) = ordinal as u16,
+                _ => *ptr.cast::<u32>().add(row) = ordinal,
+            }
+        }
+    }
+
+    /// Writes `label` at `row`, or returns an [`Error::InvalidCoercion`] if
+    /// it isn't a label of the enum.
+    pub fn insert_label(&self, row: usize, label: &str) -> Result<()> {
+        match self.ordinals.get(label) {
+            Some(&ordinal) => {
+                self.insert_ordinal(row, ordinal);
+                Ok(())
+            }
+            None => Err(Error::InvalidCoercion(
+                Type::Text,
+                Type::Enum,
+                format!("{label:?} is not a label of the enum"),
+            )),
+        }
+    }
+
+    /// Set row as null
+    pub fn set_null(&mut self, row: usize) {
+        self.vector.set_null(row);
+    }
+}
+
 /// A array vector. (fixed-size list)
 pub struct ArrayVector {
     /// ArrayVector does not own the vector pointer.
@@ -219,8 +815,39 @@ impl ArrayVector {
     }
 
     /// Set primitive data to the child node.
-    pub fn set_child<T: Copy>(&self, data: &[T]) {
-        self.child(data.len()).copy(data);
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` as [`FlatVector::copy`] does.
+    pub fn set_child<T: Copy>(&self, data: &[T]) -> Result<()> {
+        self.child(data.len()).copy(data)
+    }
+
+    /// Returns true if the array at `row` is null. This is distinct from an
+    /// array of null elements.
+    pub fn row_is_null(&self, row: usize) -> bool {
+        FlatVector::from(self.ptr).row_is_null(row)
+    }
+
+    /// Set the array at `row` as null.
+    pub fn set_null(&mut self, row: usize) {
+        FlatVector::from(self.ptr).set_null(row);
+    }
+
+    /// Returns true if the element at `idx` in the child vector is null. The
+    /// elements of the array at `row` are at `row * array_size` onwards.
+    pub fn child_is_null(&self, idx: usize) -> bool {
+        self.child(self.child_capacity()).row_is_null(idx)
+    }
+
+    /// Set the element at `idx` in the child vector as null, see
+    /// [`child_is_null`](ArrayVector::child_is_null).
+    pub fn set_child_null(&mut self, idx: usize) {
+        self.child(self.child_capacity()).set_null(idx);
+    }
+
+    fn child_capacity(&self) -> usize {
+        self.get_array_size() as usize * unsafe { duckdb_vector_size() as usize }
     }
 }
 
@@ -279,4 +906,16 @@ impl StructVector {
         let logical_type = self.logical_type();
         unsafe { duckdb_struct_type_child_count(logical_type.ptr) as usize }
     }
+
+    /// Returns the children with their names, in order.
+    pub fn children(&self) -> impl Iterator<Item = (String, FlatVector)> + '_ {
+        (0..self.num_children()).map(|idx| (self.child_name(idx), self.child(idx)))
+    }
+
+    /// Returns the child with the given name, or `None` if there is none.
+    pub fn child_by_name(&self, name: &str) -> Option<FlatVector> {
+        (0..self.num_children())
+            .find(|&idx| self.child_name(idx) == name)
+            .map(|idx| self.child(idx))
+    }
 }